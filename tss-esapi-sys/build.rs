@@ -7,6 +7,9 @@ use std::path::PathBuf;
 const MINIMUM_VERSION: &str = "2.3.3";
 
 fn main() {
+    // Declares the values of the flag set by `set_tss_version`.
+    println!("cargo:rustc-check-cfg=cfg(tpm2_tss_version, values(\"2\", \"3\"))");
+
     if std::env::var("DOCS_RS").is_ok() {
         // Nothing to be done for docs.rs builds.
         return;
@@ -40,10 +43,11 @@ fn main() {
             .atleast_version(MINIMUM_VERSION)
             .probe("tss2-sys")
             .expect("Failed to find tss2-sys library.");
-        pkg_config::Config::new()
+        let tss2_esys = pkg_config::Config::new()
             .atleast_version(MINIMUM_VERSION)
             .probe("tss2-esys")
             .expect("Failed to find tss2-esys library.");
+        set_tss_version(&tss2_esys.version);
         pkg_config::Config::new()
             .atleast_version(MINIMUM_VERSION)
             .probe("tss2-tctildr")
@@ -74,12 +78,7 @@ pub fn generate_from_system(esapi_out: PathBuf) {
         .probe("tss2-mu")
         .expect("Failed to find tss2-mu");
 
-    // Check version to automatically set compatability flag.
-    match tss2_esys.version.chars().next().unwrap() {
-        '2' => println!("cargo:rustc-cfg=tpm2_tss_version=\"2\""),
-        '3' => println!("cargo:rustc-cfg=tpm2_tss_version=\"3\""),
-        major => panic!("Unsupported TSS version: {}", major),
-    }
+    set_tss_version(&tss2_esys.version);
 
     // These three pkg-config files should contain only one include/lib path.
     let tss2_esys_include_path = tss2_esys.include_paths[0]
@@ -115,3 +114,15 @@ pub fn generate_from_system(esapi_out: PathBuf) {
         .write_to_file(esapi_out)
         .expect("Couldn't write ESYS bindings!");
 }
+
+/// Sets the compatibility flag for this crate based on the version of
+/// the TSS libraries found on the system, and exposes the version to
+/// dependent crates through the `DEP_TSS2_ESYS_VERSION` variable.
+fn set_tss_version(version: &str) {
+    match version.chars().next().unwrap() {
+        '2' => println!("cargo:rustc-cfg=tpm2_tss_version=\"2\""),
+        '3' => println!("cargo:rustc-cfg=tpm2_tss_version=\"3\""),
+        major => panic!("Unsupported TSS version: {}", major),
+    }
+    println!("cargo:version={}", version);
+}
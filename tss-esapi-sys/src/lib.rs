@@ -60,3 +60,35 @@ include!(concat!(
 // If the "generate-bindings" feature is on, use the generated bindings.
 #[cfg(feature = "generate-bindings")]
 include!(concat!(env!("OUT_DIR"), "/tss_esapi_bindings.rs"));
+
+// The following functions were introduced in version 3 of the TSS libraries
+// and are therefore missing from the committed bindings, which target the
+// minimum supported version.
+#[cfg(all(not(feature = "generate-bindings"), tpm2_tss_version = "3"))]
+extern "C" {
+    pub fn Esys_CertifyX509(
+        esysContext: *mut ESYS_CONTEXT,
+        objectHandle: ESYS_TR,
+        signHandle: ESYS_TR,
+        shandle1: ESYS_TR,
+        shandle2: ESYS_TR,
+        shandle3: ESYS_TR,
+        reserved: *const TPM2B_DATA,
+        inScheme: *const TPMT_SIG_SCHEME,
+        partialCertificate: *const TPM2B_MAX_BUFFER,
+        addedToCertificate: *mut *mut TPM2B_MAX_BUFFER,
+        tbsDigest: *mut *mut TPM2B_DIGEST,
+        signature: *mut *mut TPMT_SIGNATURE,
+    ) -> TSS2_RC;
+
+    pub fn Esys_MAC_Start(
+        esysContext: *mut ESYS_CONTEXT,
+        handle: ESYS_TR,
+        shandle1: ESYS_TR,
+        shandle2: ESYS_TR,
+        shandle3: ESYS_TR,
+        auth: *const TPM2B_AUTH,
+        inScheme: TPM2_ALG_ID,
+        sequenceHandle: *mut ESYS_TR,
+    ) -> TSS2_RC;
+}
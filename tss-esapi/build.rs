@@ -0,0 +1,17 @@
+// Copyright 2021 Contributors to the Parsec project.
+// SPDX-License-Identifier: Apache-2.0
+
+fn main() {
+    // The version of the TSS libraries is exposed by tss-esapi-sys, which
+    // is the crate linking against them. Besides gating the commands only
+    // available in version 3, the flag selects the type of the hierarchy
+    // handles given to the ESAPI, which changed between versions 2 and 3.
+    println!("cargo:rustc-check-cfg=cfg(tpm2_tss_version, values(\"2\", \"3\"))");
+    if let Ok(version) = std::env::var("DEP_TSS2_ESYS_VERSION") {
+        match version.chars().next().unwrap() {
+            '2' => println!("cargo:rustc-cfg=tpm2_tss_version=\"2\""),
+            '3' => println!("cargo:rustc-cfg=tpm2_tss_version=\"3\""),
+            major => panic!("Unsupported TSS version: {}", major),
+        }
+    }
+}
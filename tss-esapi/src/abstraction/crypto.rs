@@ -0,0 +1,226 @@
+// Copyright 2021 Contributors to the Parsec project.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Software implementations of the cryptographic functions of the specification
+//!
+//! These are used by the abstractions computing values offline, which the
+//! TPM would otherwise compute itself.
+use crate::{interface_types::algorithm::HashingAlgorithm, Error, Result, WrapperErrorKind};
+use hmac::{Hmac, Mac, NewMac};
+use log::error;
+
+/// Calls the generic `$function` with the digest type of `$hashing_algorithm`
+macro_rules! with_digest {
+    ($hashing_algorithm:expr, $function:ident($($argument:expr),*)) => {
+        match $hashing_algorithm {
+            HashingAlgorithm::Sha1 => Ok($function::<sha1::Sha1>($($argument),*)),
+            HashingAlgorithm::Sha256 => Ok($function::<sha2::Sha256>($($argument),*)),
+            HashingAlgorithm::Sha384 => Ok($function::<sha2::Sha384>($($argument),*)),
+            HashingAlgorithm::Sha512 => Ok($function::<sha2::Sha512>($($argument),*)),
+            HashingAlgorithm::Sha3_256 => Ok($function::<sha3::Sha3_256>($($argument),*)),
+            HashingAlgorithm::Sha3_384 => Ok($function::<sha3::Sha3_384>($($argument),*)),
+            HashingAlgorithm::Sha3_512 => Ok($function::<sha3::Sha3_512>($($argument),*)),
+            hashing_algorithm => {
+                error!(
+                    "Error: Hashing algorithm {:?} is not supported in software",
+                    hashing_algorithm
+                );
+                Err(Error::local_error(WrapperErrorKind::UnsupportedParam))
+            }
+        }
+    };
+}
+pub(crate) use with_digest;
+
+/// Computes the digest of the concatenation of `data` with `hashing_algorithm`
+pub(crate) fn hash(hashing_algorithm: HashingAlgorithm, data: &[&[u8]]) -> Result<Vec<u8>> {
+    fn hash_with<D: digest::Digest>(data: &[&[u8]]) -> Vec<u8> {
+        let mut hasher = D::new();
+        for chunk in data {
+            hasher.update(chunk);
+        }
+        hasher.finalize().to_vec()
+    }
+
+    with_digest!(hashing_algorithm, hash_with(data))
+}
+
+/// Computes the HMAC of the concatenation of `data` with `hashing_algorithm`
+pub(crate) fn hmac(
+    hashing_algorithm: HashingAlgorithm,
+    key: &[u8],
+    data: &[&[u8]],
+) -> Result<Vec<u8>> {
+    fn hmac_with<D>(key: &[u8], data: &[&[u8]]) -> Vec<u8>
+    where
+        D: digest::Update
+            + digest::BlockInput
+            + digest::FixedOutput
+            + digest::Reset
+            + Default
+            + Clone,
+    {
+        let mut mac = Hmac::<D>::new_from_slice(key).expect("HMAC accepts keys of any size");
+        for chunk in data {
+            mac.update(chunk);
+        }
+        mac.finalize().into_bytes().to_vec()
+    }
+
+    with_digest!(hashing_algorithm, hmac_with(key, data))
+}
+
+/// Derives `bits` bits from `key` with KDFa, the counter mode KDF using HMAC
+// `usize::div_ceil` is not available with the minimum supported Rust version.
+#[allow(unknown_lints, clippy::manual_div_ceil)]
+pub(crate) fn kdf_a(
+    hashing_algorithm: HashingAlgorithm,
+    key: &[u8],
+    label: &str,
+    context_u: &[u8],
+    context_v: &[u8],
+    bits: u32,
+) -> Result<Vec<u8>> {
+    let size = (bits as usize + 7) / 8;
+    let mut derived = Vec::with_capacity(size);
+    let mut counter: u32 = 0;
+    while derived.len() < size {
+        counter += 1;
+        derived.extend(hmac(
+            hashing_algorithm,
+            key,
+            &[
+                &counter.to_be_bytes(),
+                label.as_bytes(),
+                &[0],
+                context_u,
+                context_v,
+                &bits.to_be_bytes(),
+            ],
+        )?);
+    }
+    derived.truncate(size);
+    Ok(derived)
+}
+
+/// Derives `bits` bits from the shared secret `z` with KDFe, the KDF used
+/// with ECDH
+// `usize::div_ceil` is not available with the minimum supported Rust version.
+#[allow(unknown_lints, clippy::manual_div_ceil)]
+pub(crate) fn kdf_e(
+    hashing_algorithm: HashingAlgorithm,
+    z: &[u8],
+    label: &str,
+    party_u: &[u8],
+    party_v: &[u8],
+    bits: u32,
+) -> Result<Vec<u8>> {
+    let size = (bits as usize + 7) / 8;
+    let mut derived = Vec::with_capacity(size);
+    let mut counter: u32 = 0;
+    while derived.len() < size {
+        counter += 1;
+        derived.extend(hash(
+            hashing_algorithm,
+            &[
+                &counter.to_be_bytes(),
+                z,
+                label.as_bytes(),
+                &[0],
+                party_u,
+                party_v,
+            ],
+        )?);
+    }
+    derived.truncate(size);
+    Ok(derived)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KEY: [u8; 32] = [
+        0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e,
+        0x0f, 0x10, 0x11, 0x12, 0x13, 0x14, 0x15, 0x16, 0x17, 0x18, 0x19, 0x1a, 0x1b, 0x1c, 0x1d,
+        0x1e, 0x1f,
+    ];
+
+    #[test]
+    fn kdf_a_single_block() {
+        assert_eq!(
+            kdf_a(HashingAlgorithm::Sha256, &KEY, "STORAGE", &[1; 4], &[], 128).unwrap(),
+            vec![
+                0x36, 0xca, 0x2f, 0x92, 0x97, 0x7c, 0x60, 0x89, 0xbc, 0xf6, 0xce, 0x4a, 0x2f, 0x30,
+                0x11, 0x0d,
+            ]
+        );
+    }
+
+    #[test]
+    fn kdf_a_several_blocks() {
+        assert_eq!(
+            kdf_a(HashingAlgorithm::Sha256, &KEY, "INTEGRITY", &[], &[], 384).unwrap(),
+            vec![
+                0x06, 0x2b, 0x7f, 0x39, 0xdf, 0xd0, 0xe1, 0xc6, 0xb0, 0x00, 0xf9, 0xa0, 0x37, 0xb2,
+                0x9c, 0xf4, 0x4a, 0x3c, 0xbb, 0xd2, 0x31, 0x66, 0x07, 0xe0, 0x4f, 0x97, 0x61, 0xa9,
+                0x1f, 0xad, 0x5a, 0x53, 0xda, 0x3f, 0x0f, 0x49, 0x8a, 0xaf, 0x86, 0xd6, 0x74, 0xa9,
+                0xbf, 0xb7, 0x27, 0xaa, 0xf0, 0x60,
+            ]
+        );
+        // The output is truncated to the last block.
+        assert_eq!(
+            kdf_a(HashingAlgorithm::Sha1, &KEY, "STORAGE", b"abc", b"def", 200).unwrap(),
+            vec![
+                0x98, 0xa2, 0x9a, 0x09, 0x7b, 0xd4, 0x49, 0x9d, 0x9a, 0xb1, 0x4e, 0xf8, 0x49, 0x47,
+                0xba, 0xbb, 0x70, 0xba, 0x2a, 0xbb, 0x30, 0xd4, 0x39, 0x8e, 0x62,
+            ]
+        );
+    }
+
+    #[test]
+    fn kdf_e_known_answers() {
+        let z: Vec<u8> = (0x20..0x40).collect();
+        let expected = vec![
+            0xf7, 0xad, 0xcd, 0xe3, 0x11, 0x94, 0x66, 0x04, 0x3a, 0x11, 0x95, 0xee, 0xfb, 0xb8,
+            0x1f, 0xe8, 0xf9, 0x22, 0xb5, 0x9a, 0xc8, 0x00, 0xaa, 0x62, 0x9d, 0x2d, 0xe0, 0x30,
+            0xe6, 0xf8, 0x00, 0x29, 0xc6, 0x05, 0x11, 0x43, 0xb1, 0x30, 0x6e, 0x7e,
+        ];
+        assert_eq!(
+            kdf_e(
+                HashingAlgorithm::Sha256,
+                &z,
+                "DUPLICATE",
+                &[0xAA; 32],
+                &[0xBB; 32],
+                320
+            )
+            .unwrap(),
+            expected
+        );
+        assert_eq!(
+            kdf_e(
+                HashingAlgorithm::Sha256,
+                &z,
+                "DUPLICATE",
+                &[0xAA; 32],
+                &[0xBB; 32],
+                128
+            )
+            .unwrap(),
+            expected[..16]
+        );
+    }
+
+    #[test]
+    fn kdf_unsupported_hashing_algorithm() {
+        assert_eq!(
+            kdf_a(HashingAlgorithm::Sm3_256, &KEY, "STORAGE", &[], &[], 128).unwrap_err(),
+            Error::WrapperError(WrapperErrorKind::UnsupportedParam)
+        );
+        assert_eq!(
+            kdf_e(HashingAlgorithm::Null, &KEY, "DUPLICATE", &[], &[], 128).unwrap_err(),
+            Error::WrapperError(WrapperErrorKind::UnsupportedParam)
+        );
+    }
+}
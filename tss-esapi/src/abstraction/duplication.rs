@@ -0,0 +1,402 @@
+// Copyright 2021 Contributors to the Parsec project.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Software creation of duplication blobs
+//!
+//! A key generated outside of a TPM can be protected by the TPM, and made
+//! persistent as a TPM-wrapped object, by creating in software the
+//! structures a TPM would produce when duplicating the key. These are then
+//! given to [Context::import](crate::Context::import) to obtain a private
+//! area loadable under the new parent.
+//!
+//! The duplicated key is protected with the outer wrapper described in the
+//! specification, the seed of which is encrypted with the public key of
+//! the new parent, and optionally with an inner wrapper.
+//!
+//! This module requires the `software-crypto` feature.
+use super::crypto::{hash, hmac, kdf_a, kdf_e, with_digest};
+use crate::{
+    constants::tss::{TPM2_ALG_ECC, TPM2_ALG_RSA},
+    interface_types::{
+        algorithm::{HashingAlgorithm, SymmetricMode},
+        ecc::EccCurve,
+        key_bits::AesKeyBits,
+    },
+    structures::{
+        Auth, Data, EccPoint, EncryptedSecret, Private, Public, SymmetricDefinitionObject,
+    },
+    tss2_esys::TPM2_ALG_ID,
+    Error, Result, WrapperErrorKind,
+};
+use cfb_mode::{
+    cipher::{AsyncStreamCipher, NewCipher},
+    Cfb,
+};
+use log::error;
+use rand::{rngs::OsRng, RngCore};
+use std::convert::TryFrom;
+use zeroize::Zeroizing;
+
+/// Structures to import a duplicated object
+///
+/// # Details
+/// The fields correspond to the arguments of
+/// [Context::import](crate::Context::import).
+#[derive(Debug, Clone)]
+pub struct DuplicationBlob {
+    /// The symmetric key of the inner wrapper, empty if there is none
+    pub encryption_key: Data,
+    /// The duplicated object
+    pub duplicate: Private,
+    /// The seed of the outer wrapper, encrypted for the new parent
+    pub in_sym_seed: EncryptedSecret,
+    /// The symmetric algorithm of the inner wrapper
+    pub symmetric_alg: SymmetricDefinitionObject,
+}
+
+/// A builder for the [DuplicationBlob] type.
+///
+/// # Details
+/// The object is described by its public area and its private key, which
+/// is one of the prime factors of the modulus for RSA keys, and the
+/// private scalar for ECC keys.
+///
+/// The new parent has to be a restricted decryption key, with an AES
+/// symmetric algorithm in CFB mode. Its seed is encrypted with RSA-OAEP for
+/// RSA parents, and with ECDH for ECC parents on the NIST P-256 curve.
+#[derive(Debug, Clone)]
+pub struct DuplicationBlobBuilder {
+    parent_public: Option<Public>,
+    object_public: Option<Public>,
+    private_key: Option<Zeroizing<Vec<u8>>>,
+    auth_value: Option<Auth>,
+    symmetric_alg: SymmetricDefinitionObject,
+}
+
+impl DuplicationBlobBuilder {
+    /// Creates a new builder, with an AES 128 bits CFB inner wrapper
+    pub const fn new() -> Self {
+        DuplicationBlobBuilder {
+            parent_public: None,
+            object_public: None,
+            private_key: None,
+            auth_value: None,
+            symmetric_alg: SymmetricDefinitionObject::AES_128_CFB,
+        }
+    }
+
+    /// Adds the public area of the new parent
+    pub fn with_parent_public(mut self, parent_public: Public) -> Self {
+        self.parent_public = Some(parent_public);
+        self
+    }
+
+    /// Adds the public area of the object to duplicate
+    pub fn with_object_public(mut self, object_public: Public) -> Self {
+        self.object_public = Some(object_public);
+        self
+    }
+
+    /// Adds the private key of the object to duplicate
+    pub fn with_private_key(mut self, private_key: &[u8]) -> Self {
+        self.private_key = Some(Zeroizing::new(private_key.to_vec()));
+        self
+    }
+
+    /// Adds the authorization value of the object to duplicate
+    pub fn with_auth_value(mut self, auth_value: Auth) -> Self {
+        self.auth_value = Some(auth_value);
+        self
+    }
+
+    /// Adds the symmetric algorithm of the inner wrapper
+    ///
+    /// # Details
+    /// No inner wrapper is applied with [SymmetricDefinitionObject::Null].
+    pub fn with_symmetric_alg(mut self, symmetric_alg: SymmetricDefinitionObject) -> Self {
+        self.symmetric_alg = symmetric_alg;
+        self
+    }
+
+    /// Creates the duplication blob
+    ///
+    /// # Errors
+    /// * if the public area of the parent or of the object, or the private key is missing,
+    ///   a `ParamsMissing` wrapper error is returned
+    /// * if the parent is not a restricted decryption key, or if the object is not an
+    ///   RSA or ECC key, an `InvalidParam` wrapper error is returned
+    /// * if an algorithm of the parent or of the inner wrapper is not supported in software,
+    ///   an `UnsupportedParam` wrapper error is returned
+    pub fn build(self) -> Result<DuplicationBlob> {
+        let parent_public = self.parent_public.ok_or_else(|| {
+            error!("Public area of the new parent is required");
+            Error::local_error(WrapperErrorKind::ParamsMissing)
+        })?;
+        let object_public = self.object_public.ok_or_else(|| {
+            error!("Public area of the object is required");
+            Error::local_error(WrapperErrorKind::ParamsMissing)
+        })?;
+        let private_key = self.private_key.ok_or_else(|| {
+            error!("Private key of the object is required");
+            Error::local_error(WrapperErrorKind::ParamsMissing)
+        })?;
+
+        let parent_attributes = parent_public.object_attributes();
+        if !parent_attributes.restricted() || !parent_attributes.decrypt() {
+            error!("The new parent has to be a restricted decryption key");
+            return Err(Error::local_error(WrapperErrorKind::InvalidParam));
+        }
+
+        let name = object_name(&object_public)?;
+        let sensitive = marshal_sensitive(
+            &object_public,
+            self.auth_value.unwrap_or_default().value(),
+            &private_key,
+        )?;
+
+        // The inner wrapper protects the sensitive area with an integrity
+        // value and a random symmetric key.
+        let (encryption_key, inner_duplicate) = match self.symmetric_alg {
+            SymmetricDefinitionObject::Null => (Data::default(), sensitive),
+            symmetric_alg => {
+                let key_bits = aes_cfb_key_bits(symmetric_alg)?;
+                let mut encryption_key = Zeroizing::new(vec![0; key_bits / 8]);
+                OsRng.fill_bytes(&mut encryption_key);
+                let inner_integrity =
+                    hash(object_public.name_hashing_algorithm(), &[&sensitive, &name])?;
+                let mut inner_duplicate = Zeroizing::new(sized(&inner_integrity));
+                inner_duplicate.extend_from_slice(&sensitive);
+                aes_cfb_encrypt(&encryption_key, &mut inner_duplicate)?;
+                (Data::try_from(encryption_key.to_vec())?, inner_duplicate)
+            }
+        };
+
+        // The outer wrapper protects the result with keys derived from a
+        // seed, which only the new parent can recover.
+        let parent_name_alg = parent_public.name_hashing_algorithm();
+        let digest_size = parent_name_alg.digest_size().ok_or_else(|| {
+            error!("Invalid name hashing algorithm of the new parent");
+            Error::local_error(WrapperErrorKind::InvalidParam)
+        })?;
+        let (seed, in_sym_seed) = match &parent_public {
+            Public::Rsa {
+                parameters, unique, ..
+            } => rsa_seed(
+                parent_name_alg,
+                digest_size,
+                unique.value(),
+                parameters.exponent().value(),
+            )?,
+            Public::Ecc {
+                parameters, unique, ..
+            } => ecc_seed(parent_name_alg, digest_size, parameters.ecc_curve(), unique)?,
+            _ => {
+                error!("The new parent has to be an RSA or ECC key");
+                return Err(Error::local_error(WrapperErrorKind::InvalidParam));
+            }
+        };
+        let parent_symmetric_alg = match &parent_public {
+            Public::Rsa { parameters, .. } => parameters.symmetric_definition_object(),
+            Public::Ecc { parameters, .. } => parameters.symmetric_definition_object(),
+            _ => SymmetricDefinitionObject::Null,
+        };
+        let parent_key_bits = aes_cfb_key_bits(parent_symmetric_alg)?;
+
+        let storage_key = Zeroizing::new(kdf_a(
+            parent_name_alg,
+            &seed,
+            "STORAGE",
+            &name,
+            &[],
+            parent_key_bits as u32,
+        )?);
+        let mut encrypted_duplicate = inner_duplicate.to_vec();
+        aes_cfb_encrypt(&storage_key, &mut encrypted_duplicate)?;
+        let integrity_key = Zeroizing::new(kdf_a(
+            parent_name_alg,
+            &seed,
+            "INTEGRITY",
+            &[],
+            &[],
+            (digest_size * 8) as u32,
+        )?);
+        let outer_hmac = hmac(
+            parent_name_alg,
+            &integrity_key,
+            &[&encrypted_duplicate, &name],
+        )?;
+        let mut duplicate = sized(&outer_hmac);
+        duplicate.extend_from_slice(&encrypted_duplicate);
+
+        Ok(DuplicationBlob {
+            encryption_key,
+            duplicate: Private::try_from(duplicate)?,
+            in_sym_seed: EncryptedSecret::try_from(in_sym_seed)?,
+            symmetric_alg: self.symmetric_alg,
+        })
+    }
+}
+
+impl Default for DuplicationBlobBuilder {
+    fn default() -> Self {
+        DuplicationBlobBuilder::new()
+    }
+}
+
+/// Prefixes `data` with its size, like a TPM2B structure
+fn sized(data: &[u8]) -> Vec<u8> {
+    let mut sized = (data.len() as u16).to_be_bytes().to_vec();
+    sized.extend_from_slice(data);
+    sized
+}
+
+/// Computes the name of an object from its public area
+fn object_name(object_public: &Public) -> Result<Vec<u8>> {
+    let name_alg = object_public.name_hashing_algorithm();
+    let mut name = TPM2_ALG_ID::from(name_alg).to_be_bytes().to_vec();
+    name.extend(hash(name_alg, &[&object_public.marshall()?])?);
+    Ok(name)
+}
+
+/// Marshals the sensitive area of an object, prefixed with its size
+fn marshal_sensitive(
+    object_public: &Public,
+    auth_value: &[u8],
+    private_key: &[u8],
+) -> Result<Zeroizing<Vec<u8>>> {
+    let sensitive_type = match object_public {
+        Public::Rsa { .. } => TPM2_ALG_RSA,
+        Public::Ecc { .. } => TPM2_ALG_ECC,
+        _ => {
+            error!("Only RSA and ECC keys can be duplicated in software");
+            return Err(Error::local_error(WrapperErrorKind::InvalidParam));
+        }
+    };
+    let mut sensitive = Zeroizing::new(sensitive_type.to_be_bytes().to_vec());
+    sensitive.extend_from_slice(&sized(auth_value));
+    // The seed value is only used by parents and derived objects.
+    sensitive.extend_from_slice(&sized(&[]));
+    sensitive.extend_from_slice(&Zeroizing::new(sized(private_key)));
+    Ok(Zeroizing::new(sized(&sensitive)))
+}
+
+/// Returns the key size of an AES CFB symmetric algorithm
+fn aes_cfb_key_bits(symmetric_alg: SymmetricDefinitionObject) -> Result<usize> {
+    match symmetric_alg {
+        SymmetricDefinitionObject::Aes {
+            key_bits,
+            mode: SymmetricMode::Cfb,
+        } => Ok(match key_bits {
+            AesKeyBits::Aes128 => 128,
+            AesKeyBits::Aes192 => 192,
+            AesKeyBits::Aes256 => 256,
+        }),
+        _ => {
+            error!(
+                "Error: Symmetric algorithm {:?} is not supported in software",
+                symmetric_alg
+            );
+            Err(Error::local_error(WrapperErrorKind::UnsupportedParam))
+        }
+    }
+}
+
+/// Encrypts `data` in place with AES in CFB mode and a zero IV
+fn aes_cfb_encrypt(key: &[u8], data: &mut [u8]) -> Result<()> {
+    let iv = [0; 16];
+    match key.len() {
+        16 => Cfb::<aes::Aes128>::new_from_slices(key, &iv)
+            .expect("Failed to create AES cipher")
+            .encrypt(data),
+        24 => Cfb::<aes::Aes192>::new_from_slices(key, &iv)
+            .expect("Failed to create AES cipher")
+            .encrypt(data),
+        32 => Cfb::<aes::Aes256>::new_from_slices(key, &iv)
+            .expect("Failed to create AES cipher")
+            .encrypt(data),
+        _ => {
+            error!("Error: Invalid AES key size ({} bytes)", key.len());
+            return Err(Error::local_error(WrapperErrorKind::InvalidParam));
+        }
+    }
+    Ok(())
+}
+
+/// Creates a random seed and encrypts it with RSA-OAEP for the new parent
+fn rsa_seed(
+    parent_name_alg: HashingAlgorithm,
+    digest_size: usize,
+    modulus: &[u8],
+    exponent: u32,
+) -> Result<(Zeroizing<Vec<u8>>, Vec<u8>)> {
+    fn padding_scheme<D>() -> rsa::PaddingScheme
+    where
+        D: 'static + digest::Digest + digest::DynDigest,
+    {
+        rsa::PaddingScheme::new_oaep_with_label::<D, _>("DUPLICATE\0")
+    }
+
+    let public_key = rsa::RsaPublicKey::new(
+        rsa::BigUint::from_bytes_be(modulus),
+        rsa::BigUint::from(if exponent == 0 { 65537 } else { exponent }),
+    )
+    .map_err(|e| {
+        error!("Invalid RSA public key of the new parent: {}", e);
+        Error::local_error(WrapperErrorKind::InvalidParam)
+    })?;
+    let mut seed = Zeroizing::new(vec![0; digest_size]);
+    OsRng.fill_bytes(&mut seed);
+    let padding = with_digest!(parent_name_alg, padding_scheme())?;
+    let encrypted_seed =
+        rsa::PublicKey::encrypt(&public_key, &mut OsRng, padding, &seed).map_err(|e| {
+            error!("Failed to encrypt the seed for the new parent: {}", e);
+            Error::local_error(WrapperErrorKind::InvalidParam)
+        })?;
+    Ok((seed, encrypted_seed))
+}
+
+/// Derives a seed from an ephemeral ECDH key exchange with the new parent,
+/// and returns it along with the marshalled ephemeral public point
+fn ecc_seed(
+    parent_name_alg: HashingAlgorithm,
+    digest_size: usize,
+    curve: EccCurve,
+    parent_point: &EccPoint,
+) -> Result<(Zeroizing<Vec<u8>>, Vec<u8>)> {
+    if curve != EccCurve::NistP256 {
+        error!("Error: ECC curve {:?} is not supported in software", curve);
+        return Err(Error::local_error(WrapperErrorKind::UnsupportedParam));
+    }
+    let parent_x = parent_point.x().value();
+    let parent_y = parent_point.y().value();
+    let mut encoded_parent_point = vec![0x04];
+    encoded_parent_point.extend_from_slice(parent_x);
+    encoded_parent_point.extend_from_slice(parent_y);
+    let parent_key = p256::PublicKey::from_sec1_bytes(&encoded_parent_point).map_err(|_| {
+        error!("Invalid ECC public key of the new parent");
+        Error::local_error(WrapperErrorKind::InvalidParam)
+    })?;
+
+    let ephemeral_secret = p256::ecdh::EphemeralSecret::random(&mut OsRng);
+    let ephemeral_point = p256::EncodedPoint::from(ephemeral_secret.public_key());
+    let shared_secret = ephemeral_secret.diffie_hellman(&parent_key);
+    let (ephemeral_x, ephemeral_y) = match (ephemeral_point.x(), ephemeral_point.y()) {
+        (Some(x), Some(y)) => (x.to_vec(), y.to_vec()),
+        _ => {
+            error!("Failed to encode the ephemeral ECC public key");
+            return Err(Error::local_error(WrapperErrorKind::InvalidParam));
+        }
+    };
+
+    let seed = Zeroizing::new(kdf_e(
+        parent_name_alg,
+        shared_secret.as_bytes(),
+        "DUPLICATE",
+        &ephemeral_x,
+        parent_x,
+        (digest_size * 8) as u32,
+    )?);
+    let mut encrypted_seed = sized(&ephemeral_x);
+    encrypted_seed.extend_from_slice(&sized(&ephemeral_y));
+    Ok((seed, encrypted_seed))
+}
@@ -3,9 +3,16 @@
 
 pub mod ak;
 pub mod cipher;
+#[cfg(feature = "software-crypto")]
+mod crypto;
+#[cfg(feature = "software-crypto")]
+pub mod duplication;
 pub mod ek;
 pub mod nv;
+pub mod policy;
+pub mod sequence;
 pub mod transient;
+pub mod x509;
 
 use crate::{attributes::ObjectAttributesBuilder, structures::PublicBuilder};
 
@@ -1,14 +1,20 @@
 // Copyright 2020 Contributors to the Parsec project.
 // SPDX-License-Identifier: Apache-2.0
 
+use log::error;
 use std::convert::TryFrom;
+use std::io::{Read, Seek, SeekFrom, Write};
 
 use crate::{
-    constants::{tss::*, CapabilityType, PropertyTag},
+    attributes::NvIndexAttributes,
+    constants::{tss::*, CapabilityType, NvIndexType, PropertyTag},
     handles::{NvIndexHandle, NvIndexTpmHandle, TpmHandle},
-    interface_types::resource_handles::NvAuth,
-    nv::storage::NvPublic,
-    structures::{CapabilityData, Name},
+    interface_types::{
+        algorithm::HashingAlgorithm,
+        resource_handles::{NvAuth, Provision},
+    },
+    nv::storage::{NvPublic, NvPublicBuilder},
+    structures::{Auth, CapabilityData, Digest, MaxNvBuffer, Name},
     Context, Error, Result, WrapperErrorKind,
 };
 
@@ -43,6 +49,265 @@ pub fn read_full(
     Ok(result)
 }
 
+/// The kind of an NV Index, along with the size of its data area
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum NvIndexKind {
+    /// Ordinary index holding `size` bytes of data
+    Ordinary { size: usize },
+    /// 64 bit monotonic counter
+    Counter,
+    /// 64 bit bit field
+    Bits,
+    /// Index holding a digest of the name algorithm, which can only be extended
+    Extend,
+    /// PIN index counting the successful authorizations
+    PinPass,
+    /// PIN index counting the failed authorizations
+    PinFail,
+}
+
+impl NvIndexKind {
+    /// Returns the [NvIndexType] of the kind
+    pub fn index_type(&self) -> NvIndexType {
+        match self {
+            NvIndexKind::Ordinary { .. } => NvIndexType::Ordinary,
+            NvIndexKind::Counter => NvIndexType::Counter,
+            NvIndexKind::Bits => NvIndexType::Bits,
+            NvIndexKind::Extend => NvIndexType::Extend,
+            NvIndexKind::PinPass => NvIndexType::PinPass,
+            NvIndexKind::PinFail => NvIndexType::PinFail,
+        }
+    }
+}
+
+/// Builds the public area of an NV Index of the given kind
+///
+/// # Details
+/// The data size of the index is derived from `kind` and `name_algorithm`,
+/// and the parameters are checked against the rules that the TPM applies
+/// when the index is defined:
+/// * the name algorithm cannot be [HashingAlgorithm::Null]
+/// * at least one of the `pp_read`, `owner_read`, `auth_read` and `policy_read`
+///   attributes, and one of the `pp_write`, `owner_write`, `auth_write` and
+///   `policy_write` attributes have to be set
+/// * PIN fail indexes have to have the `no_da` attribute
+/// * the index type in `attributes` has to match `kind`
+/// * ordinary indexes cannot be empty
+/// * counter, bits and PIN indexes are 8 bytes long, extend indexes are the
+///   size of a digest of the name algorithm
+/// * counter indexes cannot have the `clear_stclear` attribute
+/// * PIN pass and PIN fail indexes cannot have the `auth_write`, `global_lock`
+///   or `write_define` attributes
+/// * indexes with the `policy_delete` attribute have to be platform indexes
+/// * the authorization policy, if any, has to be a digest of the name algorithm
+/// * indexes authorized with a policy have to be given one
+///
+/// # Errors
+/// * if the name algorithm is [HashingAlgorithm::Null] or the size of an ordinary
+///   index is zero or does not fit in 16 bits, an `InvalidParam` wrapper error is returned
+/// * if a read or write authorization attribute is missing, or `no_da` is missing
+///   on a PIN fail index, a `ParamsMissing` wrapper error is returned
+/// * if the authorization policy has the wrong size, a `WrongParamSize` wrapper error is returned
+/// * if the attributes do not match the kind, an `InconsistentParams` wrapper error is returned
+/// * if a policy is required but missing, a `ParamsMissing` wrapper error is returned
+pub fn nv_public(
+    nv_index: NvIndexTpmHandle,
+    kind: NvIndexKind,
+    name_algorithm: HashingAlgorithm,
+    attributes: NvIndexAttributes,
+    auth_policy: Option<&Digest>,
+) -> Result<NvPublic> {
+    let digest_size = name_algorithm.digest_size().ok_or_else(|| {
+        error!("Error: The name algorithm of an NV index cannot be Null");
+        Error::local_error(WrapperErrorKind::InvalidParam)
+    })?;
+
+    attributes.validate()?;
+
+    let index_type = attributes.index_type()?;
+    if index_type != kind.index_type() {
+        error!(
+            "Error: The attributes are for a {:?} index, expected {:?}",
+            index_type,
+            kind.index_type()
+        );
+        return Err(Error::local_error(WrapperErrorKind::InconsistentParams));
+    }
+
+    let data_size = match kind {
+        NvIndexKind::Ordinary { size: 0 } => {
+            error!("Error: An ordinary index cannot be empty");
+            return Err(Error::local_error(WrapperErrorKind::InvalidParam));
+        }
+        NvIndexKind::Ordinary { size } => size,
+        NvIndexKind::Counter | NvIndexKind::Bits | NvIndexKind::PinPass | NvIndexKind::PinFail => 8,
+        NvIndexKind::Extend => digest_size,
+    };
+
+    if kind == NvIndexKind::Counter && attributes.clear_stclear() {
+        error!("Error: A counter index cannot have the clear_stclear attribute");
+        return Err(Error::local_error(WrapperErrorKind::InconsistentParams));
+    }
+
+    if matches!(kind, NvIndexKind::PinPass | NvIndexKind::PinFail)
+        && (attributes.auth_write() || attributes.global_lock() || attributes.write_define())
+    {
+        error!(
+            "Error: A PIN index cannot have the auth_write, global_lock or write_define attributes"
+        );
+        return Err(Error::local_error(WrapperErrorKind::InconsistentParams));
+    }
+
+    if attributes.policy_delete() && !attributes.platform_create() {
+        error!("Error: The policy_delete attribute requires the platform_create attribute");
+        return Err(Error::local_error(WrapperErrorKind::InconsistentParams));
+    }
+
+    let auth_policy = auth_policy.cloned().unwrap_or_default();
+    if !auth_policy.is_empty() && auth_policy.len() != digest_size {
+        error!(
+            "Error: The authorization policy is not a digest of the name algorithm ({} != {})",
+            auth_policy.len(),
+            digest_size
+        );
+        return Err(Error::local_error(WrapperErrorKind::WrongParamSize));
+    }
+
+    if (attributes.policy_read() || attributes.policy_write() || attributes.policy_delete())
+        && auth_policy.is_empty()
+    {
+        error!("Error: The attributes require an authorization policy but none was given");
+        return Err(Error::local_error(WrapperErrorKind::ParamsMissing));
+    }
+
+    NvPublicBuilder::new()
+        .with_nv_index(nv_index)
+        .with_index_name_algorithm(name_algorithm)
+        .with_index_attributes(attributes)
+        .with_index_auth_policy(&auth_policy)
+        .with_data_area_size(data_size)
+        .build()
+}
+
+/// Defines an NV Index of the given kind
+///
+/// # Details
+/// The public area of the index is built and validated with [nv_public]
+/// before the index is defined. In addition, the `platform_create`
+/// attribute has to be set if and only if `auth_handle` is
+/// [Provision::Platform].
+///
+/// # Errors
+/// * see [nv_public]
+/// * if the `platform_create` attribute does not match the `auth_handle`, an
+///   `InconsistentParams` wrapper error is returned
+#[allow(clippy::too_many_arguments)]
+pub fn define(
+    context: &mut Context,
+    auth_handle: Provision,
+    nv_index: NvIndexTpmHandle,
+    kind: NvIndexKind,
+    name_algorithm: HashingAlgorithm,
+    attributes: NvIndexAttributes,
+    auth_policy: Option<&Digest>,
+    auth: Option<&Auth>,
+) -> Result<NvIndexHandle> {
+    if attributes.platform_create() != (auth_handle == Provision::Platform) {
+        error!(
+            "Error: The platform_create attribute is {} but the index is defined under {:?}",
+            attributes.platform_create(),
+            auth_handle
+        );
+        return Err(Error::local_error(WrapperErrorKind::InconsistentParams));
+    }
+
+    let public_info = nv_public(nv_index, kind, name_algorithm, attributes, auth_policy)?;
+    context.nv_define_space(auth_handle, auth, &public_info)
+}
+
+/// Reads the value of a counter NV Index
+///
+/// # Errors
+/// * if the NV Index is not of the [NvIndexType::Counter] type, an `InvalidParam` wrapper error is returned
+pub fn read_counter(
+    context: &mut Context,
+    auth_handle: NvAuth,
+    nv_index_handle: NvIndexTpmHandle,
+) -> Result<u64> {
+    read_u64(context, auth_handle, nv_index_handle, NvIndexType::Counter)
+}
+
+/// Reads the bit field of a bits NV Index
+///
+/// # Errors
+/// * if the NV Index is not of the [NvIndexType::Bits] type, an `InvalidParam` wrapper error is returned
+pub fn read_bits(
+    context: &mut Context,
+    auth_handle: NvAuth,
+    nv_index_handle: NvIndexTpmHandle,
+) -> Result<u64> {
+    read_u64(context, auth_handle, nv_index_handle, NvIndexType::Bits)
+}
+
+/// Reads the 64 bit value of a counter or bits NV Index, after checking its type
+fn read_u64(
+    context: &mut Context,
+    auth_handle: NvAuth,
+    nv_index_handle: NvIndexTpmHandle,
+    expected_type: NvIndexType,
+) -> Result<u64> {
+    let mut object_handle =
+        context.execute_without_session(|ctx| ctx.tr_from_tpm_public(nv_index_handle.into()))?;
+    let nv_idx = NvIndexHandle::from(object_handle);
+
+    let result = context
+        .execute_without_session(|ctx| ctx.nv_read_public(nv_idx))
+        .and_then(|(nv_public, _)| {
+            let index_type = nv_public.attributes().index_type()?;
+            if index_type != expected_type {
+                error!(
+                    "Error: NV index is of type {:?}, expected {:?}",
+                    index_type, expected_type
+                );
+                return Err(Error::local_error(WrapperErrorKind::InvalidParam));
+            }
+            context.nv_read(auth_handle, nv_idx, 8, 0)
+        })
+        .and_then(|data| {
+            <[u8; 8]>::try_from(data.value())
+                .map(u64::from_be_bytes)
+                .map_err(|_| {
+                    error!("Error: NV index did not contain a 64 bit value");
+                    Error::local_error(WrapperErrorKind::WrongValueFromTpm)
+                })
+        });
+    context.execute_without_session(|ctx| ctx.tr_close(&mut object_handle))?;
+    result
+}
+
+/// The lock state of an NV Index
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct NvLockState {
+    /// Whether the NV Index has been written
+    pub written: bool,
+    /// Whether the NV Index is write locked
+    pub write_locked: bool,
+    /// Whether the NV Index is read locked
+    pub read_locked: bool,
+}
+
+/// Returns the current lock state of an NV Index
+pub fn lock_state(context: &mut Context, nv_index_handle: NvIndexTpmHandle) -> Result<NvLockState> {
+    let (nv_public, _) =
+        context.execute_without_session(|ctx| get_nv_index_info(ctx, nv_index_handle))?;
+    let attributes = nv_public.attributes();
+    Ok(NvLockState {
+        written: attributes.written(),
+        write_locked: attributes.write_locked(),
+        read_locked: attributes.read_locked(),
+    })
+}
+
 /// Returns the NvPublic and Name associated with an NV index TPM handle
 fn get_nv_index_info(
     context: &mut Context,
@@ -84,3 +349,159 @@ pub fn list(context: &mut Context) -> Result<Vec<(NvPublic, Name)>> {
         })
     })
 }
+
+/// Allows reading and writing an NV Index as a file, regardless of the max TPM NV buffer size
+///
+/// # Details
+/// The reads and writes are split into chunks of at most `NvBufferMax`
+/// bytes, as reported by the TPM. The position can be moved within the
+/// data area of the NV Index with [Seek].
+///
+/// The ESYS handle of the NV Index is closed when the object is dropped.
+#[derive(Debug)]
+pub struct NvReaderWriter<'a> {
+    context: &'a mut Context,
+    auth_handle: NvAuth,
+    nv_idx: NvIndexHandle,
+    buffer_size: usize,
+    data_size: usize,
+    offset: usize,
+}
+
+impl<'a> NvReaderWriter<'a> {
+    /// Opens the NV Index identified by `nv_index_handle`
+    ///
+    /// # Details
+    /// The `auth_handle` is used to authorize all reads and writes
+    /// of the NV Index, with the sessions set on the context.
+    pub fn open(
+        context: &'a mut Context,
+        auth_handle: NvAuth,
+        nv_index_handle: NvIndexTpmHandle,
+    ) -> Result<Self> {
+        let buffer_size = std::cmp::min(
+            context
+                .get_tpm_property(PropertyTag::NvBufferMax)?
+                .unwrap_or(512) as usize,
+            MaxNvBuffer::MAX_SIZE,
+        );
+
+        let mut object_handle = context
+            .execute_without_session(|ctx| ctx.tr_from_tpm_public(nv_index_handle.into()))?;
+        let nv_idx = NvIndexHandle::from(object_handle);
+        let data_size = match context.execute_without_session(|ctx| ctx.nv_read_public(nv_idx)) {
+            Ok((nv_public, _)) => nv_public.data_size(),
+            Err(e) => {
+                let _ = context.execute_without_session(|ctx| ctx.tr_close(&mut object_handle));
+                return Err(e);
+            }
+        };
+
+        Ok(NvReaderWriter {
+            context,
+            auth_handle,
+            nv_idx,
+            buffer_size,
+            data_size,
+            offset: 0,
+        })
+    }
+
+    /// Returns the size of the data area of the NV Index
+    pub fn data_size(&self) -> usize {
+        self.data_size
+    }
+}
+
+impl Read for NvReaderWriter<'_> {
+    // `io::Error::other` is not available with the minimum supported Rust version.
+    #[allow(unknown_lints, clippy::io_other_error)]
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        if self.offset >= self.data_size {
+            return Ok(0);
+        }
+        let size = [buf.len(), self.buffer_size, self.data_size - self.offset]
+            .iter()
+            .copied()
+            .min()
+            .unwrap_or(0);
+        if size == 0 {
+            return Ok(0);
+        }
+
+        let data = self
+            .context
+            .nv_read(
+                self.auth_handle,
+                self.nv_idx,
+                size as u16,
+                self.offset as u16,
+            )
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))?;
+        buf[..data.len()].copy_from_slice(&data);
+        self.offset += data.len();
+        Ok(data.len())
+    }
+}
+
+impl Write for NvReaderWriter<'_> {
+    // `io::Error::other` is not available with the minimum supported Rust version.
+    #[allow(unknown_lints, clippy::io_other_error)]
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        if self.offset >= self.data_size {
+            return Ok(0);
+        }
+        let size = [buf.len(), self.buffer_size, self.data_size - self.offset]
+            .iter()
+            .copied()
+            .min()
+            .unwrap_or(0);
+        if size == 0 {
+            return Ok(0);
+        }
+
+        let data = MaxNvBuffer::try_from(&buf[..size])
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))?;
+        self.context
+            .nv_write(self.auth_handle, self.nv_idx, &data, self.offset as u16)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))?;
+        self.offset += size;
+        Ok(size)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+impl Seek for NvReaderWriter<'_> {
+    fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+        let (base, offset) = match pos {
+            SeekFrom::Start(offset) => (0, offset as i64),
+            SeekFrom::End(offset) => (self.data_size as i64, offset),
+            SeekFrom::Current(offset) => (self.offset as i64, offset),
+        };
+        match base.checked_add(offset) {
+            Some(new_offset) if new_offset >= 0 => {
+                self.offset = new_offset as usize;
+                Ok(self.offset as u64)
+            }
+            _ => Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "Invalid seek to a negative or overflowing position",
+            )),
+        }
+    }
+}
+
+impl Drop for NvReaderWriter<'_> {
+    fn drop(&mut self) {
+        let mut object_handle = self.nv_idx.into();
+        if let Err(e) = self
+            .context
+            .execute_without_session(|ctx| ctx.tr_close(&mut object_handle))
+        {
+            error!("Failed to close NV index handle: {}", e);
+        }
+    }
+}
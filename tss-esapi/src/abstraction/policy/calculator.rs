@@ -0,0 +1,324 @@
+// Copyright 2021 Contributors to the Parsec project.
+// SPDX-License-Identifier: Apache-2.0
+use crate::{
+    abstraction::crypto::hash,
+    constants::{tss::*, ArithmeticOperation, StructureTag},
+    interface_types::algorithm::HashingAlgorithm,
+    structures::{AuthTicket, Digest, DigestList, Name, Nonce, Operand, PcrSelectionList, Ticket},
+    tss2_esys::{TPM2_CC, TPMA_LOCALITY, TPML_PCR_SELECTION},
+    Error, Result, WrapperErrorKind,
+};
+use log::error;
+use std::convert::TryFrom;
+
+/// Marshals a PCR selection list the way the TPM does
+fn marshal_pcr_selection_list(pcr_selection_list: PcrSelectionList) -> Vec<u8> {
+    let tss_pcr_selection_list = TPML_PCR_SELECTION::from(pcr_selection_list);
+    let mut marshalled = tss_pcr_selection_list.count.to_be_bytes().to_vec();
+    for pcr_selection in
+        &tss_pcr_selection_list.pcrSelections[..tss_pcr_selection_list.count as usize]
+    {
+        marshalled.extend_from_slice(&pcr_selection.hash.to_be_bytes());
+        marshalled.push(pcr_selection.sizeofSelect);
+        marshalled
+            .extend_from_slice(&pcr_selection.pcrSelect[..pcr_selection.sizeofSelect as usize]);
+    }
+    marshalled
+}
+
+/// Software implementation of the policy digest computations of the TPM
+///
+/// # Details
+/// The calculator holds a policy digest which is updated by its `policy_*`
+/// methods exactly like the TPM updates the digest of a trial session
+/// for the policy command of the same name. This allows computing the
+/// policy of an object without access to a TPM. The conditions of the
+/// policy are not checked.
+///
+/// Only the hashing algorithms from the SHA-1, SHA-2 and SHA-3 families
+/// are supported.
+///
+/// # Example
+///
+/// ```rust
+/// # use tss_esapi::{
+/// #     abstraction::policy::PolicyCalculator,
+/// #     constants::tss::TPM2_CC_Unseal,
+/// #     interface_types::algorithm::HashingAlgorithm,
+/// # };
+/// let mut calculator = PolicyCalculator::new(HashingAlgorithm::Sha256)?;
+/// calculator.policy_auth_value()?;
+/// calculator.policy_command_code(TPM2_CC_Unseal)?;
+/// let policy_digest = calculator.digest()?;
+/// # Ok::<(), tss_esapi::Error>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PolicyCalculator {
+    hashing_algorithm: HashingAlgorithm,
+    digest: Vec<u8>,
+}
+
+impl PolicyCalculator {
+    /// Creates a calculator starting from the empty policy
+    ///
+    /// # Errors
+    /// * if the hashing algorithm is not supported, an `UnsupportedParam` wrapper error is returned
+    pub fn new(hashing_algorithm: HashingAlgorithm) -> Result<Self> {
+        let digest_size = match hashing_algorithm.digest_size() {
+            Some(digest_size) => {
+                // Make sure the algorithm is usable before it is needed.
+                let _ = hash(hashing_algorithm, &[])?;
+                digest_size
+            }
+            None => {
+                error!("Error: A policy cannot use the Null hashing algorithm");
+                return Err(Error::local_error(WrapperErrorKind::UnsupportedParam));
+            }
+        };
+        Ok(PolicyCalculator {
+            hashing_algorithm,
+            digest: vec![0; digest_size],
+        })
+    }
+
+    /// Returns the hashing algorithm of the policy
+    pub fn hashing_algorithm(&self) -> HashingAlgorithm {
+        self.hashing_algorithm
+    }
+
+    /// Returns the current policy digest
+    pub fn digest(&self) -> Result<Digest> {
+        Digest::try_from(self.digest.clone())
+    }
+
+    /// Resets the policy digest to the empty policy, like PolicyRestart
+    pub fn reset(&mut self) {
+        self.digest = vec![0; self.digest.len()];
+    }
+
+    /// Extends the policy digest with a command code and its arguments
+    fn extend(&mut self, command_code: TPM2_CC, arguments: &[&[u8]]) -> Result<()> {
+        let command_code = command_code.to_be_bytes();
+        let mut data: Vec<&[u8]> = vec![&self.digest, &command_code];
+        data.extend_from_slice(arguments);
+        self.digest = hash(self.hashing_algorithm, &data)?;
+        Ok(())
+    }
+
+    /// Implements PolicyUpdate() from the specification, which is used by
+    /// the commands that take a policyRef
+    fn update(&mut self, command_code: TPM2_CC, name: &Name, policy_ref: &Nonce) -> Result<()> {
+        self.extend(command_code, &[name.value()])?;
+        self.digest = hash(self.hashing_algorithm, &[&self.digest, policy_ref.value()])?;
+        Ok(())
+    }
+
+    /// Computes the digest of the operand, offset and operation of
+    /// PolicyNV and PolicyCounterTimer
+    fn operation_arguments(
+        &self,
+        operand_b: &Operand,
+        offset: u16,
+        operation: ArithmeticOperation,
+    ) -> Result<Vec<u8>> {
+        hash(
+            self.hashing_algorithm,
+            &[
+                operand_b.value(),
+                &offset.to_be_bytes(),
+                &u16::from(operation).to_be_bytes(),
+            ],
+        )
+    }
+
+    /// Updates the policy like PolicySigned, for an authorization signed
+    /// by the key named `auth_object_name`
+    pub fn policy_signed(&mut self, auth_object_name: &Name, policy_ref: &Nonce) -> Result<()> {
+        self.update(TPM2_CC_PolicySigned, auth_object_name, policy_ref)
+    }
+
+    /// Updates the policy like PolicySecret, for an authorization given
+    /// by the entity named `auth_name`
+    pub fn policy_secret(&mut self, auth_name: &Name, policy_ref: &Nonce) -> Result<()> {
+        self.update(TPM2_CC_PolicySecret, auth_name, policy_ref)
+    }
+
+    /// Updates the policy like PolicyTicket, for a `ticket` issued by
+    /// PolicySigned or PolicySecret for the entity named `auth_name`
+    ///
+    /// # Details
+    /// The policy is updated like it was by the command that issued the
+    /// ticket, which is determined by the tag of the ticket.
+    ///
+    /// # Errors
+    /// * if the ticket is not an authorization ticket, an `InvalidParam` wrapper error is returned
+    pub fn policy_ticket(
+        &mut self,
+        auth_name: &Name,
+        policy_ref: &Nonce,
+        ticket: &AuthTicket,
+    ) -> Result<()> {
+        let command_code = match ticket.tag() {
+            StructureTag::AuthSigned => TPM2_CC_PolicySigned,
+            StructureTag::AuthSecret => TPM2_CC_PolicySecret,
+            tag => {
+                error!("Error: {:?} is not the tag of an authorization ticket", tag);
+                return Err(Error::local_error(WrapperErrorKind::InvalidParam));
+            }
+        };
+        self.update(command_code, auth_name, policy_ref)
+    }
+
+    /// Updates the policy like PolicyOR
+    ///
+    /// # Details
+    /// The policy digest is replaced by a digest of the list, whether or not
+    /// it is a member of the list.
+    ///
+    /// # Errors
+    /// * if the list has less than 2 or more than 8 digests, a `WrongParamSize` wrapper error is returned
+    pub fn policy_or(&mut self, digest_list: &DigestList) -> Result<()> {
+        if digest_list.value().len() < DigestList::MIN_SIZE
+            || digest_list.value().len() > DigestList::MAX_SIZE
+        {
+            error!(
+                "Error: The number of digests in the list ({}) must be between {} and {}",
+                digest_list.value().len(),
+                DigestList::MIN_SIZE,
+                DigestList::MAX_SIZE
+            );
+            return Err(Error::local_error(WrapperErrorKind::WrongParamSize));
+        }
+        self.reset();
+        let digests: Vec<&[u8]> = digest_list.value().iter().map(Digest::value).collect();
+        self.extend(TPM2_CC_PolicyOR, &digests)
+    }
+
+    /// Updates the policy like PolicyPCR
+    ///
+    /// # Details
+    /// The `pcr_policy_digest` is the digest, with the hashing algorithm of
+    /// the policy, of the concatenation of the values of the selected PCRs.
+    pub fn policy_pcr(
+        &mut self,
+        pcr_policy_digest: &Digest,
+        pcr_selection_list: PcrSelectionList,
+    ) -> Result<()> {
+        let pcrs = marshal_pcr_selection_list(pcr_selection_list);
+        self.extend(TPM2_CC_PolicyPCR, &[&pcrs, pcr_policy_digest.value()])
+    }
+
+    /// Updates the policy like PolicyLocality
+    pub fn policy_locality(&mut self, locality: TPMA_LOCALITY) -> Result<()> {
+        self.extend(TPM2_CC_PolicyLocality, &[&[locality]])
+    }
+
+    /// Updates the policy like PolicyNV, for a comparison with the NV
+    /// index named `nv_index_name`
+    pub fn policy_nv(
+        &mut self,
+        operand_b: &Operand,
+        offset: u16,
+        operation: ArithmeticOperation,
+        nv_index_name: &Name,
+    ) -> Result<()> {
+        let arguments = self.operation_arguments(operand_b, offset, operation)?;
+        self.extend(TPM2_CC_PolicyNV, &[&arguments, nv_index_name.value()])
+    }
+
+    /// Updates the policy like PolicyCounterTimer
+    pub fn policy_counter_timer(
+        &mut self,
+        operand_b: &Operand,
+        offset: u16,
+        operation: ArithmeticOperation,
+    ) -> Result<()> {
+        let arguments = self.operation_arguments(operand_b, offset, operation)?;
+        self.extend(TPM2_CC_PolicyCounterTimer, &[&arguments])
+    }
+
+    /// Updates the policy like PolicyCommandCode
+    pub fn policy_command_code(&mut self, code: TPM2_CC) -> Result<()> {
+        self.extend(TPM2_CC_PolicyCommandCode, &[&code.to_be_bytes()])
+    }
+
+    /// Updates the policy like PolicyPhysicalPresence
+    pub fn policy_physical_presence(&mut self) -> Result<()> {
+        self.extend(TPM2_CC_PolicyPhysicalPresence, &[])
+    }
+
+    /// Updates the policy like PolicyCpHash
+    pub fn policy_cp_hash(&mut self, cp_hash_a: &Digest) -> Result<()> {
+        self.extend(TPM2_CC_PolicyCpHash, &[cp_hash_a.value()])
+    }
+
+    /// Updates the policy like PolicyNameHash
+    pub fn policy_name_hash(&mut self, name_hash: &Digest) -> Result<()> {
+        self.extend(TPM2_CC_PolicyNameHash, &[name_hash.value()])
+    }
+
+    /// Updates the policy like PolicyDuplicationSelect
+    pub fn policy_duplication_select(
+        &mut self,
+        object_name: &Name,
+        new_parent_name: &Name,
+        include_object: bool,
+    ) -> Result<()> {
+        if include_object {
+            self.extend(
+                TPM2_CC_PolicyDuplicationSelect,
+                &[object_name.value(), new_parent_name.value(), &[1]],
+            )
+        } else {
+            self.extend(
+                TPM2_CC_PolicyDuplicationSelect,
+                &[new_parent_name.value(), &[0]],
+            )
+        }
+    }
+
+    /// Updates the policy like PolicyAuthorize, for policies approved
+    /// by the key named `key_sign`
+    ///
+    /// # Details
+    /// The policy digest is replaced by a value that only depends on
+    /// `policy_ref` and `key_sign`.
+    pub fn policy_authorize(&mut self, policy_ref: &Nonce, key_sign: &Name) -> Result<()> {
+        self.reset();
+        self.update(TPM2_CC_PolicyAuthorize, key_sign, policy_ref)
+    }
+
+    /// Updates the policy like PolicyAuthValue
+    pub fn policy_auth_value(&mut self) -> Result<()> {
+        self.extend(TPM2_CC_PolicyAuthValue, &[])
+    }
+
+    /// Updates the policy like PolicyPassword
+    ///
+    /// # Details
+    /// The resulting digest is the same as with [PolicyCalculator::policy_auth_value].
+    pub fn policy_password(&mut self) -> Result<()> {
+        self.extend(TPM2_CC_PolicyAuthValue, &[])
+    }
+
+    /// Updates the policy like PolicyNvWritten
+    pub fn policy_nv_written(&mut self, written_set: bool) -> Result<()> {
+        self.extend(TPM2_CC_PolicyNvWritten, &[&[u8::from(written_set)]])
+    }
+
+    /// Updates the policy like PolicyTemplate
+    pub fn policy_template(&mut self, template_hash: &Digest) -> Result<()> {
+        self.extend(TPM2_CC_PolicyTemplate, &[template_hash.value()])
+    }
+
+    /// Updates the policy like PolicyAuthorizeNV, for the authorized policy
+    /// stored in the NV index named `nv_index_name`
+    ///
+    /// # Details
+    /// The policy digest is replaced by a value that only depends on
+    /// `nv_index_name`.
+    pub fn policy_authorize_nv(&mut self, nv_index_name: &Name) -> Result<()> {
+        self.reset();
+        self.extend(TPM2_CC_PolicyAuthorizeNV, &[nv_index_name.value()])
+    }
+}
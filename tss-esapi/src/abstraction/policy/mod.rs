@@ -0,0 +1,20 @@
+// Copyright 2021 Contributors to the Parsec project.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Abstractions over policy sessions and policy digests
+//!
+//! The policy calculator and the PolicyOR tree, which compute policy digests
+//! in software, require the `software-crypto` feature.
+#[cfg(feature = "software-crypto")]
+mod calculator;
+#[cfg(feature = "software-crypto")]
+mod or_tree;
+mod signed;
+mod tree;
+
+#[cfg(feature = "software-crypto")]
+pub use calculator::PolicyCalculator;
+#[cfg(feature = "software-crypto")]
+pub use or_tree::PolicyOrTree;
+pub use signed::SignedPolicy;
+pub use tree::{NoAuthorizer, Policy, PolicyAuthorizer};
@@ -0,0 +1,146 @@
+// Copyright 2021 Contributors to the Parsec project.
+// SPDX-License-Identifier: Apache-2.0
+use super::PolicyCalculator;
+use crate::{
+    interface_types::{algorithm::HashingAlgorithm, session_handles::PolicySession},
+    structures::{Digest, DigestList},
+    Context, Error, Result, WrapperErrorKind,
+};
+use log::error;
+
+/// Tree of PolicyOR assertions allowing any number of branches
+///
+/// # Details
+/// A single PolicyOR is limited to [DigestList::MAX_SIZE] branches. The
+/// tree splits the branch digests in balanced groups which are combined
+/// with PolicyOR, and recursively does the same with the digests of the
+/// groups until a single digest, the root, is left.
+///
+/// A branch is proven by satisfying its policy on a session and then
+/// issuing one PolicyOR per level of the tree, see [PolicyOrTree::execute].
+#[derive(Debug, Clone)]
+pub struct PolicyOrTree {
+    hashing_algorithm: HashingAlgorithm,
+    // The groups of digests combined at every level, starting from the branches.
+    levels: Vec<Vec<DigestList>>,
+    root: Digest,
+}
+
+impl PolicyOrTree {
+    /// Builds the tree of the given branch digests
+    ///
+    /// # Errors
+    /// * if there are less than 2 branches, a `WrongParamSize` wrapper error is returned
+    /// * if the hashing algorithm is not supported, an `UnsupportedParam` wrapper error is returned
+    pub fn new(hashing_algorithm: HashingAlgorithm, branch_digests: Vec<Digest>) -> Result<Self> {
+        if branch_digests.len() < DigestList::MIN_SIZE {
+            error!(
+                "Error: A PolicyOR tree needs at least {} branches",
+                DigestList::MIN_SIZE
+            );
+            return Err(Error::local_error(WrapperErrorKind::WrongParamSize));
+        }
+
+        let mut levels = Vec::new();
+        let mut digests = branch_digests;
+        while digests.len() > 1 {
+            // Spread the digests evenly, so that no group has less than
+            // the minimum number of digests.
+            let group_count = (digests.len() - 1) / DigestList::MAX_SIZE + 1;
+            let mut groups = Vec::with_capacity(group_count);
+            let mut remaining = digests.into_iter();
+            for group_index in 0..group_count {
+                let group_size = remaining.len() / (group_count - group_index);
+                let mut group = DigestList::new();
+                for digest in remaining.by_ref().take(group_size) {
+                    group.add(digest)?;
+                }
+                groups.push(group);
+            }
+
+            digests = groups
+                .iter()
+                .map(|group| {
+                    let mut calculator = PolicyCalculator::new(hashing_algorithm)?;
+                    calculator.policy_or(group)?;
+                    calculator.digest()
+                })
+                .collect::<Result<Vec<Digest>>>()?;
+            levels.push(groups);
+        }
+
+        Ok(PolicyOrTree {
+            hashing_algorithm,
+            levels,
+            root: digests.remove(0),
+        })
+    }
+
+    /// Returns the hashing algorithm of the tree
+    pub fn hashing_algorithm(&self) -> HashingAlgorithm {
+        self.hashing_algorithm
+    }
+
+    /// Returns the number of branches of the tree
+    pub fn branch_count(&self) -> usize {
+        self.levels[0].iter().map(|group| group.value().len()).sum()
+    }
+
+    /// Returns the policy digest of the whole tree
+    pub fn digest(&self) -> &Digest {
+        &self.root
+    }
+
+    /// Returns the digest lists of the PolicyOR assertions proving the
+    /// branch with index `branch`, from the branch to the root
+    ///
+    /// # Errors
+    /// * if there is no such branch, an `InvalidParam` wrapper error is returned
+    pub fn branch_digest_lists(&self, branch: usize) -> Result<Vec<DigestList>> {
+        if branch >= self.branch_count() {
+            error!(
+                "Error: Branch {} is out of range ({} branches)",
+                branch,
+                self.branch_count()
+            );
+            return Err(Error::local_error(WrapperErrorKind::InvalidParam));
+        }
+
+        let mut digest_lists = Vec::with_capacity(self.levels.len());
+        let mut index = branch;
+        for groups in &self.levels {
+            let mut group_index = 0;
+            for group in groups {
+                if index < group.value().len() {
+                    break;
+                }
+                index -= group.value().len();
+                group_index += 1;
+            }
+            digest_lists.push(groups[group_index].clone());
+            index = group_index;
+        }
+        Ok(digest_lists)
+    }
+
+    /// Proves the branch with index `branch` on `policy_session`
+    ///
+    /// # Details
+    /// The policy of the branch has to be satisfied on the session
+    /// beforehand. The PolicyOR assertions of every level are then issued,
+    /// after which the digest of the session is the digest of the tree.
+    ///
+    /// # Errors
+    /// * if there is no such branch, an `InvalidParam` wrapper error is returned
+    pub fn execute(
+        &self,
+        context: &mut Context,
+        policy_session: PolicySession,
+        branch: usize,
+    ) -> Result<()> {
+        for digest_list in self.branch_digest_lists(branch)? {
+            context.policy_or(policy_session, digest_list)?;
+        }
+        Ok(())
+    }
+}
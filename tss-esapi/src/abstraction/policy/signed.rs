@@ -0,0 +1,192 @@
+// Copyright 2021 Contributors to the Parsec project.
+// SPDX-License-Identifier: Apache-2.0
+use super::PolicyAuthorizer;
+use crate::{
+    constants::tss::{TPM2_RH_NULL, TPM2_ST_HASHCHECK},
+    handles::KeyHandle,
+    interface_types::{resource_handles::Hierarchy, session_handles::PolicySession},
+    structures::{
+        Digest, HashcheckTicket, MaxBuffer, Name, Nonce, Public, Signature, SignatureScheme,
+        VerifiedTicket,
+    },
+    tss2_esys::TPMT_TK_HASHCHECK,
+    Context, Error, Result, WrapperErrorKind,
+};
+use log::error;
+use serde::{Deserialize, Serialize};
+use std::convert::TryFrom;
+
+/// Computes the digest signed by the authority, aHash, with the name
+/// hashing algorithm of its key
+fn a_hash(
+    context: &mut Context,
+    authority_public: &Public,
+    approved_policy: &[u8],
+    policy_ref: &[u8],
+) -> Result<Digest> {
+    let data = MaxBuffer::try_from([approved_policy, policy_ref].concat())?;
+    let (digest, _) = context.hash(
+        &data,
+        authority_public.name_hashing_algorithm(),
+        Hierarchy::Null,
+    )?;
+    Ok(digest)
+}
+
+/// Policy approved by an authority, for PolicyAuthorize
+///
+/// # Details
+/// The authority signs the digest of a policy and a policy reference with
+/// [SignedPolicy::sign]. The resulting document holds the public area of
+/// the signing key and can be serialized and distributed to the devices.
+///
+/// On a device, once the approved policy has been satisfied on a policy
+/// session, [SignedPolicy::authorize] loads the public key of the authority,
+/// verifies the signature and issues PolicyAuthorize, after which the
+/// digest of the session is the one of a PolicyAuthorize for the key.
+///
+/// The document can also be used as a [PolicyAuthorizer] for a
+/// [Policy::Authorize](super::Policy::Authorize) assertion.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignedPolicy {
+    authority_public: Vec<u8>,
+    approved_policy: Vec<u8>,
+    policy_ref: Vec<u8>,
+    signature: Vec<u8>,
+}
+
+impl SignedPolicy {
+    /// Signs `approved_policy` and `policy_ref` with the key identified by
+    /// `key_handle`
+    ///
+    /// # Details
+    /// The digest of the approved policy and the policy reference is
+    /// computed by the TPM with the name hashing algorithm of the key, which
+    /// has to match the hashing algorithm of the signing `scheme`. The key
+    /// is authorized with the first session of the context.
+    pub fn sign(
+        context: &mut Context,
+        key_handle: KeyHandle,
+        approved_policy: &Digest,
+        policy_ref: &Nonce,
+        scheme: SignatureScheme,
+    ) -> Result<Self> {
+        let (authority_public, _, _) = context.read_public(key_handle)?;
+        let a_hash = a_hash(
+            context,
+            &authority_public,
+            approved_policy.value(),
+            policy_ref.value(),
+        )?;
+        let validation = TPMT_TK_HASHCHECK {
+            tag: TPM2_ST_HASHCHECK,
+            hierarchy: TPM2_RH_NULL,
+            digest: Default::default(),
+        };
+        let signature = context.sign(
+            key_handle,
+            &a_hash,
+            scheme.into(),
+            HashcheckTicket::try_from(validation)?,
+        )?;
+        Ok(SignedPolicy {
+            authority_public: authority_public.marshall()?,
+            approved_policy: approved_policy.value().to_vec(),
+            policy_ref: policy_ref.value().to_vec(),
+            signature: signature.marshall()?,
+        })
+    }
+
+    /// Returns the public area of the key of the authority
+    pub fn authority_public(&self) -> Result<Public> {
+        Public::unmarshall(&self.authority_public)
+    }
+
+    /// Returns the digest of the approved policy
+    pub fn approved_policy(&self) -> Result<Digest> {
+        Digest::try_from(self.approved_policy.clone())
+    }
+
+    /// Returns the policy reference
+    pub fn policy_ref(&self) -> Result<Nonce> {
+        Nonce::try_from(self.policy_ref.clone())
+    }
+
+    /// Returns the signature of the authority
+    pub fn signature(&self) -> Result<Signature> {
+        Signature::unmarshall(&self.signature)
+    }
+
+    /// Verifies the signature of the document
+    ///
+    /// # Details
+    /// The public key of the authority is loaded in the owner hierarchy,
+    /// so that the TPM produces a ticket usable with PolicyAuthorize, and
+    /// is flushed once the signature has been verified.
+    ///
+    /// Returns the name of the key of the authority along with the ticket.
+    ///
+    /// # Errors
+    /// * if the signature is invalid, a TPM error is returned
+    pub fn verify(&self, context: &mut Context) -> Result<(Name, VerifiedTicket)> {
+        let authority_public = self.authority_public()?;
+        let a_hash = a_hash(
+            context,
+            &authority_public,
+            &self.approved_policy,
+            &self.policy_ref,
+        )?;
+        let signature = self.signature()?;
+
+        let key_handle = context.load_external_public(&authority_public, Hierarchy::Owner)?;
+        let result = context.tr_get_name(key_handle.into()).and_then(|key_name| {
+            context
+                .verify_signature(key_handle, &a_hash, signature)
+                .map(|ticket| (key_name, ticket))
+        });
+        context.flush_context(key_handle.into())?;
+        result
+    }
+
+    /// Issues PolicyAuthorize for the approved policy on `policy_session`
+    ///
+    /// # Details
+    /// The approved policy has to be satisfied on the session beforehand.
+    ///
+    /// # Errors
+    /// * if the signature is invalid, or if the digest of the session is not
+    ///   the approved policy, a TPM error is returned
+    pub fn authorize(&self, context: &mut Context, policy_session: PolicySession) -> Result<()> {
+        let (key_name, ticket) = self.verify(context)?;
+        context.policy_authorize(
+            policy_session,
+            &self.approved_policy()?,
+            &self.policy_ref()?,
+            &key_name,
+            ticket,
+        )
+    }
+}
+
+impl PolicyAuthorizer for SignedPolicy {
+    fn policy_authorize(
+        &mut self,
+        context: &mut Context,
+        approved_policy: &Digest,
+        policy_ref: &Nonce,
+        key_name: &Name,
+    ) -> Result<VerifiedTicket> {
+        if approved_policy.value() != self.approved_policy.as_slice()
+            || policy_ref.value() != self.policy_ref.as_slice()
+        {
+            error!("Error: The signed policy does not approve the executed policy");
+            return Err(Error::local_error(WrapperErrorKind::InconsistentParams));
+        }
+        let (authority_name, ticket) = self.verify(context)?;
+        if &authority_name != key_name {
+            error!("Error: The signed policy was not signed by the expected key");
+            return Err(Error::local_error(WrapperErrorKind::InconsistentParams));
+        }
+        Ok(ticket)
+    }
+}
@@ -0,0 +1,480 @@
+// Copyright 2021 Contributors to the Parsec project.
+// SPDX-License-Identifier: Apache-2.0
+#[cfg(feature = "software-crypto")]
+use crate::{
+    abstraction::policy::{PolicyCalculator, PolicyOrTree},
+    constants::tss::{TPM2_RH_ENDORSEMENT, TPM2_RH_LOCKOUT, TPM2_RH_OWNER, TPM2_RH_PLATFORM},
+    handles::{AuthHandle, NvIndexHandle},
+    interface_types::{
+        algorithm::HashingAlgorithm, resource_handles::NvAuth, session_handles::PolicySession,
+    },
+    tss2_esys::TPM2_HANDLE,
+};
+use crate::{
+    constants::{ArithmeticOperation, CommandCode},
+    handles::{NvIndexTpmHandle, ObjectHandle, TpmHandle},
+    interface_types::resource_handles::Provision,
+    structures::{Digest, Name, Nonce, Operand, PcrSelectionList, Signature, VerifiedTicket},
+    Context, Error, Result, WrapperErrorKind,
+};
+use log::error;
+use serde::{Deserialize, Serialize};
+
+/// Policy tree, made of AND-chains and OR branches of policy assertions
+///
+/// # Details
+/// The tree only holds plain data, so that it can be serialized and stored
+/// along with the objects it protects. Entities are referred to by their
+/// TPM handles and names.
+///
+/// The digest of the tree is computed in software with
+/// [Policy::digest], and the tree is satisfied on a policy session with
+/// [Policy::execute]. The values which cannot be stored in the tree, such
+/// as signatures, are provided at execution time by a [PolicyAuthorizer].
+///
+/// Both methods require the `software-crypto` feature, as executing an OR
+/// node requires the digests of all its branches, which are computed in
+/// software.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Policy {
+    /// All the policies have to be satisfied, in order
+    And(Vec<Policy>),
+    /// One of the policies has to be satisfied, combined with PolicyOR
+    ///
+    /// More than [DigestList::MAX_SIZE](crate::structures::DigestList::MAX_SIZE)
+    /// policies are combined with a
+    /// [PolicyOrTree].
+    Or(Vec<Policy>),
+    /// PolicyPCR, with the selected PCRs and the digest of their expected
+    /// values
+    Pcr {
+        pcr_selection_list: PcrSelectionList,
+        pcr_digest: Digest,
+    },
+    /// PolicyCommandCode
+    CommandCode(CommandCode),
+    /// PolicyAuthValue
+    AuthValue,
+    /// PolicySecret, authorized by the entity with the given handle and name
+    Secret {
+        auth_handle: TpmHandle,
+        auth_name: Name,
+        policy_ref: Nonce,
+    },
+    /// PolicySigned, authorized by a signature of the key with the given name
+    Signed { key_name: Name, policy_ref: Nonce },
+    /// PolicyNV, comparing the contents of an NV index with an operand
+    ///
+    /// The index is read with the authorization of `auth_handle`, or with
+    /// its own authorization if `auth_handle` is `None`.
+    Nv {
+        auth_handle: Option<Provision>,
+        nv_index: NvIndexTpmHandle,
+        nv_index_name: Name,
+        operand_b: Operand,
+        offset: u16,
+        operation: ArithmeticOperation,
+    },
+    /// PolicyAuthorize, approving the policy executed before it with the
+    /// key with the given name
+    Authorize { key_name: Name, policy_ref: Nonce },
+}
+
+/// Provides the values of a policy which can only be produced when it
+/// is executed
+///
+/// # Details
+/// The default implementations return a `ParamsMissing` wrapper error, so
+/// that only the methods needed by a policy have to be implemented.
+pub trait PolicyAuthorizer {
+    /// Returns the handle of the loaded key named `key_name` and its
+    /// signature of `signed_data`, for PolicySigned
+    ///
+    /// # Details
+    /// The `signed_data` is the concatenation of an empty nonceTPM, an
+    /// expiration of 0, an empty cpHashA and the policy reference. It has
+    /// to be hashed with the hashing algorithm of the signing scheme.
+    fn policy_signed(
+        &mut self,
+        _context: &mut Context,
+        _key_name: &Name,
+        _signed_data: &[u8],
+    ) -> Result<(ObjectHandle, Signature)> {
+        error!("Error: No authorizer available for PolicySigned");
+        Err(Error::local_error(WrapperErrorKind::ParamsMissing))
+    }
+
+    /// Returns the ticket proving that the key named `key_name` approved
+    /// `approved_policy` with `policy_ref`, for PolicyAuthorize
+    fn policy_authorize(
+        &mut self,
+        _context: &mut Context,
+        _approved_policy: &Digest,
+        _policy_ref: &Nonce,
+        _key_name: &Name,
+    ) -> Result<VerifiedTicket> {
+        error!("Error: No authorizer available for PolicyAuthorize");
+        Err(Error::local_error(WrapperErrorKind::ParamsMissing))
+    }
+}
+
+/// Authorizer for policies without PolicySigned or PolicyAuthorize assertions
+#[derive(Debug, Copy, Clone)]
+pub struct NoAuthorizer;
+impl PolicyAuthorizer for NoAuthorizer {}
+
+/// Builds the PolicyOR tree of the branches of an OR policy
+#[cfg(feature = "software-crypto")]
+fn or_tree(policies: &[Policy], calculator: &PolicyCalculator) -> Result<PolicyOrTree> {
+    let branch_digests = policies
+        .iter()
+        .map(|policy| {
+            let mut branch_calculator = calculator.clone();
+            policy.calculate(&mut branch_calculator)?;
+            branch_calculator.digest()
+        })
+        .collect::<Result<Vec<Digest>>>()?;
+    PolicyOrTree::new(calculator.hashing_algorithm(), branch_digests)
+}
+
+/// State of the execution of a policy
+///
+/// # Details
+/// The branches of the OR nodes are recorded in the order the nodes are met.
+#[cfg(feature = "software-crypto")]
+#[derive(Debug, Default)]
+struct Execution {
+    /// Command the session is meant to authorize, if known
+    command_code: Option<CommandCode>,
+    /// Branches to start from, which replay the path of the previous attempt
+    forced: Vec<usize>,
+    /// Branches taken in the current attempt
+    taken: Vec<usize>,
+    /// Branches to start from in the next attempt, set when a branch failed
+    /// after it changed the policy digest of the session
+    retry: Option<Vec<usize>>,
+}
+
+/// Returns the ESYS handle of an entity given its TPM handle, and whether
+/// it has to be closed after use
+#[cfg(feature = "software-crypto")]
+fn auth_handle(context: &mut Context, handle: TpmHandle) -> Result<(AuthHandle, bool)> {
+    match TPM2_HANDLE::from(handle) {
+        TPM2_RH_OWNER => Ok((AuthHandle::Owner, false)),
+        TPM2_RH_ENDORSEMENT => Ok((AuthHandle::Endorsement, false)),
+        TPM2_RH_PLATFORM => Ok((AuthHandle::Platform, false)),
+        TPM2_RH_LOCKOUT => Ok((AuthHandle::Lockout, false)),
+        _ => Ok((context.tr_from_tpm_public(handle)?.into(), true)),
+    }
+}
+
+#[cfg(feature = "software-crypto")]
+impl Policy {
+    /// Computes the digest of the policy
+    pub fn digest(&self, hashing_algorithm: HashingAlgorithm) -> Result<Digest> {
+        let mut calculator = PolicyCalculator::new(hashing_algorithm)?;
+        self.calculate(&mut calculator)?;
+        calculator.digest()
+    }
+
+    /// Satisfies the policy on `policy_session`, for the command with the
+    /// given `command_code` if it is known
+    ///
+    /// # Details
+    /// The session is restarted and the policy commands are issued depth
+    /// first. For every OR node, the branches are tried in order and the
+    /// first one that is satisfied is used. Branches requiring a command
+    /// other than `command_code` are skipped.
+    ///
+    /// The session cannot be rolled back, so when a branch fails after one
+    /// of its assertions succeeded, the session is restarted and the
+    /// branches taken before are issued again before the next branch is
+    /// tried. The assertions which are likely to fail, like PolicyPCR,
+    /// should therefore come first in a branch.
+    ///
+    /// PolicyCommandCode and PolicyAuthValue only fail when the session is
+    /// used, so a branch made of them is always satisfied here. Without a
+    /// `command_code`, the first branch which is not otherwise restricted is
+    /// used. The `hashing_algorithm` has to be the one of the session.
+    ///
+    /// Secret and NV assertions are authorized with the sessions set on
+    /// the context.
+    ///
+    /// # Errors
+    /// * if the policy cannot be satisfied, the error of the assertion that failed is returned
+    /// * if no branch of an OR node allows `command_code`, an `InvalidParam` wrapper error is returned
+    pub fn execute(
+        &self,
+        context: &mut Context,
+        policy_session: PolicySession,
+        hashing_algorithm: HashingAlgorithm,
+        command_code: Option<CommandCode>,
+        authorizer: &mut dyn PolicyAuthorizer,
+    ) -> Result<()> {
+        let mut execution = Execution {
+            command_code,
+            ..Default::default()
+        };
+        loop {
+            let mut calculator = PolicyCalculator::new(hashing_algorithm)?;
+            context.policy_restart(policy_session)?;
+            let result = self.satisfy(
+                context,
+                policy_session,
+                &mut calculator,
+                authorizer,
+                &mut execution,
+            );
+            match execution.retry.take() {
+                Some(forced) if result.is_err() => {
+                    execution.forced = forced;
+                    execution.taken.clear();
+                }
+                _ => return result,
+            }
+        }
+    }
+
+    /// Returns whether the policy can authorize the command with the given
+    /// `command_code`
+    fn allows_command(&self, command_code: CommandCode) -> bool {
+        match self {
+            Policy::And(policies) => policies
+                .iter()
+                .all(|policy| policy.allows_command(command_code)),
+            Policy::Or(policies) => policies
+                .iter()
+                .any(|policy| policy.allows_command(command_code)),
+            Policy::CommandCode(code) => *code == command_code,
+            _ => true,
+        }
+    }
+
+    /// Updates the calculator with the digest of the policy
+    fn calculate(&self, calculator: &mut PolicyCalculator) -> Result<()> {
+        match self {
+            Policy::And(policies) => policies
+                .iter()
+                .try_for_each(|policy| policy.calculate(calculator)),
+            Policy::Or(policies) => {
+                // Any branch leads to the digest of the tree.
+                or_tree(policies, calculator)?
+                    .branch_digest_lists(0)?
+                    .iter()
+                    .try_for_each(|digest_list| calculator.policy_or(digest_list))
+            }
+            Policy::Pcr {
+                pcr_selection_list,
+                pcr_digest,
+            } => calculator.policy_pcr(pcr_digest, pcr_selection_list.clone()),
+            Policy::CommandCode(code) => calculator.policy_command_code((*code).into()),
+            Policy::AuthValue => calculator.policy_auth_value(),
+            Policy::Secret {
+                auth_name,
+                policy_ref,
+                ..
+            } => calculator.policy_secret(auth_name, policy_ref),
+            Policy::Signed {
+                key_name,
+                policy_ref,
+            } => calculator.policy_signed(key_name, policy_ref),
+            Policy::Nv {
+                nv_index_name,
+                operand_b,
+                offset,
+                operation,
+                ..
+            } => calculator.policy_nv(operand_b, *offset, *operation, nv_index_name),
+            Policy::Authorize {
+                key_name,
+                policy_ref,
+            } => calculator.policy_authorize(policy_ref, key_name),
+        }
+    }
+
+    /// Issues the policy commands satisfying the policy, and updates the
+    /// calculator, which holds the digest expected in the session, with
+    /// the digest of the policy
+    fn satisfy(
+        &self,
+        context: &mut Context,
+        policy_session: PolicySession,
+        calculator: &mut PolicyCalculator,
+        authorizer: &mut dyn PolicyAuthorizer,
+        execution: &mut Execution,
+    ) -> Result<()> {
+        match self {
+            Policy::And(policies) => {
+                return policies.iter().try_for_each(|policy| {
+                    policy.satisfy(context, policy_session, calculator, authorizer, execution)
+                })
+            }
+            Policy::Or(policies) => {
+                return Policy::satisfy_or(
+                    policies,
+                    context,
+                    policy_session,
+                    calculator,
+                    authorizer,
+                    execution,
+                )
+            }
+            Policy::Pcr {
+                pcr_selection_list,
+                pcr_digest,
+            } => context.policy_pcr(policy_session, pcr_digest, pcr_selection_list.clone())?,
+            Policy::CommandCode(code) => {
+                context.policy_command_code(policy_session, (*code).into())?
+            }
+            Policy::AuthValue => context.policy_auth_value(policy_session)?,
+            Policy::Secret {
+                auth_handle: tpm_auth_handle,
+                policy_ref,
+                ..
+            } => {
+                let (auth_handle, close) = auth_handle(context, *tpm_auth_handle)?;
+                let result = context.policy_secret(
+                    policy_session,
+                    auth_handle,
+                    Nonce::default(),
+                    Digest::default(),
+                    policy_ref.clone(),
+                    None,
+                );
+                if close {
+                    context.tr_close(&mut auth_handle.into())?;
+                }
+                let _ = result?;
+            }
+            Policy::Signed {
+                key_name,
+                policy_ref,
+            } => {
+                let mut signed_data = vec![0; 4];
+                signed_data.extend_from_slice(policy_ref.value());
+                let (key_handle, signature) =
+                    authorizer.policy_signed(context, key_name, &signed_data)?;
+                let _ = context.policy_signed(
+                    policy_session,
+                    key_handle,
+                    Nonce::default(),
+                    Digest::default(),
+                    policy_ref.clone(),
+                    None,
+                    signature,
+                )?;
+            }
+            Policy::Nv {
+                auth_handle,
+                nv_index,
+                operand_b,
+                offset,
+                operation,
+                ..
+            } => {
+                let mut nv_index_handle =
+                    context.tr_from_tpm_public(TpmHandle::NvIndex(*nv_index))?;
+                let nv_auth = match auth_handle {
+                    Some(Provision::Owner) => NvAuth::Owner,
+                    Some(Provision::Platform) => NvAuth::Platform,
+                    None => NvAuth::NvIndex(NvIndexHandle::from(nv_index_handle)),
+                };
+                let result = context.policy_nv(
+                    policy_session,
+                    nv_auth,
+                    NvIndexHandle::from(nv_index_handle),
+                    operand_b.clone(),
+                    *offset,
+                    *operation,
+                );
+                context.tr_close(&mut nv_index_handle)?;
+                result?
+            }
+            Policy::Authorize {
+                key_name,
+                policy_ref,
+            } => {
+                let approved_policy = context.policy_get_digest(policy_session)?;
+                let check_ticket =
+                    authorizer.policy_authorize(context, &approved_policy, policy_ref, key_name)?;
+                context.policy_authorize(
+                    policy_session,
+                    &approved_policy,
+                    policy_ref,
+                    key_name,
+                    check_ticket,
+                )?
+            }
+        }
+        self.calculate(calculator)
+    }
+
+    /// Satisfies the first satisfiable branch of an OR node, and combines
+    /// it with PolicyOR
+    ///
+    /// # Details
+    /// If a branch fails after it changed the session, the next branch to
+    /// try is recorded in the execution for the session to be restarted.
+    fn satisfy_or(
+        policies: &[Policy],
+        context: &mut Context,
+        policy_session: PolicySession,
+        calculator: &mut PolicyCalculator,
+        authorizer: &mut dyn PolicyAuthorizer,
+        execution: &mut Execution,
+    ) -> Result<()> {
+        let tree = or_tree(policies, calculator)?;
+        let initial_digest = calculator.digest()?;
+        let position = execution.taken.len();
+        let command_code = execution.command_code;
+        let allowed_branches = |first: usize| {
+            policies
+                .iter()
+                .enumerate()
+                .skip(first)
+                .filter(move |(_, policy)| match command_code {
+                    Some(code) => policy.allows_command(code),
+                    None => true,
+                })
+                .map(|(branch, _)| branch)
+        };
+        if allowed_branches(0).next().is_none() {
+            error!("Error: No branch of the OR policy allows the command");
+            return Err(Error::local_error(WrapperErrorKind::InvalidParam));
+        }
+        let first = execution.forced.get(position).copied().unwrap_or(0);
+        let mut result = Ok(());
+        for branch in allowed_branches(first) {
+            execution.taken.truncate(position);
+            execution.taken.push(branch);
+            let mut branch_calculator = calculator.clone();
+            result = policies[branch].satisfy(
+                context,
+                policy_session,
+                &mut branch_calculator,
+                authorizer,
+                execution,
+            );
+            if result.is_ok() {
+                for digest_list in tree.branch_digest_lists(branch)? {
+                    context.policy_or(policy_session, digest_list.clone())?;
+                    calculator.policy_or(&digest_list)?;
+                }
+                return Ok(());
+            }
+            if execution.retry.is_some() {
+                // A nested OR node will be retried.
+                return result;
+            }
+            if context.policy_get_digest(policy_session)? != initial_digest {
+                if let Some(next) = allowed_branches(branch + 1).next() {
+                    let mut retry = execution.taken[..position].to_vec();
+                    retry.push(next);
+                    execution.retry = Some(retry);
+                }
+                return result;
+            }
+        }
+        result
+    }
+}
@@ -0,0 +1,322 @@
+// Copyright 2021 Contributors to the Parsec project.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Streaming adapters over TPM hash, HMAC and event sequences
+//!
+//! The one-shot [Context::hash] and [Context::hmac] are limited to the
+//! size of a [MaxBuffer], and [Context::pcr_extend] requires the digests
+//! of every bank to be computed beforehand. The types in this module
+//! implement [std::io::Write] on top of the sequence commands, so that
+//! arbitrarily large inputs can be digested or measured, e.g. with
+//! [std::io::copy].
+//!
+//! The data written to a sequence is sent to the TPM in chunks no larger
+//! than the input buffer of the TPM, as reported by its
+//! [PropertyTag::InputBuffer] property.
+//!
+//! The sequence objects are created with an empty authorization value
+//! and are authorized with a password session. It replaces the first
+//! session of the context for the duration of each sequence command,
+//! while the second and third sessions are used as they are, e.g. for
+//! parameter encryption. The sessions of the context are restored once
+//! the command has been executed.
+//!
+//! If writing to a sequence fails, part of the data may already have
+//! been added to it. The sequence is then considered failed: further
+//! writes return an error, and so does completing it.
+use crate::{
+    constants::PropertyTag,
+    handles::{ObjectHandle, PcrHandle},
+    interface_types::{
+        algorithm::HashingAlgorithm, resource_handles::Hierarchy, session_handles::AuthSession,
+    },
+    structures::{Digest, DigestValues, HashcheckTicket, MaxBuffer},
+    Context, Error, Result, WrapperErrorKind,
+};
+use log::error;
+use std::convert::TryFrom;
+use std::io::{self, Write};
+
+/// State of a sequence shared by the adapters
+#[derive(Debug)]
+struct SequenceState {
+    // The handle is cleared once the sequence has been completed.
+    sequence_handle: Option<ObjectHandle>,
+    chunk_size: usize,
+    failed: bool,
+}
+
+impl SequenceState {
+    /// Starts a sequence with `start`, after having queried the size of
+    /// the input buffer of the TPM
+    fn start<F>(context: &mut Context, start: F) -> Result<Self>
+    where
+        F: FnOnce(&mut Context) -> Result<ObjectHandle>,
+    {
+        let input_buffer_size = context
+            .get_tpm_property(PropertyTag::InputBuffer)?
+            .map_or(MaxBuffer::MAX_SIZE, |size| size as usize);
+        let chunk_size = std::cmp::min(input_buffer_size, MaxBuffer::MAX_SIZE);
+        if chunk_size == 0 {
+            error!("The TPM reported an empty input buffer");
+            return Err(Error::local_error(WrapperErrorKind::WrongValueFromTpm));
+        }
+        Ok(SequenceState {
+            sequence_handle: Some(start(context)?),
+            chunk_size,
+            failed: false,
+        })
+    }
+
+    /// Returns the handle of a sequence that can still be used
+    fn handle(&self) -> Result<ObjectHandle> {
+        if self.failed {
+            error!("The sequence failed while data was being added to it");
+            return Err(Error::local_error(WrapperErrorKind::InvalidHandleState));
+        }
+        self.sequence_handle.ok_or_else(|| {
+            error!("The sequence has already been completed");
+            Error::local_error(WrapperErrorKind::InvalidHandleState)
+        })
+    }
+
+    /// Adds data to the sequence, split into chunks that fit in the input
+    /// buffer of the TPM
+    fn update(&mut self, context: &mut Context, data: &[u8]) -> Result<()> {
+        let sequence_handle = self.handle()?;
+        let chunk_size = self.chunk_size;
+        let result = with_sequence_session(context, |ctx| {
+            for chunk in data.chunks(chunk_size) {
+                ctx.sequence_update(sequence_handle, MaxBuffer::try_from(chunk)?)?;
+            }
+            Ok(())
+        });
+        if result.is_err() {
+            self.failed = true;
+        }
+        result
+    }
+
+    /// Implements [Write::write] for the sequence adapters
+    // `io::Error::other` is not available with the minimum supported Rust version.
+    #[allow(unknown_lints, clippy::io_other_error)]
+    fn write(&mut self, context: &mut Context, buf: &[u8]) -> io::Result<usize> {
+        self.update(context, buf)
+            .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+        Ok(buf.len())
+    }
+
+    /// Completes the sequence with `complete`
+    ///
+    /// # Details
+    /// The handle is only cleared if the sequence was completed, so that
+    /// it is flushed when the adapter is dropped otherwise.
+    fn complete<F, T>(&mut self, context: &mut Context, complete: F) -> Result<T>
+    where
+        F: FnOnce(&mut Context, ObjectHandle) -> Result<T>,
+    {
+        let sequence_handle = self.handle()?;
+        let result = complete(context, sequence_handle)?;
+        self.sequence_handle = None;
+        Ok(result)
+    }
+
+    /// Flushes the sequence if it was never completed
+    fn abandon(&mut self, context: &mut Context) {
+        if let Some(sequence_handle) = self.sequence_handle.take() {
+            if let Err(e) = context.flush_context(sequence_handle) {
+                error!("Failed to flush abandoned sequence: {}", e);
+            }
+        }
+    }
+}
+
+/// Executes `f` with a password session in place of the first session of
+/// the context
+fn with_sequence_session<F, T>(context: &mut Context, f: F) -> T
+where
+    F: FnOnce(&mut Context) -> T,
+{
+    let (_, session_2, session_3) = context.sessions();
+    context.execute_with_sessions((Some(AuthSession::Password), session_2, session_3), f)
+}
+
+/// Hash sequence implementing [std::io::Write]
+///
+/// # Details
+/// The sequence is flushed from the TPM if it is dropped before
+/// [HashSequence::finish] is called, or if finishing it fails.
+#[derive(Debug)]
+pub struct HashSequence<'a> {
+    context: &'a mut Context,
+    state: SequenceState,
+}
+
+impl<'a> HashSequence<'a> {
+    /// Starts a new hash sequence with the given hashing algorithm
+    pub fn new(context: &'a mut Context, hashing_algorithm: HashingAlgorithm) -> Result<Self> {
+        let state = SequenceState::start(context, |ctx| {
+            ctx.hash_sequence_start(None, hashing_algorithm)
+        })?;
+        Ok(HashSequence { context, state })
+    }
+
+    /// Completes the sequence and returns the digest of all the data
+    /// written to it
+    ///
+    /// # Details
+    /// The returned ticket is produced for the given `hierarchy`, it is a
+    /// null ticket if `hierarchy` is [Hierarchy::Null].
+    ///
+    /// # Errors
+    /// * if writing to the sequence failed, an `InvalidHandleState` wrapper error is returned
+    pub fn finish(mut self, hierarchy: Hierarchy) -> Result<(Digest, HashcheckTicket)> {
+        self.state.complete(self.context, |ctx, sequence_handle| {
+            with_sequence_session(ctx, |ctx| {
+                ctx.sequence_complete(sequence_handle, MaxBuffer::default(), hierarchy)
+            })
+        })
+    }
+}
+
+impl Write for HashSequence<'_> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.state.write(self.context, buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl Drop for HashSequence<'_> {
+    fn drop(&mut self) {
+        self.state.abandon(self.context);
+    }
+}
+
+/// HMAC sequence implementing [std::io::Write]
+///
+/// # Details
+/// The sequence is flushed from the TPM if it is dropped before
+/// [HmacSequence::finish] is called, or if finishing it fails.
+#[derive(Debug)]
+pub struct HmacSequence<'a> {
+    context: &'a mut Context,
+    state: SequenceState,
+}
+
+impl<'a> HmacSequence<'a> {
+    /// Starts a new HMAC sequence with the key identified by `key_handle`
+    ///
+    /// # Details
+    /// The key requires authorization, so a session has to be set on the
+    /// context.
+    pub fn new(
+        context: &'a mut Context,
+        key_handle: ObjectHandle,
+        hashing_algorithm: HashingAlgorithm,
+    ) -> Result<Self> {
+        let state = SequenceState::start(context, |ctx| {
+            ctx.hmac_start(key_handle, None, hashing_algorithm)
+        })?;
+        Ok(HmacSequence { context, state })
+    }
+
+    /// Completes the sequence and returns the HMAC of all the data
+    /// written to it
+    ///
+    /// # Errors
+    /// * if writing to the sequence failed, an `InvalidHandleState` wrapper error is returned
+    pub fn finish(mut self) -> Result<Digest> {
+        self.state
+            .complete(self.context, |ctx, sequence_handle| {
+                with_sequence_session(ctx, |ctx| {
+                    ctx.sequence_complete(sequence_handle, MaxBuffer::default(), Hierarchy::Null)
+                })
+            })
+            .map(|(digest, _)| digest)
+    }
+}
+
+impl Write for HmacSequence<'_> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.state.write(self.context, buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl Drop for HmacSequence<'_> {
+    fn drop(&mut self) {
+        self.state.abandon(self.context);
+    }
+}
+
+/// Event sequence implementing [std::io::Write]
+///
+/// # Details
+/// An event sequence computes a digest of the data written to it for
+/// every PCR bank allocated in the TPM, and extends these digests into a
+/// PCR when it is finished. This allows measuring data which does not fit
+/// in a single command, e.g. by using [std::io::copy].
+///
+/// The sequence is flushed from the TPM if it is dropped before
+/// [EventSequence::finish] is called, or if finishing it fails.
+#[derive(Debug)]
+pub struct EventSequence<'a> {
+    context: &'a mut Context,
+    state: SequenceState,
+}
+
+impl<'a> EventSequence<'a> {
+    /// Starts a new event sequence
+    pub fn new(context: &'a mut Context) -> Result<Self> {
+        let state = SequenceState::start(context, |ctx| {
+            ctx.hash_sequence_start(None, HashingAlgorithm::Null)
+        })?;
+        Ok(EventSequence { context, state })
+    }
+
+    /// Completes the sequence and extends the result into the PCR
+    /// identified by `pcr_handle`
+    ///
+    /// # Details
+    /// The PCR is authorized with the first session of the context, and
+    /// the sequence with a password session in place of the second one.
+    /// The returned values hold the digest extended into each bank, so
+    /// that they can be recorded in an event log.
+    ///
+    /// # Errors
+    /// * if the first session of the context is missing, a `MissingAuthSession` wrapper error will be returned
+    /// * if writing to the sequence failed, an `InvalidHandleState` wrapper error is returned
+    pub fn finish(mut self, pcr_handle: PcrHandle) -> Result<DigestValues> {
+        self.state.complete(self.context, |ctx, sequence_handle| {
+            let (pcr_session, _, session_3) = ctx.sessions();
+            ctx.execute_with_sessions(
+                (pcr_session, Some(AuthSession::Password), session_3),
+                |ctx| {
+                    ctx.event_sequence_complete(pcr_handle, sequence_handle, MaxBuffer::default())
+                },
+            )
+        })
+    }
+}
+
+impl Write for EventSequence<'_> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.state.write(self.context, buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl Drop for EventSequence<'_> {
+    fn drop(&mut self) {
+        self.state.abandon(self.context);
+    }
+}
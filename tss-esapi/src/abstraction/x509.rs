@@ -0,0 +1,591 @@
+// Copyright 2021 Contributors to the Parsec project.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Helpers for producing X.509 certificates with TPM2_CertifyX509
+//!
+//! The TPM expects the caller to provide part of the TBSCertificate
+//! (the `partialCertificate`), fills in the remaining fields itself and
+//! signs the result. The [PartialCertificateBuilder] creates the DER encoded
+//! input and [assemble_certificate] combines it with the output of the
+//! command into a complete DER encoded certificate.
+use crate::{
+    structures::{EccParameter, MaxBuffer, Signature},
+    Error, Result, WrapperErrorKind,
+};
+use log::error;
+use std::convert::TryFrom;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Object identifiers of commonly used attributes and extensions
+pub mod oid {
+    /// id-at-commonName
+    pub const COMMON_NAME: &[u64] = &[2, 5, 4, 3];
+    /// id-at-countryName
+    pub const COUNTRY_NAME: &[u64] = &[2, 5, 4, 6];
+    /// id-at-organizationName
+    pub const ORGANIZATION_NAME: &[u64] = &[2, 5, 4, 10];
+    /// id-at-organizationalUnitName
+    pub const ORGANIZATIONAL_UNIT_NAME: &[u64] = &[2, 5, 4, 11];
+    /// id-ce-keyUsage
+    pub const KEY_USAGE: &[u64] = &[2, 5, 29, 15];
+    /// tcg-tpmaObject, the extension holding the TPMA_OBJECT of the key
+    pub const TCG_TPMA_OBJECT: &[u64] = &[2, 23, 133, 10, 1, 1, 1];
+}
+
+const TAG_BOOLEAN: u8 = 0x01;
+const TAG_INTEGER: u8 = 0x02;
+const TAG_BIT_STRING: u8 = 0x03;
+const TAG_OCTET_STRING: u8 = 0x04;
+const TAG_OBJECT_IDENTIFIER: u8 = 0x06;
+const TAG_UTF8_STRING: u8 = 0x0c;
+const TAG_PRINTABLE_STRING: u8 = 0x13;
+const TAG_UTC_TIME: u8 = 0x17;
+const TAG_GENERALIZED_TIME: u8 = 0x18;
+const TAG_SEQUENCE: u8 = 0x30;
+const TAG_SET: u8 = 0x31;
+const TAG_EXTENSIONS: u8 = 0xa3;
+
+/// Structure representing a distinguished name
+///
+/// # Details
+/// The attributes are encoded in the order in which they were added,
+/// each in its own relative distinguished name.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DistinguishedName {
+    attributes: Vec<(Vec<u64>, String)>,
+}
+
+impl DistinguishedName {
+    /// Creates an empty distinguished name
+    pub fn new() -> Self {
+        DistinguishedName::default()
+    }
+
+    /// Adds an attribute with the provided object identifier
+    pub fn with_attribute(mut self, oid: &[u64], value: &str) -> Self {
+        self.attributes.push((oid.to_vec(), value.to_string()));
+        self
+    }
+
+    /// Adds a common name attribute
+    pub fn with_common_name(self, common_name: &str) -> Self {
+        self.with_attribute(oid::COMMON_NAME, common_name)
+    }
+
+    /// Adds an organization attribute
+    pub fn with_organization(self, organization: &str) -> Self {
+        self.with_attribute(oid::ORGANIZATION_NAME, organization)
+    }
+
+    /// Adds an organizational unit attribute
+    pub fn with_organizational_unit(self, organizational_unit: &str) -> Self {
+        self.with_attribute(oid::ORGANIZATIONAL_UNIT_NAME, organizational_unit)
+    }
+
+    /// Adds a country attribute
+    pub fn with_country(self, country: &str) -> Self {
+        self.with_attribute(oid::COUNTRY_NAME, country)
+    }
+
+    /// Returns the attributes of the distinguished name
+    pub fn attributes(&self) -> &[(Vec<u64>, String)] {
+        &self.attributes
+    }
+
+    fn to_der(&self) -> Result<Vec<u8>> {
+        let mut rdn_sequence = Vec::new();
+        for (attribute_oid, value) in &self.attributes {
+            // Country names are restricted to PrintableString by RFC 5280.
+            let value_tag = if attribute_oid.as_slice() == oid::COUNTRY_NAME {
+                TAG_PRINTABLE_STRING
+            } else {
+                TAG_UTF8_STRING
+            };
+            let mut attribute = encode_oid(attribute_oid)?;
+            attribute.extend(encode_tlv(value_tag, value.as_bytes()));
+            rdn_sequence.extend(encode_tlv(TAG_SET, &encode_tlv(TAG_SEQUENCE, &attribute)));
+        }
+        Ok(encode_tlv(TAG_SEQUENCE, &rdn_sequence))
+    }
+}
+
+/// Enum representing the bits of the key usage extension
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyUsage {
+    DigitalSignature,
+    NonRepudiation,
+    KeyEncipherment,
+    DataEncipherment,
+    KeyAgreement,
+    KeyCertSign,
+    CrlSign,
+    EncipherOnly,
+    DecipherOnly,
+}
+
+impl KeyUsage {
+    fn bit(self) -> usize {
+        match self {
+            KeyUsage::DigitalSignature => 0,
+            KeyUsage::NonRepudiation => 1,
+            KeyUsage::KeyEncipherment => 2,
+            KeyUsage::DataEncipherment => 3,
+            KeyUsage::KeyAgreement => 4,
+            KeyUsage::KeyCertSign => 5,
+            KeyUsage::CrlSign => 6,
+            KeyUsage::EncipherOnly => 7,
+            KeyUsage::DecipherOnly => 8,
+        }
+    }
+}
+
+/// Structure representing a certificate extension
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Extension {
+    oid: Vec<u64>,
+    critical: bool,
+    value: Vec<u8>,
+}
+
+impl Extension {
+    /// Creates an extension from its object identifier and
+    /// DER encoded value
+    pub fn new(oid: &[u64], critical: bool, value: Vec<u8>) -> Self {
+        Extension {
+            oid: oid.to_vec(),
+            critical,
+            value,
+        }
+    }
+
+    /// Creates a key usage extension
+    ///
+    /// # Details
+    /// The TPM checks that the key usage is consistent with the
+    /// attributes of the certified object.
+    ///
+    /// # Errors
+    /// * if no key usage is provided, a `ParamsMissing` wrapper error will be returned
+    pub fn key_usage(critical: bool, key_usages: &[KeyUsage]) -> Result<Self> {
+        let last_bit = key_usages
+            .iter()
+            .map(|key_usage| key_usage.bit())
+            .max()
+            .ok_or_else(|| {
+                error!("At least one key usage is required in a key usage extension");
+                Error::local_error(WrapperErrorKind::ParamsMissing)
+            })?;
+        // DER encoded named bit lists do not contain trailing zero bits.
+        let mut bits = vec![0u8; last_bit / 8 + 1];
+        for key_usage in key_usages {
+            let bit = key_usage.bit();
+            bits[bit / 8] |= 0x80 >> (bit % 8);
+        }
+        let mut content = vec![(7 - last_bit % 8) as u8];
+        content.extend(bits);
+        Ok(Extension::new(
+            oid::KEY_USAGE,
+            critical,
+            encode_tlv(TAG_BIT_STRING, &content),
+        ))
+    }
+
+    /// Returns the object identifier of the extension
+    pub fn oid(&self) -> &[u64] {
+        &self.oid
+    }
+
+    /// Returns whether the extension is critical
+    pub const fn critical(&self) -> bool {
+        self.critical
+    }
+
+    /// Returns the DER encoded value of the extension
+    pub fn value(&self) -> &[u8] {
+        &self.value
+    }
+
+    fn to_der(&self) -> Result<Vec<u8>> {
+        let mut content = encode_oid(&self.oid)?;
+        if self.critical {
+            content.extend(encode_tlv(TAG_BOOLEAN, &[0xff]));
+        }
+        content.extend(encode_tlv(TAG_OCTET_STRING, &self.value));
+        Ok(encode_tlv(TAG_SEQUENCE, &content))
+    }
+}
+
+/// Builder for the `partialCertificate` input of TPM2_CertifyX509
+///
+/// # Details
+/// The partial certificate holds the issuer, validity, subject and
+/// extensions of the certificate. The signature algorithm is left out
+/// so that the TPM fills it in based on the signing key and scheme.
+#[derive(Debug, Clone, Default)]
+pub struct PartialCertificateBuilder {
+    issuer: Option<DistinguishedName>,
+    not_before: Option<SystemTime>,
+    not_after: Option<SystemTime>,
+    subject: Option<DistinguishedName>,
+    extensions: Vec<Extension>,
+}
+
+impl PartialCertificateBuilder {
+    /// Creates a new builder
+    pub fn new() -> Self {
+        PartialCertificateBuilder::default()
+    }
+
+    /// Adds the issuer of the certificate
+    pub fn with_issuer(mut self, issuer: DistinguishedName) -> Self {
+        self.issuer = Some(issuer);
+        self
+    }
+
+    /// Adds the start of the validity period of the certificate
+    pub fn with_not_before(mut self, not_before: SystemTime) -> Self {
+        self.not_before = Some(not_before);
+        self
+    }
+
+    /// Adds the end of the validity period of the certificate
+    pub fn with_not_after(mut self, not_after: SystemTime) -> Self {
+        self.not_after = Some(not_after);
+        self
+    }
+
+    /// Adds the subject of the certificate
+    pub fn with_subject(mut self, subject: DistinguishedName) -> Self {
+        self.subject = Some(subject);
+        self
+    }
+
+    /// Adds an extension to the certificate
+    pub fn with_extension(mut self, extension: Extension) -> Self {
+        self.extensions.push(extension);
+        self
+    }
+
+    /// Builds the DER encoded partial certificate
+    ///
+    /// # Errors
+    /// * if the issuer, subject, validity or extensions are missing, a `ParamsMissing`
+    ///   wrapper error will be returned
+    /// * if the validity period ends before it starts, an `InconsistentParams` wrapper
+    ///   error will be returned
+    /// * if a time predates the UNIX epoch, an `InvalidParam` wrapper error will be returned
+    /// * if the encoded partial certificate is too large, a `WrongParamSize` wrapper error
+    ///   will be returned
+    pub fn build(self) -> Result<MaxBuffer> {
+        let issuer = self.issuer.ok_or_else(|| {
+            error!("Issuer is required in a partial certificate");
+            Error::local_error(WrapperErrorKind::ParamsMissing)
+        })?;
+        let subject = self.subject.ok_or_else(|| {
+            error!("Subject is required in a partial certificate");
+            Error::local_error(WrapperErrorKind::ParamsMissing)
+        })?;
+        let (not_before, not_after) = match (self.not_before, self.not_after) {
+            (Some(not_before), Some(not_after)) => (not_before, not_after),
+            _ => {
+                error!("Validity period is required in a partial certificate");
+                return Err(Error::local_error(WrapperErrorKind::ParamsMissing));
+            }
+        };
+        if not_after < not_before {
+            error!("Validity period of the partial certificate ends before it starts");
+            return Err(Error::local_error(WrapperErrorKind::InconsistentParams));
+        }
+        if self.extensions.is_empty() {
+            error!("At least one extension is required in a partial certificate");
+            return Err(Error::local_error(WrapperErrorKind::ParamsMissing));
+        }
+
+        let mut validity = encode_time(not_before)?;
+        validity.extend(encode_time(not_after)?);
+
+        let mut extensions = Vec::new();
+        for extension in &self.extensions {
+            extensions.extend(extension.to_der()?);
+        }
+
+        let mut content = issuer.to_der()?;
+        content.extend(encode_tlv(TAG_SEQUENCE, &validity));
+        content.extend(subject.to_der()?);
+        content.extend(encode_tlv(
+            TAG_EXTENSIONS,
+            &encode_tlv(TAG_SEQUENCE, &extensions),
+        ));
+        MaxBuffer::try_from(encode_tlv(TAG_SEQUENCE, &content))
+    }
+}
+
+/// Assembles the DER encoded certificate from the input and output of
+/// TPM2_CertifyX509
+///
+/// # Details
+/// The fields of the `partial_certificate` and of the `added_to_certificate`
+/// returned by the TPM are put in the order of a TBSCertificate, which is then
+/// combined with the signature algorithm and the `signature` over it. The
+/// `tbsDigest` returned by the TPM is not needed for this, but can be used to
+/// verify the signature.
+///
+/// # Errors
+/// * if either input is not a valid DER sequence with the expected number of
+///   fields, an `InvalidParam` wrapper error will be returned
+/// * if the signature algorithm is present in both or none of the inputs, an
+///   `InconsistentParams` wrapper error will be returned
+/// * if the signature is not an RSA or ECC signature, an `UnsupportedParam`
+///   wrapper error will be returned
+pub fn assemble_certificate(
+    partial_certificate: &MaxBuffer,
+    added_to_certificate: &MaxBuffer,
+    signature: &Signature,
+) -> Result<Vec<u8>> {
+    // partialCertificate: [signature], issuer, validity, subject, extensions
+    let partial_fields = decode_sequence(partial_certificate.value())?;
+    // addedToCertificate: version, serialNumber, [signature], subjectPublicKeyInfo
+    let added_fields = decode_sequence(added_to_certificate.value())?;
+
+    let (signature_algorithm, issuer, validity, subject, extensions) =
+        match partial_fields.as_slice() {
+            [issuer, validity, subject, extensions] => {
+                (None, *issuer, *validity, *subject, *extensions)
+            }
+            [signature_algorithm, issuer, validity, subject, extensions] => (
+                Some(*signature_algorithm),
+                *issuer,
+                *validity,
+                *subject,
+                *extensions,
+            ),
+            _ => {
+                error!(
+                    "Unexpected number of fields in partial certificate: {}",
+                    partial_fields.len()
+                );
+                return Err(Error::local_error(WrapperErrorKind::InvalidParam));
+            }
+        };
+    let (version, serial_number, added_signature_algorithm, subject_public_key_info) =
+        match added_fields.as_slice() {
+            [version, serial_number, subject_public_key_info] => {
+                (*version, *serial_number, None, *subject_public_key_info)
+            }
+            [version, serial_number, signature_algorithm, subject_public_key_info] => (
+                *version,
+                *serial_number,
+                Some(*signature_algorithm),
+                *subject_public_key_info,
+            ),
+            _ => {
+                error!(
+                    "Unexpected number of fields in the fields added to the certificate: {}",
+                    added_fields.len()
+                );
+                return Err(Error::local_error(WrapperErrorKind::InvalidParam));
+            }
+        };
+    let signature_algorithm = match (signature_algorithm, added_signature_algorithm) {
+        (Some(signature_algorithm), None) | (None, Some(signature_algorithm)) => {
+            signature_algorithm
+        }
+        _ => {
+            error!("The signature algorithm must be provided by exactly one of the inputs");
+            return Err(Error::local_error(WrapperErrorKind::InconsistentParams));
+        }
+    };
+
+    let mut tbs_certificate = Vec::new();
+    for field in &[
+        version,
+        serial_number,
+        signature_algorithm,
+        issuer,
+        validity,
+        subject,
+        subject_public_key_info,
+        extensions,
+    ] {
+        tbs_certificate.extend_from_slice(field);
+    }
+
+    let mut signature_value = vec![0x00];
+    signature_value.extend(encode_signature(signature)?);
+
+    let mut certificate = encode_tlv(TAG_SEQUENCE, &tbs_certificate);
+    certificate.extend_from_slice(signature_algorithm);
+    certificate.extend(encode_tlv(TAG_BIT_STRING, &signature_value));
+    Ok(encode_tlv(TAG_SEQUENCE, &certificate))
+}
+
+fn encode_signature(signature: &Signature) -> Result<Vec<u8>> {
+    match signature {
+        Signature::RsaSsa(rsa_signature) | Signature::RsaPss(rsa_signature) => {
+            Ok(rsa_signature.signature().value().to_vec())
+        }
+        Signature::EcDsa(ecc_signature) | Signature::Sm2(ecc_signature) => {
+            let mut content = encode_integer(ecc_signature.signature_r());
+            content.extend(encode_integer(ecc_signature.signature_s()));
+            Ok(encode_tlv(TAG_SEQUENCE, &content))
+        }
+        _ => {
+            error!(
+                "Signature algorithm {:?} is not supported in certificates",
+                signature.algorithm()
+            );
+            Err(Error::local_error(WrapperErrorKind::UnsupportedParam))
+        }
+    }
+}
+
+fn encode_integer(ecc_parameter: &EccParameter) -> Vec<u8> {
+    let value = ecc_parameter.value();
+    let start = value
+        .iter()
+        .position(|byte| *byte != 0)
+        .unwrap_or(value.len());
+    let mut content = Vec::with_capacity(value.len() - start + 1);
+    // Integers are signed, so a leading zero is needed when the
+    // most significant bit is set.
+    if start == value.len() || value[start] & 0x80 != 0 {
+        content.push(0x00);
+    }
+    content.extend_from_slice(&value[start..]);
+    encode_tlv(TAG_INTEGER, &content)
+}
+
+fn encode_time(time: SystemTime) -> Result<Vec<u8>> {
+    let seconds = time
+        .duration_since(UNIX_EPOCH)
+        .map_err(|_| {
+            error!("Certificate validity times before the UNIX epoch are not supported");
+            Error::local_error(WrapperErrorKind::InvalidParam)
+        })?
+        .as_secs();
+    let (year, month, day) = civil_from_days(seconds / 86400);
+    let seconds_of_day = seconds % 86400;
+    let time_of_day = format!(
+        "{:02}{:02}{:02}{:02}{:02}Z",
+        month,
+        day,
+        seconds_of_day / 3600,
+        seconds_of_day % 3600 / 60,
+        seconds_of_day % 60
+    );
+    // RFC 5280 requires UTCTime for dates through the year 2049.
+    if year < 2050 {
+        Ok(encode_tlv(
+            TAG_UTC_TIME,
+            format!("{:02}{}", year % 100, time_of_day).as_bytes(),
+        ))
+    } else {
+        Ok(encode_tlv(
+            TAG_GENERALIZED_TIME,
+            format!("{:04}{}", year, time_of_day).as_bytes(),
+        ))
+    }
+}
+
+// Converts a number of days since the UNIX epoch into a date
+// in the proleptic Gregorian calendar.
+fn civil_from_days(days: u64) -> (u64, u64, u64) {
+    let days = days + 719_468;
+    let era = days / 146_097;
+    let day_of_era = days % 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
+fn encode_oid(arcs: &[u64]) -> Result<Vec<u8>> {
+    if arcs.len() < 2 || arcs[0] > 2 || (arcs[0] < 2 && arcs[1] > 39) {
+        error!("Invalid object identifier: {:?}", arcs);
+        return Err(Error::local_error(WrapperErrorKind::InvalidParam));
+    }
+    let mut content = Vec::new();
+    for arc in std::iter::once(arcs[0] * 40 + arcs[1]).chain(arcs[2..].iter().copied()) {
+        let mut encoded_arc = vec![(arc & 0x7f) as u8];
+        let mut remaining = arc >> 7;
+        while remaining != 0 {
+            encoded_arc.push((remaining & 0x7f) as u8 | 0x80);
+            remaining >>= 7;
+        }
+        content.extend(encoded_arc.iter().rev());
+    }
+    Ok(encode_tlv(TAG_OBJECT_IDENTIFIER, &content))
+}
+
+fn encode_tlv(tag: u8, content: &[u8]) -> Vec<u8> {
+    let mut encoded = vec![tag];
+    let length = content.len();
+    if length < 0x80 {
+        encoded.push(length as u8);
+    } else {
+        let length_bytes = length.to_be_bytes();
+        let first = length_bytes
+            .iter()
+            .position(|byte| *byte != 0)
+            .unwrap_or(length_bytes.len() - 1);
+        encoded.push(0x80 | (length_bytes.len() - first) as u8);
+        encoded.extend_from_slice(&length_bytes[first..]);
+    }
+    encoded.extend_from_slice(content);
+    encoded
+}
+
+// Splits the content of a DER sequence into its encoded elements.
+fn decode_sequence(data: &[u8]) -> Result<Vec<&[u8]>> {
+    let (tag, content, remaining) = decode_tlv(data)?;
+    if tag != TAG_SEQUENCE || !remaining.is_empty() {
+        error!("Data is not a single DER encoded sequence");
+        return Err(Error::local_error(WrapperErrorKind::InvalidParam));
+    }
+    let mut elements = Vec::new();
+    let mut remaining = content;
+    while !remaining.is_empty() {
+        let (_, _, rest) = decode_tlv(remaining)?;
+        let element_length = remaining.len() - rest.len();
+        elements.push(&remaining[..element_length]);
+        remaining = rest;
+    }
+    Ok(elements)
+}
+
+// Returns the tag, the content and the data following a DER encoded element.
+fn decode_tlv(data: &[u8]) -> Result<(u8, &[u8], &[u8])> {
+    let invalid_encoding = || {
+        error!("Invalid DER encoding");
+        Error::local_error(WrapperErrorKind::InvalidParam)
+    };
+    let (&tag, data) = data.split_first().ok_or_else(invalid_encoding)?;
+    let (&first_length_byte, data) = data.split_first().ok_or_else(invalid_encoding)?;
+    let (length, data) = if first_length_byte < 0x80 {
+        (usize::from(first_length_byte), data)
+    } else {
+        let length_size = usize::from(first_length_byte & 0x7f);
+        if length_size == 0
+            || length_size > std::mem::size_of::<usize>()
+            || data.len() < length_size
+        {
+            return Err(invalid_encoding());
+        }
+        let length = data[..length_size]
+            .iter()
+            .fold(0usize, |length, byte| (length << 8) | usize::from(*byte));
+        (length, &data[length_size..])
+    };
+    if data.len() < length {
+        return Err(invalid_encoding());
+    }
+    Ok((tag, &data[..length], &data[length..]))
+}
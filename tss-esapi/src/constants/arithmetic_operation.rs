@@ -0,0 +1,56 @@
+// Copyright 2021 Contributors to the Parsec project.
+// SPDX-License-Identifier: Apache-2.0
+use crate::{
+    constants::tss::{
+        TPM2_EO_BITCLEAR, TPM2_EO_BITSET, TPM2_EO_EQ, TPM2_EO_NEQ, TPM2_EO_SIGNED_GE,
+        TPM2_EO_SIGNED_GT, TPM2_EO_SIGNED_LE, TPM2_EO_SIGNED_LT, TPM2_EO_UNSIGNED_GE,
+        TPM2_EO_UNSIGNED_GT, TPM2_EO_UNSIGNED_LE, TPM2_EO_UNSIGNED_LT,
+    },
+    tss2_esys::TPM2_EO,
+    Error, Result, WrapperErrorKind,
+};
+use log::error;
+use num_derive::{FromPrimitive, ToPrimitive};
+use num_traits::{FromPrimitive, ToPrimitive};
+use serde::{Deserialize, Serialize};
+use std::convert::TryFrom;
+
+/// Enum representing the arithmetic operations used to compare
+/// an operand with the contents of an NV index or of the TPM clock
+/// in a policy.
+#[derive(FromPrimitive, ToPrimitive, Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[repr(u16)]
+pub enum ArithmeticOperation {
+    Eq = TPM2_EO_EQ,
+    Neq = TPM2_EO_NEQ,
+    SignedGt = TPM2_EO_SIGNED_GT,
+    UnsignedGt = TPM2_EO_UNSIGNED_GT,
+    SignedLt = TPM2_EO_SIGNED_LT,
+    UnsignedLt = TPM2_EO_UNSIGNED_LT,
+    SignedGe = TPM2_EO_SIGNED_GE,
+    UnsignedGe = TPM2_EO_UNSIGNED_GE,
+    SignedLe = TPM2_EO_SIGNED_LE,
+    UnsignedLe = TPM2_EO_UNSIGNED_LE,
+    Bitset = TPM2_EO_BITSET,
+    Bitclear = TPM2_EO_BITCLEAR,
+}
+
+impl From<ArithmeticOperation> for TPM2_EO {
+    fn from(arithmetic_operation: ArithmeticOperation) -> TPM2_EO {
+        // The values are well defined so this cannot fail.
+        arithmetic_operation.to_u16().unwrap()
+    }
+}
+
+impl TryFrom<TPM2_EO> for ArithmeticOperation {
+    type Error = Error;
+    fn try_from(tpm_arithmetic_operation: TPM2_EO) -> Result<ArithmeticOperation> {
+        ArithmeticOperation::from_u16(tpm_arithmetic_operation).ok_or_else(|| {
+            error!(
+                "Error: value = {} did not match any ArithmeticOperation.",
+                tpm_arithmetic_operation
+            );
+            Error::local_error(WrapperErrorKind::InvalidParam)
+        })
+    }
+}
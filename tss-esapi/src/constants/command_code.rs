@@ -0,0 +1,191 @@
+// Copyright 2021 Contributors to the Parsec project.
+// SPDX-License-Identifier: Apache-2.0
+use crate::{
+    constants::tss::{
+        TPM2_CC_AC_GetCapability, TPM2_CC_AC_Send, TPM2_CC_ActivateCredential, TPM2_CC_Certify,
+        TPM2_CC_CertifyCreation, TPM2_CC_ChangeEPS, TPM2_CC_ChangePPS, TPM2_CC_Clear,
+        TPM2_CC_ClearControl, TPM2_CC_ClockRateAdjust, TPM2_CC_ClockSet, TPM2_CC_Commit,
+        TPM2_CC_ContextLoad, TPM2_CC_ContextSave, TPM2_CC_Create, TPM2_CC_CreateLoaded,
+        TPM2_CC_CreatePrimary, TPM2_CC_DictionaryAttackLockReset,
+        TPM2_CC_DictionaryAttackParameters, TPM2_CC_Duplicate, TPM2_CC_ECC_Parameters,
+        TPM2_CC_ECDH_KeyGen, TPM2_CC_ECDH_ZGen, TPM2_CC_EC_Ephemeral, TPM2_CC_EncryptDecrypt,
+        TPM2_CC_EncryptDecrypt2, TPM2_CC_EventSequenceComplete, TPM2_CC_EvictControl,
+        TPM2_CC_FieldUpgradeData, TPM2_CC_FieldUpgradeStart, TPM2_CC_FirmwareRead,
+        TPM2_CC_FlushContext, TPM2_CC_GetCapability, TPM2_CC_GetCommandAuditDigest,
+        TPM2_CC_GetRandom, TPM2_CC_GetSessionAuditDigest, TPM2_CC_GetTestResult, TPM2_CC_GetTime,
+        TPM2_CC_HMAC_Start, TPM2_CC_Hash, TPM2_CC_HashSequenceStart, TPM2_CC_HierarchyChangeAuth,
+        TPM2_CC_HierarchyControl, TPM2_CC_Import, TPM2_CC_IncrementalSelfTest, TPM2_CC_Load,
+        TPM2_CC_LoadExternal, TPM2_CC_MakeCredential, TPM2_CC_NV_Certify, TPM2_CC_NV_ChangeAuth,
+        TPM2_CC_NV_DefineSpace, TPM2_CC_NV_Extend, TPM2_CC_NV_GlobalWriteLock,
+        TPM2_CC_NV_Increment, TPM2_CC_NV_Read, TPM2_CC_NV_ReadLock, TPM2_CC_NV_ReadPublic,
+        TPM2_CC_NV_SetBits, TPM2_CC_NV_UndefineSpace, TPM2_CC_NV_UndefineSpaceSpecial,
+        TPM2_CC_NV_Write, TPM2_CC_NV_WriteLock, TPM2_CC_ObjectChangeAuth, TPM2_CC_PCR_Allocate,
+        TPM2_CC_PCR_Event, TPM2_CC_PCR_Extend, TPM2_CC_PCR_Read, TPM2_CC_PCR_Reset,
+        TPM2_CC_PCR_SetAuthPolicy, TPM2_CC_PCR_SetAuthValue, TPM2_CC_PP_Commands,
+        TPM2_CC_PolicyAuthValue, TPM2_CC_PolicyAuthorize, TPM2_CC_PolicyAuthorizeNV,
+        TPM2_CC_PolicyCommandCode, TPM2_CC_PolicyCounterTimer, TPM2_CC_PolicyCpHash,
+        TPM2_CC_PolicyDuplicationSelect, TPM2_CC_PolicyGetDigest, TPM2_CC_PolicyLocality,
+        TPM2_CC_PolicyNV, TPM2_CC_PolicyNameHash, TPM2_CC_PolicyNvWritten, TPM2_CC_PolicyOR,
+        TPM2_CC_PolicyPCR, TPM2_CC_PolicyPassword, TPM2_CC_PolicyPhysicalPresence,
+        TPM2_CC_PolicyRestart, TPM2_CC_PolicySecret, TPM2_CC_PolicySigned, TPM2_CC_PolicyTemplate,
+        TPM2_CC_PolicyTicket, TPM2_CC_Policy_AC_SendSelect, TPM2_CC_Quote, TPM2_CC_RSA_Decrypt,
+        TPM2_CC_RSA_Encrypt, TPM2_CC_ReadClock, TPM2_CC_ReadPublic, TPM2_CC_Rewrap,
+        TPM2_CC_SelfTest, TPM2_CC_SequenceComplete, TPM2_CC_SequenceUpdate,
+        TPM2_CC_SetAlgorithmSet, TPM2_CC_SetCommandCodeAuditStatus, TPM2_CC_SetPrimaryPolicy,
+        TPM2_CC_Shutdown, TPM2_CC_Sign, TPM2_CC_StartAuthSession, TPM2_CC_Startup,
+        TPM2_CC_StirRandom, TPM2_CC_TestParms, TPM2_CC_Unseal, TPM2_CC_Vendor_TCG_Test,
+        TPM2_CC_VerifySignature, TPM2_CC_ZGen_2Phase, TPM2_CC_HMAC,
+    },
+    tss2_esys::TPM2_CC,
+    Error, Result, WrapperErrorKind,
+};
+use log::error;
+use num_derive::{FromPrimitive, ToPrimitive};
+use num_traits::{FromPrimitive, ToPrimitive};
+use serde::{Deserialize, Serialize};
+use std::convert::TryFrom;
+
+/// Enum representing the command codes of the commands
+/// defined in the specification.
+#[derive(
+    FromPrimitive, ToPrimitive, Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize,
+)]
+#[repr(u32)]
+pub enum CommandCode {
+    NvUndefineSpaceSpecial = TPM2_CC_NV_UndefineSpaceSpecial,
+    EvictControl = TPM2_CC_EvictControl,
+    HierarchyControl = TPM2_CC_HierarchyControl,
+    NvUndefineSpace = TPM2_CC_NV_UndefineSpace,
+    ChangeEps = TPM2_CC_ChangeEPS,
+    ChangePps = TPM2_CC_ChangePPS,
+    Clear = TPM2_CC_Clear,
+    ClearControl = TPM2_CC_ClearControl,
+    ClockSet = TPM2_CC_ClockSet,
+    HierarchyChangeAuth = TPM2_CC_HierarchyChangeAuth,
+    NvDefineSpace = TPM2_CC_NV_DefineSpace,
+    PcrAllocate = TPM2_CC_PCR_Allocate,
+    PcrSetAuthPolicy = TPM2_CC_PCR_SetAuthPolicy,
+    PpCommands = TPM2_CC_PP_Commands,
+    SetPrimaryPolicy = TPM2_CC_SetPrimaryPolicy,
+    FieldUpgradeStart = TPM2_CC_FieldUpgradeStart,
+    ClockRateAdjust = TPM2_CC_ClockRateAdjust,
+    CreatePrimary = TPM2_CC_CreatePrimary,
+    NvGlobalWriteLock = TPM2_CC_NV_GlobalWriteLock,
+    GetCommandAuditDigest = TPM2_CC_GetCommandAuditDigest,
+    NvIncrement = TPM2_CC_NV_Increment,
+    NvSetBits = TPM2_CC_NV_SetBits,
+    NvExtend = TPM2_CC_NV_Extend,
+    NvWrite = TPM2_CC_NV_Write,
+    NvWriteLock = TPM2_CC_NV_WriteLock,
+    DictionaryAttackLockReset = TPM2_CC_DictionaryAttackLockReset,
+    DictionaryAttackParameters = TPM2_CC_DictionaryAttackParameters,
+    NvChangeAuth = TPM2_CC_NV_ChangeAuth,
+    PcrEvent = TPM2_CC_PCR_Event,
+    PcrReset = TPM2_CC_PCR_Reset,
+    SequenceComplete = TPM2_CC_SequenceComplete,
+    SetAlgorithmSet = TPM2_CC_SetAlgorithmSet,
+    SetCommandCodeAuditStatus = TPM2_CC_SetCommandCodeAuditStatus,
+    FieldUpgradeData = TPM2_CC_FieldUpgradeData,
+    IncrementalSelfTest = TPM2_CC_IncrementalSelfTest,
+    SelfTest = TPM2_CC_SelfTest,
+    Startup = TPM2_CC_Startup,
+    Shutdown = TPM2_CC_Shutdown,
+    StirRandom = TPM2_CC_StirRandom,
+    ActivateCredential = TPM2_CC_ActivateCredential,
+    Certify = TPM2_CC_Certify,
+    PolicyNv = TPM2_CC_PolicyNV,
+    CertifyCreation = TPM2_CC_CertifyCreation,
+    Duplicate = TPM2_CC_Duplicate,
+    GetTime = TPM2_CC_GetTime,
+    GetSessionAuditDigest = TPM2_CC_GetSessionAuditDigest,
+    NvRead = TPM2_CC_NV_Read,
+    NvReadLock = TPM2_CC_NV_ReadLock,
+    ObjectChangeAuth = TPM2_CC_ObjectChangeAuth,
+    PolicySecret = TPM2_CC_PolicySecret,
+    Rewrap = TPM2_CC_Rewrap,
+    Create = TPM2_CC_Create,
+    EcdhZGen = TPM2_CC_ECDH_ZGen,
+    Hmac = TPM2_CC_HMAC,
+    Import = TPM2_CC_Import,
+    Load = TPM2_CC_Load,
+    Quote = TPM2_CC_Quote,
+    RsaDecrypt = TPM2_CC_RSA_Decrypt,
+    HmacStart = TPM2_CC_HMAC_Start,
+    SequenceUpdate = TPM2_CC_SequenceUpdate,
+    Sign = TPM2_CC_Sign,
+    Unseal = TPM2_CC_Unseal,
+    PolicySigned = TPM2_CC_PolicySigned,
+    ContextLoad = TPM2_CC_ContextLoad,
+    ContextSave = TPM2_CC_ContextSave,
+    EcdhKeyGen = TPM2_CC_ECDH_KeyGen,
+    EncryptDecrypt = TPM2_CC_EncryptDecrypt,
+    FlushContext = TPM2_CC_FlushContext,
+    LoadExternal = TPM2_CC_LoadExternal,
+    MakeCredential = TPM2_CC_MakeCredential,
+    NvReadPublic = TPM2_CC_NV_ReadPublic,
+    PolicyAuthorize = TPM2_CC_PolicyAuthorize,
+    PolicyAuthValue = TPM2_CC_PolicyAuthValue,
+    PolicyCommandCode = TPM2_CC_PolicyCommandCode,
+    PolicyCounterTimer = TPM2_CC_PolicyCounterTimer,
+    PolicyCpHash = TPM2_CC_PolicyCpHash,
+    PolicyLocality = TPM2_CC_PolicyLocality,
+    PolicyNameHash = TPM2_CC_PolicyNameHash,
+    PolicyOr = TPM2_CC_PolicyOR,
+    PolicyTicket = TPM2_CC_PolicyTicket,
+    ReadPublic = TPM2_CC_ReadPublic,
+    RsaEncrypt = TPM2_CC_RSA_Encrypt,
+    StartAuthSession = TPM2_CC_StartAuthSession,
+    VerifySignature = TPM2_CC_VerifySignature,
+    EccParameters = TPM2_CC_ECC_Parameters,
+    FirmwareRead = TPM2_CC_FirmwareRead,
+    GetCapability = TPM2_CC_GetCapability,
+    GetRandom = TPM2_CC_GetRandom,
+    GetTestResult = TPM2_CC_GetTestResult,
+    Hash = TPM2_CC_Hash,
+    PcrRead = TPM2_CC_PCR_Read,
+    PolicyPcr = TPM2_CC_PolicyPCR,
+    PolicyRestart = TPM2_CC_PolicyRestart,
+    ReadClock = TPM2_CC_ReadClock,
+    PcrExtend = TPM2_CC_PCR_Extend,
+    PcrSetAuthValue = TPM2_CC_PCR_SetAuthValue,
+    NvCertify = TPM2_CC_NV_Certify,
+    EventSequenceComplete = TPM2_CC_EventSequenceComplete,
+    HashSequenceStart = TPM2_CC_HashSequenceStart,
+    PolicyPhysicalPresence = TPM2_CC_PolicyPhysicalPresence,
+    PolicyDuplicationSelect = TPM2_CC_PolicyDuplicationSelect,
+    PolicyGetDigest = TPM2_CC_PolicyGetDigest,
+    TestParms = TPM2_CC_TestParms,
+    Commit = TPM2_CC_Commit,
+    PolicyPassword = TPM2_CC_PolicyPassword,
+    ZGen2Phase = TPM2_CC_ZGen_2Phase,
+    EcEphemeral = TPM2_CC_EC_Ephemeral,
+    PolicyNvWritten = TPM2_CC_PolicyNvWritten,
+    PolicyTemplate = TPM2_CC_PolicyTemplate,
+    CreateLoaded = TPM2_CC_CreateLoaded,
+    PolicyAuthorizeNv = TPM2_CC_PolicyAuthorizeNV,
+    EncryptDecrypt2 = TPM2_CC_EncryptDecrypt2,
+    AcGetCapability = TPM2_CC_AC_GetCapability,
+    AcSend = TPM2_CC_AC_Send,
+    PolicyAcSendSelect = TPM2_CC_Policy_AC_SendSelect,
+    VendorTcgTest = TPM2_CC_Vendor_TCG_Test,
+}
+
+impl From<CommandCode> for TPM2_CC {
+    fn from(command_code: CommandCode) -> TPM2_CC {
+        // The values are well defined so this cannot fail.
+        command_code.to_u32().unwrap()
+    }
+}
+
+impl TryFrom<TPM2_CC> for CommandCode {
+    type Error = Error;
+    fn try_from(tpm_command_code: TPM2_CC) -> Result<CommandCode> {
+        CommandCode::from_u32(tpm_command_code).ok_or_else(|| {
+            error!(
+                "Error: value = {} did not match any CommandCode.",
+                tpm_command_code
+            );
+            Error::local_error(WrapperErrorKind::InvalidParam)
+        })
+    }
+}
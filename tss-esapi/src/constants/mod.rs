@@ -47,7 +47,17 @@ pub mod response_code;
 /// NV Storage -> TPM_NT section of the specfication
 pub mod nv_index_type;
 
+/// Representation of the constants defined in the
+/// Constants -> TPM_EO section of the specfication
+pub mod arithmetic_operation;
+
+/// Representation of the constants defined in the
+/// Constants -> TPM_CC section of the specfication
+pub mod command_code;
+
+pub use arithmetic_operation::ArithmeticOperation;
 pub use capabilities::CapabilityType;
+pub use command_code::CommandCode;
 pub use nv_index_type::NvIndexType;
 pub use property_tag::PropertyTag;
 pub use response_code::{ResponseCode, Tss2ResponseCode, Tss2ResponseCodeKind};
@@ -2,12 +2,14 @@
 // SPDX-License-Identifier: Apache-2.0
 use crate::{
     handles::KeyHandle,
+    interface_types::{algorithm::EccSchemeAlgorithm, ecc::EccCurve},
     structures::Data,
-    structures::{EccPoint, PublicKeyRsa, RsaDecryptionScheme},
+    structures::{EccCurveParameters, EccPoint, PublicKeyRsa, RsaDecryptionScheme},
     tss2_esys::*,
     Context, Error, Result,
 };
 use log::error;
+use mbox::MBox;
 use std::convert::TryFrom;
 use std::convert::TryInto;
 use std::ptr::null_mut;
@@ -342,6 +344,89 @@ impl Context {
         }
     }
 
-    // Missing function: ECC_Parameters
-    // Missing function: ZGen_2Phase
+    /// Get the parameters of an ECC curve
+    ///
+    /// # Arguments
+    /// * `curve` - The [EccCurve] of which the parameters are returned.
+    ///
+    /// # Errors
+    /// * if the curve is not supported by the TPM, a TPM error is returned
+    pub fn ecc_parameters(&mut self, curve: EccCurve) -> Result<EccCurveParameters> {
+        let mut parameters_ptr = null_mut();
+        let ret = unsafe {
+            Esys_ECC_Parameters(
+                self.mut_context(),
+                self.optional_session_1(),
+                self.optional_session_2(),
+                self.optional_session_3(),
+                curve.into(),
+                &mut parameters_ptr,
+            )
+        };
+        let ret = Error::from_tss_rc(ret);
+
+        if ret.is_success() {
+            let parameters = unsafe { MBox::from_raw(parameters_ptr) };
+            EccCurveParameters::try_from(*parameters)
+        } else {
+            error!("Error when getting the ECC curve parameters: {}", ret);
+            Err(ret)
+        }
+    }
+
+    /// Generate the shared secrets of a two-phase key exchange
+    ///
+    /// # Details
+    /// The static key `key_a` of party A is combined with the ephemeral key
+    /// generated by [Context::ec_ephemeral] for the commit `counter`, and with
+    /// the static and ephemeral public keys of party B, to compute the
+    /// shared secrets `Z1` and `Z2` of the key exchange `in_scheme`, which is
+    /// one of [EccSchemeAlgorithm::EcDh], [EccSchemeAlgorithm::EcMqv] and
+    /// [EccSchemeAlgorithm::Sm2]. The ephemeral key can only be used once.
+    ///
+    /// # Arguments
+    /// * `key_a` - A [KeyHandle] of the static ECC key of party A.
+    /// * `in_qs_b` - The static public key of party B.
+    /// * `in_qe_b` - The ephemeral public key of party B.
+    /// * `in_scheme` - The key exchange scheme.
+    /// * `counter` - The commit counter of the ephemeral key of party A.
+    pub fn zgen_2phase(
+        &mut self,
+        key_a: KeyHandle,
+        in_qs_b: EccPoint,
+        in_qe_b: EccPoint,
+        in_scheme: EccSchemeAlgorithm,
+        counter: u16,
+    ) -> Result<(EccPoint, EccPoint)> {
+        let mut out_z1_ptr = null_mut();
+        let mut out_z2_ptr = null_mut();
+        let ret = unsafe {
+            Esys_ZGen_2Phase(
+                self.mut_context(),
+                key_a.into(),
+                self.required_session_1()?,
+                self.optional_session_2(),
+                self.optional_session_3(),
+                &in_qs_b.into(),
+                &in_qe_b.into(),
+                in_scheme.into(),
+                counter,
+                &mut out_z1_ptr,
+                &mut out_z2_ptr,
+            )
+        };
+        let ret = Error::from_tss_rc(ret);
+
+        if ret.is_success() {
+            let out_z1 = unsafe { MBox::from_raw(out_z1_ptr) };
+            let out_z2 = unsafe { MBox::from_raw(out_z2_ptr) };
+            Ok((
+                EccPoint::try_from(out_z1.point)?,
+                EccPoint::try_from(out_z2.point)?,
+            ))
+        } else {
+            error!("Error when performing ZGen 2Phase: {}", ret);
+            Err(ret)
+        }
+    }
 }
@@ -1,22 +1,138 @@
 // Copyright 2021 Contributors to the Parsec project.
 // SPDX-License-Identifier: Apache-2.0
+#[cfg(tpm2_tss_version = "3")]
+use crate::structures::MaxBuffer;
 use crate::{
-    handles::KeyHandle,
-    structures::{Data, PcrSelectionList, Signature},
+    handles::{AuthHandle, KeyHandle, ObjectHandle, SessionHandle},
+    interface_types::{resource_handles::Privacy, session_handles::HmacSession},
+    structures::{
+        Attest, CreationTicket, Data, Digest, PcrSelectionList, Signature, SignatureScheme,
+    },
     tss2_esys::*,
     Context, Error, Result,
 };
 use log::error;
 use mbox::MBox;
-use std::convert::TryFrom;
+use std::convert::{TryFrom, TryInto};
 use std::ptr::null_mut;
 
 impl Context {
-    // Missing function: Certify
-    // Missing function: CertifyCreation
+    /// Prove that an object is loaded in the TPM
+    ///
+    /// # Details
+    /// The object identified by `object_handle` is certified by signing
+    /// its names with the key identified by `signing_key_handle`. Both
+    /// handles require authorization, so two sessions have to be set on
+    /// the context.
+    ///
+    /// The returned [Attest] holds a [CertifyInfo](crate::structures::CertifyInfo)
+    /// as attested data.
+    ///
+    /// # Errors
+    /// * if the qualifying data provided is too long, a `WrongParamSize` wrapper error will be returned
+    /// * if either of the first two sessions is missing, a `MissingAuthSession` wrapper error will be returned
+    pub fn certify(
+        &mut self,
+        object_handle: ObjectHandle,
+        signing_key_handle: KeyHandle,
+        qualifying_data: Data,
+        signing_scheme: SignatureScheme,
+    ) -> Result<(Attest, Signature)> {
+        let mut certify_info = null_mut();
+        let mut signature = null_mut();
+        let ret = unsafe {
+            Esys_Certify(
+                self.mut_context(),
+                object_handle.into(),
+                signing_key_handle.into(),
+                self.required_session_1()?,
+                self.required_session_2()?,
+                self.optional_session_3(),
+                &qualifying_data.into(),
+                &signing_scheme.into(),
+                &mut certify_info,
+                &mut signature,
+            )
+        };
+        let ret = Error::from_tss_rc(ret);
+
+        if ret.is_success() {
+            let certify_info = unsafe { MBox::<TPM2B_ATTEST>::from_raw(certify_info) };
+            let signature = unsafe { MBox::from_raw(signature) };
+            Ok((
+                Attest::try_from(*certify_info)?,
+                Signature::try_from(*signature)?,
+            ))
+        } else {
+            error!("Error in certifying object: {}", ret);
+            Err(ret)
+        }
+    }
+
+    /// Prove the association between an object and its creation data
+    ///
+    /// # Details
+    /// The `creation_hash` and `creation_ticket` are the ones that were
+    /// returned by the TPM when the object identified by `object_handle`
+    /// was created. The TPM validates the ticket and signs the association
+    /// with the key identified by `signing_key_handle`, which requires an
+    /// authorization session.
+    ///
+    /// The returned [Attest] holds a [CreationInfo](crate::structures::CreationInfo)
+    /// as attested data.
+    ///
+    /// # Errors
+    /// * if the qualifying data provided is too long, a `WrongParamSize` wrapper error will be returned
+    /// * if the first session is missing, a `MissingAuthSession` wrapper error will be returned
+    pub fn certify_creation(
+        &mut self,
+        signing_key_handle: KeyHandle,
+        object_handle: ObjectHandle,
+        qualifying_data: Data,
+        creation_hash: Digest,
+        signing_scheme: SignatureScheme,
+        creation_ticket: CreationTicket,
+    ) -> Result<(Attest, Signature)> {
+        let mut certify_info = null_mut();
+        let mut signature = null_mut();
+        let ret = unsafe {
+            Esys_CertifyCreation(
+                self.mut_context(),
+                signing_key_handle.into(),
+                object_handle.into(),
+                self.required_session_1()?,
+                self.optional_session_2(),
+                self.optional_session_3(),
+                &qualifying_data.into(),
+                &creation_hash.into(),
+                &signing_scheme.into(),
+                &creation_ticket.try_into()?,
+                &mut certify_info,
+                &mut signature,
+            )
+        };
+        let ret = Error::from_tss_rc(ret);
+
+        if ret.is_success() {
+            let certify_info = unsafe { MBox::<TPM2B_ATTEST>::from_raw(certify_info) };
+            let signature = unsafe { MBox::from_raw(signature) };
+            Ok((
+                Attest::try_from(*certify_info)?,
+                Signature::try_from(*signature)?,
+            ))
+        } else {
+            error!("Error in certifying creation: {}", ret);
+            Err(ret)
+        }
+    }
 
     /// Generate a quote on the selected PCRs
     ///
+    /// # Details
+    /// The returned [Attest] holds the parsed attestation data. The exact
+    /// bytes over which the signature was computed can be retrieved with
+    /// [Attest::marshall].
+    ///
     /// # Errors
     /// * if the qualifying data provided is too long, a `WrongParamSize` wrapper error will be returned
     pub fn quote(
@@ -25,7 +141,7 @@ impl Context {
         qualifying_data: &Data,
         signing_scheme: TPMT_SIG_SCHEME,
         pcr_selection_list: PcrSelectionList,
-    ) -> Result<(TPM2B_ATTEST, Signature)> {
+    ) -> Result<(Attest, Signature)> {
         let mut quoted = null_mut();
         let mut signature = null_mut();
         let ret = unsafe {
@@ -47,15 +163,236 @@ impl Context {
         if ret.is_success() {
             let quoted = unsafe { MBox::<TPM2B_ATTEST>::from_raw(quoted) };
             let signature = unsafe { MBox::from_raw(signature) };
-            Ok((*quoted, Signature::try_from(*signature)?))
+            Ok((Attest::try_from(*quoted)?, Signature::try_from(*signature)?))
         } else {
             error!("Error in quoting PCR: {}", ret);
             Err(ret)
         }
     }
 
-    // Missing function: GetSessionAuditDigest
-    // Missing function: GestCommandAuditDigest
-    // Missing function: GetTime
-    // Missing function: CertifyX509
+    /// Get a signed digest of the commands audited in a session
+    ///
+    /// # Details
+    /// The audit digest of the session identified by `audit_session` is
+    /// signed with the key identified by `signing_key_handle`. Both the
+    /// privacy administrator and the signing key require authorization, so
+    /// two sessions have to be set on the context.
+    ///
+    /// The returned [Attest] holds a [SessionAuditInfo](crate::structures::SessionAuditInfo)
+    /// as attested data.
+    ///
+    /// # Errors
+    /// * if the qualifying data provided is too long, a `WrongParamSize` wrapper error will be returned
+    /// * if either of the first two sessions is missing, a `MissingAuthSession` wrapper error will be returned
+    pub fn get_session_audit_digest(
+        &mut self,
+        privacy_admin_handle: Privacy,
+        signing_key_handle: KeyHandle,
+        audit_session: HmacSession,
+        qualifying_data: Data,
+        signing_scheme: SignatureScheme,
+    ) -> Result<(Attest, Signature)> {
+        let mut audit_info = null_mut();
+        let mut signature = null_mut();
+        let ret = unsafe {
+            Esys_GetSessionAuditDigest(
+                self.mut_context(),
+                AuthHandle::from(privacy_admin_handle).into(),
+                signing_key_handle.into(),
+                SessionHandle::from(audit_session).into(),
+                self.required_session_1()?,
+                self.required_session_2()?,
+                self.optional_session_3(),
+                &qualifying_data.into(),
+                &signing_scheme.into(),
+                &mut audit_info,
+                &mut signature,
+            )
+        };
+        let ret = Error::from_tss_rc(ret);
+
+        if ret.is_success() {
+            let audit_info = unsafe { MBox::<TPM2B_ATTEST>::from_raw(audit_info) };
+            let signature = unsafe { MBox::from_raw(signature) };
+            Ok((
+                Attest::try_from(*audit_info)?,
+                Signature::try_from(*signature)?,
+            ))
+        } else {
+            error!("Error in getting session audit digest: {}", ret);
+            Err(ret)
+        }
+    }
+
+    /// Get a signed digest of the commands audited by the TPM
+    ///
+    /// # Details
+    /// The current command audit digest of the TPM is signed with the key
+    /// identified by `signing_key_handle`. Both the privacy administrator
+    /// and the signing key require authorization, so two sessions have to
+    /// be set on the context.
+    ///
+    /// The returned [Attest] holds a [CommandAuditInfo](crate::structures::CommandAuditInfo)
+    /// as attested data.
+    ///
+    /// # Errors
+    /// * if the qualifying data provided is too long, a `WrongParamSize` wrapper error will be returned
+    /// * if either of the first two sessions is missing, a `MissingAuthSession` wrapper error will be returned
+    pub fn get_command_audit_digest(
+        &mut self,
+        privacy_admin_handle: Privacy,
+        signing_key_handle: KeyHandle,
+        qualifying_data: Data,
+        signing_scheme: SignatureScheme,
+    ) -> Result<(Attest, Signature)> {
+        let mut audit_info = null_mut();
+        let mut signature = null_mut();
+        let ret = unsafe {
+            Esys_GetCommandAuditDigest(
+                self.mut_context(),
+                AuthHandle::from(privacy_admin_handle).into(),
+                signing_key_handle.into(),
+                self.required_session_1()?,
+                self.required_session_2()?,
+                self.optional_session_3(),
+                &qualifying_data.into(),
+                &signing_scheme.into(),
+                &mut audit_info,
+                &mut signature,
+            )
+        };
+        let ret = Error::from_tss_rc(ret);
+
+        if ret.is_success() {
+            let audit_info = unsafe { MBox::<TPM2B_ATTEST>::from_raw(audit_info) };
+            let signature = unsafe { MBox::from_raw(signature) };
+            Ok((
+                Attest::try_from(*audit_info)?,
+                Signature::try_from(*signature)?,
+            ))
+        } else {
+            error!("Error in getting command audit digest: {}", ret);
+            Err(ret)
+        }
+    }
+
+    /// Get a signed attestation of the current time and clock of the TPM
+    ///
+    /// # Details
+    /// The current time and clock values are signed with the key identified
+    /// by `signing_key_handle`. Both the privacy administrator and the
+    /// signing key require authorization, so two sessions have to be set
+    /// on the context.
+    ///
+    /// The returned [Attest] holds a [TimeAttestInfo](crate::structures::TimeAttestInfo)
+    /// as attested data.
+    ///
+    /// # Errors
+    /// * if the qualifying data provided is too long, a `WrongParamSize` wrapper error will be returned
+    /// * if either of the first two sessions is missing, a `MissingAuthSession` wrapper error will be returned
+    pub fn get_time(
+        &mut self,
+        privacy_admin_handle: Privacy,
+        signing_key_handle: KeyHandle,
+        qualifying_data: Data,
+        signing_scheme: SignatureScheme,
+    ) -> Result<(Attest, Signature)> {
+        let mut time_info = null_mut();
+        let mut signature = null_mut();
+        let ret = unsafe {
+            Esys_GetTime(
+                self.mut_context(),
+                AuthHandle::from(privacy_admin_handle).into(),
+                signing_key_handle.into(),
+                self.required_session_1()?,
+                self.required_session_2()?,
+                self.optional_session_3(),
+                &qualifying_data.into(),
+                &signing_scheme.into(),
+                &mut time_info,
+                &mut signature,
+            )
+        };
+        let ret = Error::from_tss_rc(ret);
+
+        if ret.is_success() {
+            let time_info = unsafe { MBox::<TPM2B_ATTEST>::from_raw(time_info) };
+            let signature = unsafe { MBox::from_raw(signature) };
+            Ok((
+                Attest::try_from(*time_info)?,
+                Signature::try_from(*signature)?,
+            ))
+        } else {
+            error!("Error in getting time: {}", ret);
+            Err(ret)
+        }
+    }
+
+    /// Have the TPM sign an X.509 certificate for an object
+    ///
+    /// # Details
+    /// The `partial_certificate` holds the DER encoded fields of the
+    /// certificate that are provided by the caller (see
+    /// [PartialCertificateBuilder](crate::abstraction::x509::PartialCertificateBuilder)).
+    /// The TPM adds the remaining fields, among which the subject public key
+    /// of the object identified by `object_handle`, and signs the resulting
+    /// TBSCertificate with the key identified by `signing_key_handle`. Both
+    /// handles require authorization, so two sessions have to be set on the
+    /// context.
+    ///
+    /// The returned values are the DER encoded fields added by the TPM, the
+    /// digest of the TBSCertificate and the signature. The complete certificate
+    /// can be obtained with [assemble_certificate](crate::abstraction::x509::assemble_certificate).
+    ///
+    /// This command is only available with version 3 of the TSS libraries.
+    ///
+    /// # Errors
+    /// * if either of the first two sessions is missing, a `MissingAuthSession` wrapper error will be returned
+    #[cfg(tpm2_tss_version = "3")]
+    pub fn certify_x509(
+        &mut self,
+        object_handle: ObjectHandle,
+        signing_key_handle: KeyHandle,
+        signing_scheme: SignatureScheme,
+        partial_certificate: MaxBuffer,
+    ) -> Result<(MaxBuffer, Digest, Signature)> {
+        let mut added_to_certificate = null_mut();
+        let mut tbs_digest = null_mut();
+        let mut signature = null_mut();
+        let ret = unsafe {
+            Esys_CertifyX509(
+                self.mut_context(),
+                object_handle.into(),
+                signing_key_handle.into(),
+                self.required_session_1()?,
+                self.required_session_2()?,
+                self.optional_session_3(),
+                &Data::default().into(),
+                &signing_scheme.into(),
+                &partial_certificate.into(),
+                &mut added_to_certificate,
+                &mut tbs_digest,
+                &mut signature,
+            )
+        };
+        let ret = Error::from_tss_rc(ret);
+
+        if ret.is_success() {
+            let added_to_certificate =
+                unsafe { MBox::<TPM2B_MAX_BUFFER>::from_raw(added_to_certificate) };
+            let tbs_digest = unsafe { MBox::<TPM2B_DIGEST>::from_raw(tbs_digest) };
+            let signature = unsafe { MBox::from_raw(signature) };
+            Ok((
+                MaxBuffer::try_from(*added_to_certificate)?,
+                Digest::try_from(*tbs_digest)?,
+                Signature::try_from(*signature)?,
+            ))
+        } else {
+            error!(
+                "Error in certifying object with an X.509 certificate: {}",
+                ret
+            );
+            Err(ret)
+        }
+    }
 }
@@ -3,11 +3,12 @@
 use crate::Context;
 use crate::{
     handles::ObjectHandle,
-    structures::{Data, EncryptedSecret, Private, SymmetricDefinitionObject},
+    structures::{Data, EncryptedSecret, Name, Private, Public, SymmetricDefinitionObject},
     tss2_esys::*,
     Error, Result,
 };
 use log::error;
+use mbox::MBox;
 
 use std::convert::TryFrom;
 use std::ptr::null_mut;
@@ -330,6 +331,120 @@ impl Context {
         }
     }
 
-    // Missing function: Rewrap
-    // Missing function: Import
+    /// Change the parent of a duplicated object.
+    ///
+    /// # Details
+    /// This command allows the TPM to serve in the role of a duplication authority.
+    /// The outer wrapper of the duplicated object, which protects it for `old_parent`,
+    /// is removed and replaced by an outer wrapper protecting it for `new_parent`.
+    ///
+    /// # Arguments
+    /// * `old_parent` - An [ObjectHandle] of the parent of the object referenced in `in_duplicate`,
+    ///   or [ObjectHandle::Null] if the duplicated object has no outer wrapper.
+    /// * `new_parent` - An [ObjectHandle] of the new parent, or [ObjectHandle::Null] to remove the
+    ///   outer wrapper. Only its public area is required to be loaded.
+    /// * `in_duplicate` - The duplicated object, protected for `old_parent`.
+    /// * `name` - The [Name] of the duplicated object.
+    /// * `in_sym_seed` - The seed of the outer wrapper, encrypted for `old_parent`.
+    ///
+    /// The `old_parent` is authorized with the first session of the context.
+    ///
+    /// Returns the duplicated object and the seed of its outer wrapper, protected
+    /// for `new_parent`.
+    pub fn rewrap(
+        &mut self,
+        old_parent: ObjectHandle,
+        new_parent: ObjectHandle,
+        in_duplicate: Private,
+        name: Name,
+        in_sym_seed: EncryptedSecret,
+    ) -> Result<(Private, EncryptedSecret)> {
+        let mut out_duplicate = null_mut();
+        let mut out_sym_seed = null_mut();
+        let ret = unsafe {
+            Esys_Rewrap(
+                self.mut_context(),
+                old_parent.into(),
+                new_parent.into(),
+                self.required_session_1()?,
+                self.optional_session_2(),
+                self.optional_session_3(),
+                &in_duplicate.into(),
+                &TPM2B_NAME::try_from(name)?,
+                &in_sym_seed.into(),
+                &mut out_duplicate,
+                &mut out_sym_seed,
+            )
+        };
+        let ret = Error::from_tss_rc(ret);
+
+        if ret.is_success() {
+            let out_duplicate = unsafe { MBox::from_raw(out_duplicate) };
+            let out_sym_seed = unsafe { MBox::from_raw(out_sym_seed) };
+            Ok((
+                Private::try_from(*out_duplicate)?,
+                EncryptedSecret::try_from(*out_sym_seed)?,
+            ))
+        } else {
+            error!("Error when performing rewrap: {}", ret);
+            Err(ret)
+        }
+    }
+
+    /// Import a duplicated object so that it may be loaded under a new parent.
+    ///
+    /// # Details
+    /// This command allows an object to be encrypted using the symmetric encryption
+    /// values of a storage key. After encryption, the object can be loaded and used in
+    /// the new parent's hierarchy.
+    ///
+    /// # Arguments
+    /// * `parent_handle` - An [ObjectHandle] of the new parent.
+    /// * `encryption_key` - The symmetric key of the inner wrapper, if the object was
+    ///   duplicated with an inner wrapper.
+    /// * `object_public` - The public area of the duplicated object.
+    /// * `duplicate` - The duplicated object.
+    /// * `in_sym_seed` - The seed of the outer wrapper, encrypted for `parent_handle`.
+    /// * `symmetric_alg` - The symmetric algorithm of the inner wrapper, or
+    ///   [SymmetricDefinitionObject::Null] if there is no inner wrapper.
+    ///
+    /// The `parent_handle` is authorized with the first session of the context.
+    ///
+    /// Returns the private area of the object, which can be loaded under `parent_handle`
+    /// with [Context::load].
+    pub fn import(
+        &mut self,
+        parent_handle: ObjectHandle,
+        encryption_key: Option<Data>,
+        object_public: Public,
+        duplicate: Private,
+        in_sym_seed: EncryptedSecret,
+        symmetric_alg: SymmetricDefinitionObject,
+    ) -> Result<Private> {
+        let mut out_private = null_mut();
+        let ret = unsafe {
+            Esys_Import(
+                self.mut_context(),
+                parent_handle.into(),
+                self.required_session_1()?,
+                self.optional_session_2(),
+                self.optional_session_3(),
+                &encryption_key.unwrap_or_default().into(),
+                &object_public.into(),
+                &duplicate.into(),
+                &in_sym_seed.into(),
+                &symmetric_alg.into(),
+                &mut out_private,
+            )
+        };
+        let ret = Error::from_tss_rc(ret);
+
+        if ret.is_success() {
+            let out_private = unsafe { MBox::from_raw(out_private) };
+            Private::try_from(*out_private)
+        } else {
+            error!("Error when performing import: {}", ret);
+            Err(ret)
+        }
+    }
 }
@@ -1,10 +1,11 @@
 // Copyright 2021 Contributors to the Parsec project.
 // SPDX-License-Identifier: Apache-2.0
 use crate::{
-    handles::{AuthHandle, ObjectHandle, SessionHandle},
-    interface_types::session_handles::PolicySession,
+    constants::ArithmeticOperation,
+    handles::{AuthHandle, NvIndexHandle, ObjectHandle, SessionHandle},
+    interface_types::{resource_handles::NvAuth, session_handles::PolicySession},
     structures::{
-        AuthTicket, Digest, DigestList, Name, Nonce, PcrSelectionList, Signature, Timeout,
+        AuthTicket, Digest, DigestList, Name, Nonce, Operand, PcrSelectionList, Signature, Timeout,
         VerifiedTicket,
     },
     tss2_esys::*,
@@ -120,7 +121,44 @@ impl Context {
         }
     }
 
-    // Missing function: PolicyTicket
+    /// Cause the policy to include an authorization that was previously
+    /// granted with [Context::policy_signed] or [Context::policy_secret]
+    ///
+    /// # Details
+    /// The `timeout`, `cp_hash_a` and `policy_ref` have to be the values
+    /// that were used when the `ticket` was issued, and `auth_name` is the
+    /// name of the object that authorized it.
+    pub fn policy_ticket(
+        &mut self,
+        policy_session: PolicySession,
+        timeout: Timeout,
+        cp_hash_a: Digest,
+        policy_ref: Nonce,
+        auth_name: Name,
+        ticket: AuthTicket,
+    ) -> Result<()> {
+        let ret = unsafe {
+            Esys_PolicyTicket(
+                self.mut_context(),
+                SessionHandle::from(policy_session).into(),
+                self.optional_session_1(),
+                self.optional_session_2(),
+                self.optional_session_3(),
+                &timeout.into(),
+                &cp_hash_a.into(),
+                &policy_ref.into(),
+                &auth_name.try_into()?,
+                &ticket.try_into()?,
+            )
+        };
+        let ret = Error::from_tss_rc(ret);
+        if ret.is_success() {
+            Ok(())
+        } else {
+            error!("Error when sending policy ticket: {}", ret);
+            Err(ret)
+        }
+    }
 
     /// Cause conditional gating of a policy based on an OR'd condition.
     ///
@@ -225,8 +263,79 @@ impl Context {
         }
     }
 
-    // Missing function: PolicyNV
-    // Missing function: PolicyCounterTimer
+    /// Cause conditional gating of a policy based on the contents of an NV index.
+    ///
+    /// The TPM will ensure that the data of the NV index, starting at `offset`,
+    /// compares to `operand_b` according to `operation`. The index is read
+    /// using the authorization of `auth_handle`, which requires an
+    /// authorization session.
+    ///
+    /// # Errors
+    /// * if the first session is missing, a `MissingAuthSession` wrapper error will be returned
+    pub fn policy_nv(
+        &mut self,
+        policy_session: PolicySession,
+        auth_handle: NvAuth,
+        nv_index_handle: NvIndexHandle,
+        operand_b: Operand,
+        offset: u16,
+        operation: ArithmeticOperation,
+    ) -> Result<()> {
+        let ret = unsafe {
+            Esys_PolicyNV(
+                self.mut_context(),
+                AuthHandle::from(auth_handle).into(),
+                nv_index_handle.into(),
+                SessionHandle::from(policy_session).into(),
+                self.required_session_1()?,
+                self.optional_session_2(),
+                self.optional_session_3(),
+                &operand_b.into(),
+                offset,
+                operation.into(),
+            )
+        };
+        let ret = Error::from_tss_rc(ret);
+        if ret.is_success() {
+            Ok(())
+        } else {
+            error!("Error when computing policy NV: {}", ret);
+            Err(ret)
+        }
+    }
+
+    /// Cause conditional gating of a policy based on the TPM clock and timers.
+    ///
+    /// The TPM will ensure that the marshalled TPMS_TIME_INFO structure of
+    /// the TPM, starting at `offset`, compares to `operand_b` according to
+    /// `operation`.
+    pub fn policy_counter_timer(
+        &mut self,
+        policy_session: PolicySession,
+        operand_b: Operand,
+        offset: u16,
+        operation: ArithmeticOperation,
+    ) -> Result<()> {
+        let ret = unsafe {
+            Esys_PolicyCounterTimer(
+                self.mut_context(),
+                SessionHandle::from(policy_session).into(),
+                self.optional_session_1(),
+                self.optional_session_2(),
+                self.optional_session_3(),
+                &operand_b.into(),
+                offset,
+                operation.into(),
+            )
+        };
+        let ret = Error::from_tss_rc(ret);
+        if ret.is_success() {
+            Ok(())
+        } else {
+            error!("Error when computing policy counter timer: {}", ret);
+            Err(ret)
+        }
+    }
 
     /// Cause conditional gating of a policy based on command code of authorized command.
     ///
@@ -335,7 +444,40 @@ impl Context {
         }
     }
 
-    // Missing function: PolicyDuplicationSelect
+    /// Cause conditional gating of a policy based on the object being
+    /// duplicated and its new parent.
+    ///
+    /// The TPM will ensure that the current policy can only be used to
+    /// authorize the duplication of an object to the new parent named
+    /// `new_parent_name`. If `include_object` is set, the policy is also
+    /// bound to the object named `object_name`.
+    pub fn policy_duplication_select(
+        &mut self,
+        policy_session: PolicySession,
+        object_name: Name,
+        new_parent_name: Name,
+        include_object: bool,
+    ) -> Result<()> {
+        let ret = unsafe {
+            Esys_PolicyDuplicationSelect(
+                self.mut_context(),
+                SessionHandle::from(policy_session).into(),
+                self.optional_session_1(),
+                self.optional_session_2(),
+                self.optional_session_3(),
+                &object_name.try_into()?,
+                &new_parent_name.try_into()?,
+                if include_object { 1 } else { 0 },
+            )
+        };
+        let ret = Error::from_tss_rc(ret);
+        if ret.is_success() {
+            Ok(())
+        } else {
+            error!("Error when computing policy duplication select: {}", ret);
+            Err(ret)
+        }
+    }
 
     /// Cause conditional gating of a policy based on an authorized policy
     ///
@@ -507,5 +649,42 @@ impl Context {
             Err(ret)
         }
     }
-    // Missing function: PolicyAuthorizeNV
+
+    /// Cause conditional gating of a policy based on an authorized policy
+    /// stored in an NV index.
+    ///
+    /// The TPM will ensure that the current policy digest is equal to the
+    /// digest stored in the NV index, which is read using the authorization
+    /// of `auth_handle`. If this is the case, the policyDigest of the policy
+    /// session is replaced by a value derived from the name of the NV index.
+    /// This allows the authorized policy to be changed by writing the NV
+    /// index, without changing the policy of the objects.
+    ///
+    /// # Errors
+    /// * if the first session is missing, a `MissingAuthSession` wrapper error will be returned
+    pub fn policy_authorize_nv(
+        &mut self,
+        policy_session: PolicySession,
+        auth_handle: NvAuth,
+        nv_index_handle: NvIndexHandle,
+    ) -> Result<()> {
+        let ret = unsafe {
+            Esys_PolicyAuthorizeNV(
+                self.mut_context(),
+                AuthHandle::from(auth_handle).into(),
+                nv_index_handle.into(),
+                SessionHandle::from(policy_session).into(),
+                self.required_session_1()?,
+                self.optional_session_2(),
+                self.optional_session_3(),
+            )
+        };
+        let ret = Error::from_tss_rc(ret);
+        if ret.is_success() {
+            Ok(())
+        } else {
+            error!("Error when computing policy authorize NV: {}", ret);
+            Err(ret)
+        }
+    }
 }
@@ -1,8 +1,124 @@
 // Copyright 2021 Contributors to the Parsec project.
 // SPDX-License-Identifier: Apache-2.0
-use crate::Context;
+use crate::{
+    handles::KeyHandle,
+    interface_types::ecc::EccCurve,
+    structures::{CommitResult, EccParameter, EccPoint, SensitiveData},
+    tss2_esys::*,
+    Context, Error, Result,
+};
+use log::error;
+use mbox::MBox;
+use std::convert::TryFrom;
+use std::ptr::{null, null_mut};
 
 impl Context {
-    // Missing function: Commit
-    // Missing function: EC_Ephemeral
+    /// Perform the first part of an ECC anonymous signing operation
+    ///
+    /// # Details
+    /// The TPM generates an ephemeral key for the anonymous signing scheme
+    /// of the key referenced by `sign_handle`, and returns it in the form of
+    /// the points `K`, `L` and `E` along with the commit counter, which is
+    /// then given to the signing operation. `K` and `L` are only computed
+    /// when `p1` is provided, and `E` is computed from `p1` if provided, and
+    /// from the point derived from `s2` and `y2` otherwise.
+    ///
+    /// # Arguments
+    /// * `sign_handle` - A [KeyHandle] of an ECC key with an anonymous signing scheme.
+    /// * `p1` - An optional point on the curve of the key.
+    /// * `s2` - The optional octet array used to derive the x coordinate of a point.
+    /// * `y2` - The optional y coordinate of the point derived from `s2`.
+    ///
+    /// Returns a [CommitResult] holding the points `K`, `L` and `E`, and the
+    /// commit counter.
+    pub fn commit(
+        &mut self,
+        sign_handle: KeyHandle,
+        p1: Option<EccPoint>,
+        s2: Option<SensitiveData>,
+        y2: Option<EccParameter>,
+    ) -> Result<CommitResult> {
+        let p1 = p1.map(TPM2B_ECC_POINT::from);
+        let p1_ptr: *const TPM2B_ECC_POINT = match &p1 {
+            Some(val) => val,
+            None => null(),
+        };
+        let s2 = s2.map(TPM2B_SENSITIVE_DATA::from);
+        let s2_ptr: *const TPM2B_SENSITIVE_DATA = match &s2 {
+            Some(val) => val,
+            None => null(),
+        };
+        let y2 = y2.map(TPM2B_ECC_PARAMETER::from);
+        let y2_ptr: *const TPM2B_ECC_PARAMETER = match &y2 {
+            Some(val) => val,
+            None => null(),
+        };
+        let mut k_ptr = null_mut();
+        let mut l_ptr = null_mut();
+        let mut e_ptr = null_mut();
+        let mut counter = 0;
+        let ret = unsafe {
+            Esys_Commit(
+                self.mut_context(),
+                sign_handle.into(),
+                self.required_session_1()?,
+                self.optional_session_2(),
+                self.optional_session_3(),
+                p1_ptr,
+                s2_ptr,
+                y2_ptr,
+                &mut k_ptr,
+                &mut l_ptr,
+                &mut e_ptr,
+                &mut counter,
+            )
+        };
+        let ret = Error::from_tss_rc(ret);
+
+        if ret.is_success() {
+            let k = unsafe { MBox::from_raw(k_ptr) };
+            let l = unsafe { MBox::from_raw(l_ptr) };
+            let e = unsafe { MBox::from_raw(e_ptr) };
+            Ok(CommitResult {
+                k: EccPoint::try_from(k.point)?,
+                l: EccPoint::try_from(l.point)?,
+                e: EccPoint::try_from(e.point)?,
+                counter,
+            })
+        } else {
+            error!("Error when performing commit: {}", ret);
+            Err(ret)
+        }
+    }
+
+    /// Generate an ephemeral key for a two-phase key exchange
+    ///
+    /// # Details
+    /// The TPM generates an ephemeral key on `curve` and only returns its
+    /// public part, along with the commit counter from which it can
+    /// regenerate the private part in [Context::zgen_2phase].
+    pub fn ec_ephemeral(&mut self, curve: EccCurve) -> Result<(EccPoint, u16)> {
+        let mut q_ptr = null_mut();
+        let mut counter = 0;
+        let ret = unsafe {
+            Esys_EC_Ephemeral(
+                self.mut_context(),
+                self.optional_session_1(),
+                self.optional_session_2(),
+                self.optional_session_3(),
+                curve.into(),
+                &mut q_ptr,
+                &mut counter,
+            )
+        };
+        let ret = Error::from_tss_rc(ret);
+
+        if ret.is_success() {
+            let q = unsafe { MBox::from_raw(q_ptr) };
+            Ok((EccPoint::try_from(q.point)?, counter))
+        } else {
+            error!("Error when generating an ephemeral key: {}", ret);
+            Err(ret)
+        }
+    }
 }
@@ -1,12 +1,274 @@
 // Copyright 2021 Contributors to the Parsec project.
 // SPDX-License-Identifier: Apache-2.0
-use crate::Context;
+#[cfg(tpm2_tss_version = "3")]
+use crate::{constants::AlgorithmIdentifier, interface_types::algorithm::MacAlgorithm};
+use crate::{
+    context::handle_manager::HandleDropAction,
+    handles::{ObjectHandle, PcrHandle, TpmHandle},
+    interface_types::{algorithm::HashingAlgorithm, resource_handles::Hierarchy},
+    structures::{Auth, Digest, DigestValues, HashcheckTicket, MaxBuffer},
+    tss2_esys::*,
+    Context, Error, Result,
+};
+use log::error;
+use mbox::MBox;
+use std::convert::TryFrom;
+use std::ptr::null_mut;
 
 impl Context {
-    // Missing function: HMAC_Start
-    // Missing function: MAC_Start
-    // Missing function: HashSequenceStart
-    // Missing function: SequenceUpdate
-    // Missing function: SequenceComplete
-    // Missing function: EventSequenceComplete
+    /// Starts an HMAC sequence
+    ///
+    /// # Details
+    /// The HMAC is computed using the key identified by `handle`, which
+    /// requires an authorization session. The `auth` value is the
+    /// authorization value of the returned sequence object, which is
+    /// needed to update and complete the sequence.
+    ///
+    /// # Errors
+    /// * if the first session is missing, a `MissingAuthSession` wrapper error will be returned
+    pub fn hmac_start(
+        &mut self,
+        handle: ObjectHandle,
+        auth: Option<Auth>,
+        hashing_algorithm: HashingAlgorithm,
+    ) -> Result<ObjectHandle> {
+        let mut sequence_handle = ESYS_TR_NONE;
+        let ret = unsafe {
+            Esys_HMAC_Start(
+                self.mut_context(),
+                handle.into(),
+                self.required_session_1()?,
+                self.optional_session_2(),
+                self.optional_session_3(),
+                &auth.unwrap_or_default().into(),
+                hashing_algorithm.into(),
+                &mut sequence_handle,
+            )
+        };
+        let ret = Error::from_tss_rc(ret);
+
+        if ret.is_success() {
+            let sequence_handle = ObjectHandle::from(sequence_handle);
+            self.handle_manager
+                .add_handle(sequence_handle, HandleDropAction::Flush)?;
+            Ok(sequence_handle)
+        } else {
+            error!("Error in starting HMAC sequence: {}", ret);
+            Err(ret)
+        }
+    }
+
+    /// Starts a MAC sequence
+    ///
+    /// # Details
+    /// The MAC is computed using the key identified by `handle`, which
+    /// requires an authorization session. The `auth` value is the
+    /// authorization value of the returned sequence object, which is
+    /// needed to update and complete the sequence.
+    ///
+    /// This command is only available with version 3 of the TSS libraries.
+    ///
+    /// # Errors
+    /// * if the first session is missing, a `MissingAuthSession` wrapper error will be returned
+    #[cfg(tpm2_tss_version = "3")]
+    pub fn mac_start(
+        &mut self,
+        handle: ObjectHandle,
+        auth: Option<Auth>,
+        mac_algorithm: MacAlgorithm,
+    ) -> Result<ObjectHandle> {
+        let mut sequence_handle = ESYS_TR_NONE;
+        let ret = unsafe {
+            Esys_MAC_Start(
+                self.mut_context(),
+                handle.into(),
+                self.required_session_1()?,
+                self.optional_session_2(),
+                self.optional_session_3(),
+                &auth.unwrap_or_default().into(),
+                AlgorithmIdentifier::from(mac_algorithm).into(),
+                &mut sequence_handle,
+            )
+        };
+        let ret = Error::from_tss_rc(ret);
+
+        if ret.is_success() {
+            let sequence_handle = ObjectHandle::from(sequence_handle);
+            self.handle_manager
+                .add_handle(sequence_handle, HandleDropAction::Flush)?;
+            Ok(sequence_handle)
+        } else {
+            error!("Error in starting MAC sequence: {}", ret);
+            Err(ret)
+        }
+    }
+
+    /// Starts a hash or an event sequence
+    ///
+    /// # Details
+    /// If the `hashing_algorithm` is [HashingAlgorithm::Null] an event
+    /// sequence is started, which computes a digest for every PCR bank
+    /// of the TPM and is completed with [Context::event_sequence_complete].
+    ///
+    /// The `auth` value is the authorization value of the returned
+    /// sequence object, which is needed to update and complete the
+    /// sequence.
+    pub fn hash_sequence_start(
+        &mut self,
+        auth: Option<Auth>,
+        hashing_algorithm: HashingAlgorithm,
+    ) -> Result<ObjectHandle> {
+        let mut sequence_handle = ESYS_TR_NONE;
+        let ret = unsafe {
+            Esys_HashSequenceStart(
+                self.mut_context(),
+                self.optional_session_1(),
+                self.optional_session_2(),
+                self.optional_session_3(),
+                &auth.unwrap_or_default().into(),
+                hashing_algorithm.into(),
+                &mut sequence_handle,
+            )
+        };
+        let ret = Error::from_tss_rc(ret);
+
+        if ret.is_success() {
+            let sequence_handle = ObjectHandle::from(sequence_handle);
+            self.handle_manager
+                .add_handle(sequence_handle, HandleDropAction::Flush)?;
+            Ok(sequence_handle)
+        } else {
+            error!("Error in starting hash sequence: {}", ret);
+            Err(ret)
+        }
+    }
+
+    /// Adds data to a hash, HMAC, MAC or event sequence
+    ///
+    /// # Details
+    /// The sequence object requires an authorization session.
+    ///
+    /// # Errors
+    /// * if the first session is missing, a `MissingAuthSession` wrapper error will be returned
+    pub fn sequence_update(
+        &mut self,
+        sequence_handle: ObjectHandle,
+        buffer: MaxBuffer,
+    ) -> Result<()> {
+        let ret = unsafe {
+            Esys_SequenceUpdate(
+                self.mut_context(),
+                sequence_handle.into(),
+                self.required_session_1()?,
+                self.optional_session_2(),
+                self.optional_session_3(),
+                &buffer.into(),
+            )
+        };
+        let ret = Error::from_tss_rc(ret);
+
+        if ret.is_success() {
+            Ok(())
+        } else {
+            error!("Error in updating sequence: {}", ret);
+            Err(ret)
+        }
+    }
+
+    /// Adds the last data to a hash, HMAC or MAC sequence and returns the result
+    ///
+    /// # Details
+    /// The sequence object requires an authorization session and is
+    /// flushed by the TPM when the command succeeds.
+    ///
+    /// For hash sequences, the returned ticket can be used to prove that
+    /// the digest was computed by the TPM over data that did not start
+    /// with TPM_GENERATED_VALUE when it is signed with a restricted key.
+    /// For other sequences, or if `hierarchy` is [Hierarchy::Null], the
+    /// ticket is a null ticket.
+    ///
+    /// # Errors
+    /// * if the first session is missing, a `MissingAuthSession` wrapper error will be returned
+    pub fn sequence_complete(
+        &mut self,
+        sequence_handle: ObjectHandle,
+        buffer: MaxBuffer,
+        hierarchy: Hierarchy,
+    ) -> Result<(Digest, HashcheckTicket)> {
+        let mut result = null_mut();
+        let mut validation = null_mut();
+        let ret = unsafe {
+            Esys_SequenceComplete(
+                self.mut_context(),
+                sequence_handle.into(),
+                self.required_session_1()?,
+                self.optional_session_2(),
+                self.optional_session_3(),
+                &buffer.into(),
+                if cfg!(tpm2_tss_version = "3") {
+                    ObjectHandle::from(hierarchy).into()
+                } else {
+                    TpmHandle::from(hierarchy).into()
+                },
+                &mut result,
+                &mut validation,
+            )
+        };
+        let ret = Error::from_tss_rc(ret);
+
+        if ret.is_success() {
+            self.handle_manager.set_as_flushed(sequence_handle)?;
+            let result = unsafe { MBox::<TPM2B_DIGEST>::from_raw(result) };
+            let validation = unsafe { MBox::<TPMT_TK_HASHCHECK>::from_raw(validation) };
+            Ok((
+                Digest::try_from(*result)?,
+                HashcheckTicket::try_from(*validation)?,
+            ))
+        } else {
+            error!("Error in completing sequence: {}", ret);
+            Err(ret)
+        }
+    }
+
+    /// Adds the last data to an event sequence and extends the result into a PCR
+    ///
+    /// # Details
+    /// The digests computed for every PCR bank are extended into the PCR
+    /// identified by `pcr_handle` and returned. Both the PCR and the
+    /// sequence object require authorization, so two sessions have to be
+    /// set on the context. The sequence object is flushed by the TPM when
+    /// the command succeeds.
+    ///
+    /// # Errors
+    /// * if either of the first two sessions is missing, a `MissingAuthSession` wrapper error will be returned
+    pub fn event_sequence_complete(
+        &mut self,
+        pcr_handle: PcrHandle,
+        sequence_handle: ObjectHandle,
+        buffer: MaxBuffer,
+    ) -> Result<DigestValues> {
+        let mut results = null_mut();
+        let ret = unsafe {
+            Esys_EventSequenceComplete(
+                self.mut_context(),
+                pcr_handle.into(),
+                sequence_handle.into(),
+                self.required_session_1()?,
+                self.required_session_2()?,
+                self.optional_session_3(),
+                &buffer.into(),
+                &mut results,
+            )
+        };
+        let ret = Error::from_tss_rc(ret);
+
+        if ret.is_success() {
+            self.handle_manager.set_as_flushed(sequence_handle)?;
+            let results = unsafe { MBox::<TPML_DIGEST_VALUES>::from_raw(results) };
+            DigestValues::try_from(*results)
+        } else {
+            error!("Error in completing event sequence: {}", ret);
+            Err(ret)
+        }
+    }
 }
@@ -2,14 +2,18 @@
 // SPDX-License-Identifier: Apache-2.0
 use crate::{
     context::handle_manager::HandleDropAction,
-    handles::{AuthHandle, KeyHandle, ObjectHandle},
-    interface_types::resource_handles::Hierarchy,
+    handles::{AuthHandle, KeyHandle, ObjectHandle, TpmHandle},
+    interface_types::{
+        algorithm::HashingAlgorithm,
+        hierarchy_state::HierarchyState,
+        resource_handles::{Enables, Hierarchy, HierarchyPolicy, Platform},
+    },
     structures::{
         Auth, CreatePrimaryKeyResult, CreationData, CreationTicket, Data, Digest, PcrSelectionList,
         Public, SensitiveData,
     },
     tss2_esys::*,
-    Context, Error, Result,
+    Context, Error, Result, WrapperErrorKind,
 };
 use log::error;
 use mbox::MBox;
@@ -95,10 +99,131 @@ impl Context {
         }
     }
 
-    // Missing function: HierarchyControl
-    // Missing function: SetPrimaryPolicy
-    // Missing function: ChangePPS
-    // Missing function: ChangeEPS
+    /// Enable or disable the use of a hierarchy and its associated NV storage
+    ///
+    /// # Details
+    /// A hierarchy can only be disabled with the authorization of the
+    /// hierarchy itself or of the platform. Once disabled, the hierarchies
+    /// other than the platform one can only be enabled again with the
+    /// authorization of the platform, and the platform hierarchy can only
+    /// be enabled again by a reset of the TPM.
+    ///
+    /// # Errors
+    /// * if `auth_handle` is [Hierarchy::Null], an `InvalidParam` wrapper error is returned
+    pub fn hierarchy_control(
+        &mut self,
+        auth_handle: Hierarchy,
+        enable: Enables,
+        state: HierarchyState,
+    ) -> Result<()> {
+        if auth_handle == Hierarchy::Null {
+            error!("Error: The null hierarchy cannot authorize the control of a hierarchy");
+            return Err(Error::local_error(WrapperErrorKind::InvalidParam));
+        }
+        let ret = unsafe {
+            Esys_HierarchyControl(
+                self.mut_context(),
+                ObjectHandle::from(auth_handle).into(),
+                self.required_session_1()?,
+                self.optional_session_2(),
+                self.optional_session_3(),
+                TpmHandle::from(enable).into(),
+                state.into(),
+            )
+        };
+        let ret = Error::from_tss_rc(ret);
+
+        if ret.is_success() {
+            Ok(())
+        } else {
+            error!("Error in controlling hierarchy: {}", ret);
+            Err(ret)
+        }
+    }
+
+    /// Set the authorization policy of a hierarchy
+    ///
+    /// # Details
+    /// The policy is cleared by using an empty `auth_policy` along with
+    /// [HashingAlgorithm::Null].
+    pub fn set_primary_policy(
+        &mut self,
+        auth_handle: HierarchyPolicy,
+        auth_policy: Digest,
+        hash_alg: HashingAlgorithm,
+    ) -> Result<()> {
+        let ret = unsafe {
+            Esys_SetPrimaryPolicy(
+                self.mut_context(),
+                ObjectHandle::from(auth_handle).into(),
+                self.required_session_1()?,
+                self.optional_session_2(),
+                self.optional_session_3(),
+                &auth_policy.into(),
+                hash_alg.into(),
+            )
+        };
+        let ret = Error::from_tss_rc(ret);
+
+        if ret.is_success() {
+            Ok(())
+        } else {
+            error!("Error in setting primary policy: {}", ret);
+            Err(ret)
+        }
+    }
+
+    /// Replace the platform primary seed with a new value
+    ///
+    /// # Details
+    /// All the objects and sessions of the platform hierarchy are flushed,
+    /// and the platform hierarchy policy is reset.
+    pub fn change_pps(&mut self, auth_handle: Platform) -> Result<()> {
+        let ret = unsafe {
+            Esys_ChangePPS(
+                self.mut_context(),
+                AuthHandle::from(auth_handle).into(),
+                self.required_session_1()?,
+                self.optional_session_2(),
+                self.optional_session_3(),
+            )
+        };
+        let ret = Error::from_tss_rc(ret);
+
+        if ret.is_success() {
+            Ok(())
+        } else {
+            error!("Error in changing platform primary seed: {}", ret);
+            Err(ret)
+        }
+    }
+
+    /// Replace the endorsement primary seed with a new value
+    ///
+    /// # Details
+    /// All the objects and sessions of the endorsement hierarchy are
+    /// flushed, and the endorsement hierarchy policy and authorization
+    /// value are reset. The keys derived from the previous seed, such as
+    /// the endorsement keys, can no longer be recreated.
+    pub fn change_eps(&mut self, auth_handle: Platform) -> Result<()> {
+        let ret = unsafe {
+            Esys_ChangeEPS(
+                self.mut_context(),
+                AuthHandle::from(auth_handle).into(),
+                self.required_session_1()?,
+                self.optional_session_2(),
+                self.optional_session_3(),
+            )
+        };
+        let ret = Error::from_tss_rc(ret);
+
+        if ret.is_success() {
+            Ok(())
+        } else {
+            error!("Error in changing endorsement primary seed: {}", ret);
+            Err(ret)
+        }
+    }
 
     /// Clear all TPM context associated with a specific Owner
     pub fn clear(&mut self, auth_handle: AuthHandle) -> Result<()> {
@@ -1,11 +1,12 @@
 // Copyright 2021 Contributors to the Parsec project.
 // SPDX-License-Identifier: Apache-2.0
 use crate::{
-    handles::PcrHandle,
-    structures::{DigestValues, PcrSelectionList},
+    handles::{AuthHandle, PcrHandle, PcrTpmHandle},
+    interface_types::{algorithm::HashingAlgorithm, resource_handles::Platform},
+    structures::{Auth, Digest, DigestValues, Event, PcrAllocationResult, PcrSelectionList},
     tss2_esys::*,
     utils::PcrData,
-    Context, Error, Result,
+    Context, Error, Result, WrapperErrorKind,
 };
 use log::error;
 use mbox::MBox;
@@ -110,7 +111,88 @@ impl Context {
         }
     }
 
-    // Missing function: PCR_Event
+    /// Hashes event data and extends the digests into a PCR.
+    ///
+    /// # Arguments
+    /// * `pcr_handle` - A [PcrHandle] to the PCR slot that is to be extended.
+    /// * `event_data` - The [Event] data that is to be measured.
+    ///
+    /// # Details
+    /// The TPM computes the digest of `event_data` for every allocated
+    /// PCR bank and extends these digests into the indicated PCR. The
+    /// digests are returned, so that they can be recorded in an event log.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tss_esapi::{
+    /// #     Context, TctiNameConf,
+    /// #     constants::SessionType,
+    /// #     attributes::SessionAttributesBuilder,
+    /// #     structures::SymmetricDefinition,
+    /// # };
+    /// # use std::{env, str::FromStr};
+    /// # // Create context
+    /// # let mut context =
+    /// #     Context::new(
+    /// #         TctiNameConf::from_environment_variable().expect("Failed to get TCTI"),
+    /// #     ).expect("Failed to create Context");
+    /// # // Create session for a pcr
+    /// # let pcr_session = context
+    /// #     .start_auth_session(
+    /// #         None,
+    /// #         None,
+    /// #         None,
+    /// #         SessionType::Hmac,
+    /// #         SymmetricDefinition::AES_256_CFB,
+    /// #         tss_esapi::interface_types::algorithm::HashingAlgorithm::Sha256,
+    /// #     )
+    /// #     .expect("Failed to create session")
+    /// #     .expect("Recived invalid handle");
+    /// # let (session_attributes, session_attributes_mask) = SessionAttributesBuilder::new()
+    /// #     .with_decrypt(true)
+    /// #     .with_encrypt(true)
+    /// #     .build();
+    /// # context.tr_sess_set_attributes(pcr_session, session_attributes, session_attributes_mask)
+    /// #     .expect("Failed to set attributes on session");
+    /// use std::convert::TryFrom;
+    /// use tss_esapi::{
+    ///     handles::PcrHandle,
+    ///     interface_types::algorithm::HashingAlgorithm,
+    ///     structures::Event,
+    /// };
+    /// let event_data = Event::try_from(b"event data".to_vec())
+    ///     .expect("Failed to create event data");
+    /// // Use pcr_session for authorization when extending
+    /// // PCR 16 with the digests of the event data.
+    /// let digests = context.execute_with_session(Some(pcr_session), |ctx| {
+    ///     ctx.pcr_event(PcrHandle::Pcr16, event_data).expect("Call to pcr_event failed")
+    /// });
+    /// assert!(digests.get(HashingAlgorithm::Sha256).is_some());
+    /// ```
+    pub fn pcr_event(&mut self, pcr_handle: PcrHandle, event_data: Event) -> Result<DigestValues> {
+        let mut digests_ptr = null_mut();
+        let ret = unsafe {
+            Esys_PCR_Event(
+                self.mut_context(),
+                pcr_handle.into(),
+                self.optional_session_1(),
+                self.optional_session_2(),
+                self.optional_session_3(),
+                &event_data.into(),
+                &mut digests_ptr,
+            )
+        };
+        let ret = Error::from_tss_rc(ret);
+
+        if ret.is_success() {
+            let digests = unsafe { MBox::<TPML_DIGEST_VALUES>::from_raw(digests_ptr) };
+            DigestValues::try_from(*digests)
+        } else {
+            error!("Error when performing PCR event: {}", ret);
+            Err(ret)
+        }
+    }
 
     /// Reads the values of a PCR.
     ///
@@ -188,9 +270,149 @@ impl Context {
         }
     }
 
-    // Missing function: PCR_Allocate
-    // Missing function: PCR_SetAuthPolicy
-    // Missing function: PCR_SetAuthValue
+    /// Sets the allocation of the PCR banks.
+    ///
+    /// # Arguments
+    /// * `auth_handle` - The [Platform] authorization handle.
+    /// * `pcr_allocation` - A [PcrSelectionList] with the PCR slots that
+    ///   are to be allocated in each bank.
+    ///
+    /// # Details
+    /// The new allocation takes effect after the next TPM reset. The banks
+    /// that are not present in `pcr_allocation` are left unchanged.
+    ///
+    /// The returned [PcrAllocationResult] indicates whether the allocation
+    /// was accepted, the maximum number of PCRs per bank and the amount of
+    /// memory needed and available for the requested allocation.
+    ///
+    /// # Errors
+    /// * if the first session is missing, a `MissingAuthSession` wrapper error will be returned
+    pub fn pcr_allocate(
+        &mut self,
+        auth_handle: Platform,
+        pcr_allocation: PcrSelectionList,
+    ) -> Result<PcrAllocationResult> {
+        let mut allocation_success: TPMI_YES_NO = 0;
+        let mut max_pcr: u32 = 0;
+        let mut size_needed: u32 = 0;
+        let mut size_available: u32 = 0;
+        let ret = unsafe {
+            Esys_PCR_Allocate(
+                self.mut_context(),
+                AuthHandle::from(auth_handle).into(),
+                self.required_session_1()?,
+                self.optional_session_2(),
+                self.optional_session_3(),
+                &pcr_allocation.into(),
+                &mut allocation_success,
+                &mut max_pcr,
+                &mut size_needed,
+                &mut size_available,
+            )
+        };
+        let ret = Error::from_tss_rc(ret);
+
+        if ret.is_success() {
+            Ok(PcrAllocationResult {
+                allocation_success: match allocation_success {
+                    0 => false,
+                    1 => true,
+                    _ => {
+                        error!(
+                            "Error: Invalid TPMI_YES_NO value for allocation success ({})",
+                            allocation_success
+                        );
+                        return Err(Error::local_error(WrapperErrorKind::WrongValueFromTpm));
+                    }
+                },
+                max_pcr,
+                size_needed,
+                size_available,
+            })
+        } else {
+            error!("Error when allocating PCR banks: {}", ret);
+            Err(ret)
+        }
+    }
+
+    /// Sets the authorization policy of a group of PCRs.
+    ///
+    /// # Arguments
+    /// * `auth_handle` - The [Platform] authorization handle.
+    /// * `auth_policy` - The [Digest] of the new authorization policy.
+    /// * `hashing_algorithm` - The [HashingAlgorithm] used to compute the policy.
+    /// * `pcr_handle` - A [PcrTpmHandle] to a PCR in the group whose policy
+    ///   is to be set.
+    ///
+    /// # Details
+    /// An empty `auth_policy` together with [HashingAlgorithm::Null]
+    /// removes the policy of the PCR group.
+    ///
+    /// # Errors
+    /// * if the first session is missing, a `MissingAuthSession` wrapper error will be returned
+    pub fn pcr_set_auth_policy(
+        &mut self,
+        auth_handle: Platform,
+        auth_policy: Digest,
+        hashing_algorithm: HashingAlgorithm,
+        pcr_handle: PcrTpmHandle,
+    ) -> Result<()> {
+        let ret = unsafe {
+            Esys_PCR_SetAuthPolicy(
+                self.mut_context(),
+                AuthHandle::from(auth_handle).into(),
+                self.required_session_1()?,
+                self.optional_session_2(),
+                self.optional_session_3(),
+                &auth_policy.into(),
+                hashing_algorithm.into(),
+                pcr_handle.into(),
+            )
+        };
+        let ret = Error::from_tss_rc(ret);
+
+        if ret.is_success() {
+            Ok(())
+        } else {
+            error!("Error when setting PCR auth policy: {}", ret);
+            Err(ret)
+        }
+    }
+
+    /// Sets the authorization value of a group of PCRs.
+    ///
+    /// # Arguments
+    /// * `pcr_handle` - A [PcrHandle] to a PCR in the group whose
+    ///   authorization value is to be set.
+    /// * `auth` - The new authorization value.
+    ///
+    /// # Details
+    /// The PCR is authorized with its current authorization value, the
+    /// authorization value of `pcr_handle` has to be updated with
+    /// [Context::tr_set_auth] before it is used again.
+    ///
+    /// # Errors
+    /// * if the first session is missing, a `MissingAuthSession` wrapper error will be returned
+    pub fn pcr_set_auth_value(&mut self, pcr_handle: PcrHandle, auth: Auth) -> Result<()> {
+        let ret = unsafe {
+            Esys_PCR_SetAuthValue(
+                self.mut_context(),
+                pcr_handle.into(),
+                self.required_session_1()?,
+                self.optional_session_2(),
+                self.optional_session_3(),
+                &auth.into(),
+            )
+        };
+        let ret = Error::from_tss_rc(ret);
+
+        if ret.is_success() {
+            Ok(())
+        } else {
+            error!("Error when setting PCR auth value: {}", ret);
+            Err(ret)
+        }
+    }
 
     /// Resets the value in a PCR.
     ///
@@ -2,10 +2,10 @@
 // SPDX-License-Identifier: Apache-2.0
 use crate::{
     context::handle_manager::HandleDropAction,
-    handles::{AuthHandle, NvIndexHandle},
-    interface_types::resource_handles::{NvAuth, Provision},
+    handles::{AuthHandle, KeyHandle, NvIndexHandle},
+    interface_types::resource_handles::{NvAuth, Platform, Provision},
     nv::storage::NvPublic,
-    structures::{Auth, MaxNvBuffer, Name},
+    structures::{Attest, Auth, Data, MaxNvBuffer, Name, Signature, SignatureScheme},
     tss2_esys::*,
     Context, Error, Result,
 };
@@ -91,7 +91,44 @@ impl Context {
         }
     }
 
-    // Missing function: UndefineSpaceSpecial
+    /// Removes an nv index that has the `policy_delete` attribute.
+    ///
+    /// # Details
+    /// The method will instruct the TPM to remove a
+    /// nv index that was defined with the `policy_delete`
+    /// attribute. The nv index is authorized with a policy
+    /// session, in the admin role, and the platform hierarchy
+    /// with an additional session.
+    ///
+    /// # Arguments
+    /// * `nv_index_handle`- The [NvIndexHandle] associated with
+    ///                      the nv area that is to be removed.
+    /// * `platform` - The [Platform] used for authorization.
+    pub fn nv_undefine_space_special(
+        &mut self,
+        nv_index_handle: NvIndexHandle,
+        platform: Platform,
+    ) -> Result<()> {
+        let ret = unsafe {
+            Esys_NV_UndefineSpaceSpecial(
+                self.mut_context(),
+                nv_index_handle.into(),
+                AuthHandle::from(platform).into(),
+                self.optional_session_1(),
+                self.optional_session_2(),
+                self.optional_session_3(),
+            )
+        };
+
+        let ret = Error::from_tss_rc(ret);
+        if ret.is_success() {
+            self.handle_manager.set_as_closed(nv_index_handle.into())?;
+            Ok(())
+        } else {
+            error!("Error when undefining NV space special: {}", ret);
+            Err(ret)
+        }
+    }
 
     /// Reads the public part of an nv index.
     ///
@@ -159,11 +196,157 @@ impl Context {
         }
     }
 
-    // Missing function: NV_Increment
-    // Missing function: NV_Extend
-    // Missing function: NV_SetBits
-    // Missing function: NV_WriteLock
-    // Missing function: NV_GlobalWriteLock
+    /// Increments the value of a counter nv index.
+    ///
+    /// # Details
+    /// This method is used to increment the value of an
+    /// nv index that has been defined with the
+    /// [Counter](crate::constants::NvIndexType::Counter) type.
+    pub fn nv_increment(
+        &mut self,
+        auth_handle: NvAuth,
+        nv_index_handle: NvIndexHandle,
+    ) -> Result<()> {
+        let ret = unsafe {
+            Esys_NV_Increment(
+                self.mut_context(),
+                AuthHandle::from(auth_handle).into(),
+                nv_index_handle.into(),
+                self.optional_session_1(),
+                self.optional_session_2(),
+                self.optional_session_3(),
+            )
+        };
+        let ret = Error::from_tss_rc(ret);
+        if ret.is_success() {
+            Ok(())
+        } else {
+            error!("Error when incrementing NV: {}", ret);
+            Err(ret)
+        }
+    }
+
+    /// Extends data into an extend nv index.
+    ///
+    /// # Details
+    /// This method is used to extend data into an
+    /// nv index that has been defined with the
+    /// [Extend](crate::constants::NvIndexType::Extend) type,
+    /// using the name algorithm of the index.
+    pub fn nv_extend(
+        &mut self,
+        auth_handle: NvAuth,
+        nv_index_handle: NvIndexHandle,
+        data: &MaxNvBuffer,
+    ) -> Result<()> {
+        let ret = unsafe {
+            Esys_NV_Extend(
+                self.mut_context(),
+                AuthHandle::from(auth_handle).into(),
+                nv_index_handle.into(),
+                self.optional_session_1(),
+                self.optional_session_2(),
+                self.optional_session_3(),
+                &data.clone().into(),
+            )
+        };
+        let ret = Error::from_tss_rc(ret);
+        if ret.is_success() {
+            Ok(())
+        } else {
+            error!("Error when extending NV: {}", ret);
+            Err(ret)
+        }
+    }
+
+    /// Sets bits in a bit field nv index.
+    ///
+    /// # Details
+    /// This method is used to OR the provided `bits` into
+    /// the value of an nv index that has been defined with the
+    /// [Bits](crate::constants::NvIndexType::Bits) type.
+    pub fn nv_set_bits(
+        &mut self,
+        auth_handle: NvAuth,
+        nv_index_handle: NvIndexHandle,
+        bits: u64,
+    ) -> Result<()> {
+        let ret = unsafe {
+            Esys_NV_SetBits(
+                self.mut_context(),
+                AuthHandle::from(auth_handle).into(),
+                nv_index_handle.into(),
+                self.optional_session_1(),
+                self.optional_session_2(),
+                self.optional_session_3(),
+                bits,
+            )
+        };
+        let ret = Error::from_tss_rc(ret);
+        if ret.is_success() {
+            Ok(())
+        } else {
+            error!("Error when setting bits in NV: {}", ret);
+            Err(ret)
+        }
+    }
+
+    /// Prevents further writes to an nv index.
+    ///
+    /// # Details
+    /// This method is used to lock an nv index that has
+    /// been defined with the `write_define` or the `write_stclear`
+    /// attribute. With `write_stclear` the lock is removed by
+    /// the next TPM reset or restart, with `write_define` it is
+    /// permanent until the index is deleted.
+    pub fn nv_write_lock(
+        &mut self,
+        auth_handle: NvAuth,
+        nv_index_handle: NvIndexHandle,
+    ) -> Result<()> {
+        let ret = unsafe {
+            Esys_NV_WriteLock(
+                self.mut_context(),
+                AuthHandle::from(auth_handle).into(),
+                nv_index_handle.into(),
+                self.optional_session_1(),
+                self.optional_session_2(),
+                self.optional_session_3(),
+            )
+        };
+        let ret = Error::from_tss_rc(ret);
+        if ret.is_success() {
+            Ok(())
+        } else {
+            error!("Error when write locking NV: {}", ret);
+            Err(ret)
+        }
+    }
+
+    /// Prevents further writes to all nv indexes with the `global_lock` attribute.
+    ///
+    /// # Details
+    /// This method is used to write lock every nv index that has
+    /// been defined with the `global_lock` attribute, until the
+    /// next TPM reset or restart.
+    pub fn nv_global_write_lock(&mut self, auth_handle: Provision) -> Result<()> {
+        let ret = unsafe {
+            Esys_NV_GlobalWriteLock(
+                self.mut_context(),
+                AuthHandle::from(auth_handle).into(),
+                self.optional_session_1(),
+                self.optional_session_2(),
+                self.optional_session_3(),
+            )
+        };
+        let ret = Error::from_tss_rc(ret);
+        if ret.is_success() {
+            Ok(())
+        } else {
+            error!("Error when global write locking NV: {}", ret);
+            Err(ret)
+        }
+    }
 
     /// Reads data from the nv index.
     ///
@@ -202,7 +385,120 @@ impl Context {
         }
     }
 
-    // Missing function: NV_ReadLock
-    // Missing function: NV_ChangeAuth
-    // Missing function: NV_Certify
+    /// Prevents further reads of an nv index.
+    ///
+    /// # Details
+    /// This method is used to lock an nv index that has
+    /// been defined with the `read_stclear` attribute. The
+    /// lock is removed by the next TPM reset or restart.
+    pub fn nv_read_lock(
+        &mut self,
+        auth_handle: NvAuth,
+        nv_index_handle: NvIndexHandle,
+    ) -> Result<()> {
+        let ret = unsafe {
+            Esys_NV_ReadLock(
+                self.mut_context(),
+                AuthHandle::from(auth_handle).into(),
+                nv_index_handle.into(),
+                self.optional_session_1(),
+                self.optional_session_2(),
+                self.optional_session_3(),
+            )
+        };
+        let ret = Error::from_tss_rc(ret);
+        if ret.is_success() {
+            Ok(())
+        } else {
+            error!("Error when read locking NV: {}", ret);
+            Err(ret)
+        }
+    }
+
+    /// Changes the authorization value of an nv index.
+    ///
+    /// # Details
+    /// This method is used to change the authorization
+    /// value of an nv index. The command requires the admin
+    /// role, so the nv index has to be authorized with a
+    /// policy session. The new authorization value is used
+    /// for the nv index handle once the command succeeds.
+    pub fn nv_change_auth(&mut self, nv_index_handle: NvIndexHandle, new_auth: Auth) -> Result<()> {
+        let ret = unsafe {
+            Esys_NV_ChangeAuth(
+                self.mut_context(),
+                nv_index_handle.into(),
+                self.optional_session_1(),
+                self.optional_session_2(),
+                self.optional_session_3(),
+                &new_auth.into(),
+            )
+        };
+        let ret = Error::from_tss_rc(ret);
+        if ret.is_success() {
+            Ok(())
+        } else {
+            error!("Error when changing NV auth: {}", ret);
+            Err(ret)
+        }
+    }
+
+    /// Certifies the contents of an nv index.
+    ///
+    /// # Details
+    /// This method is used to sign `size` bytes of the data
+    /// of an nv index, starting at `offset`, with the key
+    /// identified by `signing_key_handle`. Both the key and the
+    /// nv index require authorization, so two sessions have to
+    /// be set on the context.
+    ///
+    /// The returned [Attest] holds a [NvCertifyInfo](crate::structures::NvCertifyInfo)
+    /// as attested data.
+    ///
+    /// # Errors
+    /// * if the qualifying data provided is too long, a `WrongParamSize` wrapper error will be returned
+    /// * if either of the first two sessions is missing, a `MissingAuthSession` wrapper error will be returned
+    #[allow(clippy::too_many_arguments)]
+    pub fn nv_certify(
+        &mut self,
+        signing_key_handle: KeyHandle,
+        auth_handle: NvAuth,
+        nv_index_handle: NvIndexHandle,
+        qualifying_data: Data,
+        signing_scheme: SignatureScheme,
+        size: u16,
+        offset: u16,
+    ) -> Result<(Attest, Signature)> {
+        let mut certify_info = null_mut();
+        let mut signature = null_mut();
+        let ret = unsafe {
+            Esys_NV_Certify(
+                self.mut_context(),
+                signing_key_handle.into(),
+                AuthHandle::from(auth_handle).into(),
+                nv_index_handle.into(),
+                self.required_session_1()?,
+                self.required_session_2()?,
+                self.optional_session_3(),
+                &qualifying_data.into(),
+                &signing_scheme.into(),
+                size,
+                offset,
+                &mut certify_info,
+                &mut signature,
+            )
+        };
+        let ret = Error::from_tss_rc(ret);
+        if ret.is_success() {
+            let certify_info = unsafe { MBox::<TPM2B_ATTEST>::from_raw(certify_info) };
+            let signature = unsafe { MBox::from_raw(signature) };
+            Ok((
+                Attest::try_from(*certify_info)?,
+                Signature::try_from(*signature)?,
+            ))
+        } else {
+            error!("Error when certifying NV: {}", ret);
+            Err(ret)
+        }
+    }
 }
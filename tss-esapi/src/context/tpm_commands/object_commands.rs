@@ -5,8 +5,9 @@ use crate::{
     handles::{KeyHandle, ObjectHandle, TpmHandle},
     interface_types::resource_handles::Hierarchy,
     structures::{
-        Auth, CreateKeyResult, CreationData, CreationTicket, Data, Digest, EncryptedSecret,
-        IDObject, Name, PcrSelectionList, Private, Public, SensitiveData,
+        Auth, CreateKeyResult, CreateLoadedKeyResult, CreationData, CreationTicket, Data, Digest,
+        EncryptedSecret, IDObject, Name, PcrSelectionList, Private, Public, SensitiveData,
+        Template,
     },
     tss2_esys::*,
     Context, Error, Result,
@@ -377,5 +378,78 @@ impl Context {
         }
     }
 
-    // Missing function: CreateLoaded
+    /// Create an object and load it in the TPM
+    ///
+    /// # Details
+    /// The `parent_handle` can be a hierarchy, in which case a primary
+    /// object is created, the handle of a storage parent, or the handle of
+    /// a derivation parent. In the last case, the object is derived from
+    /// the label and context carried by a template created with
+    /// [Template::new_derived], and the same object is returned every time
+    /// the same template is used. The TPM cannot derive RSA keys, and the
+    /// `sensitive_data_origin` attribute of a derived object must be clear,
+    /// as its sensitive data comes from the derivation parent.
+    ///
+    /// # Parameters
+    /// * `parent_handle` - The [ObjectHandle] of the parent of the new object.
+    /// * `template` - The public template of the object.
+    /// * `auth_value` - The value used to authorize the usage of the object.
+    /// * `sensitive_data` - The data that is to be sealed, a key or derivation values.
+    ///
+    /// # Errors
+    /// * if either of the slices is larger than the maximum size of the native objects, a
+    /// `WrongParamSize` wrapper error is returned
+    pub fn create_loaded(
+        &mut self,
+        parent_handle: ObjectHandle,
+        template: Template,
+        auth_value: Option<&Auth>,
+        sensitive_data: Option<&SensitiveData>,
+    ) -> Result<CreateLoadedKeyResult> {
+        let sensitive_create = TPM2B_SENSITIVE_CREATE {
+            size: std::mem::size_of::<TPMS_SENSITIVE_CREATE>()
+                .try_into()
+                .unwrap(), // will not fail on targets of at least 16 bits
+            sensitive: TPMS_SENSITIVE_CREATE {
+                userAuth: auth_value.cloned().unwrap_or_default().into(),
+                data: sensitive_data.cloned().unwrap_or_default().into(),
+            },
+        };
+
+        let mut esys_key_handle = ESYS_TR_NONE;
+        let mut out_private_ptr = null_mut();
+        let mut out_public_ptr = null_mut();
+
+        let ret = unsafe {
+            Esys_CreateLoaded(
+                self.mut_context(),
+                parent_handle.into(),
+                self.required_session_1()?,
+                self.optional_session_2(),
+                self.optional_session_3(),
+                &sensitive_create,
+                &template.into(),
+                &mut esys_key_handle,
+                &mut out_private_ptr,
+                &mut out_public_ptr,
+            )
+        };
+        let ret = Error::from_tss_rc(ret);
+
+        if ret.is_success() {
+            let out_private_owned = unsafe { MBox::from_raw(out_private_ptr) };
+            let out_public_owned = unsafe { MBox::from_raw(out_public_ptr) };
+            let key_handle = KeyHandle::from(esys_key_handle);
+            self.handle_manager
+                .add_handle(key_handle.into(), HandleDropAction::Flush)?;
+            Ok(CreateLoadedKeyResult {
+                key_handle,
+                out_private: Private::try_from(*out_private_owned)?,
+                out_public: Public::try_from(*out_public_owned)?,
+            })
+        } else {
+            error!("Error in creating and loading object: {}", ret);
+            Err(ret)
+        }
+    }
 }
@@ -9,6 +9,7 @@ use crate::{
     Error, Result, WrapperErrorKind,
 };
 use log::error;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::convert::{From, TryFrom};
 use std::stringify;
 
@@ -102,6 +103,26 @@ impl TryFrom<TPM2_HANDLE> for TpmHandle {
     }
 }
 
+impl Serialize for TpmHandle {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        TPM2_HANDLE::from(*self).serialize(serializer)
+    }
+}
+
+// The type and range of the handle are checked when it is deserialized.
+impl<'de> Deserialize<'de> for TpmHandle {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let value = TPM2_HANDLE::deserialize(deserializer)?;
+        TpmHandle::try_from(value).map_err(serde::de::Error::custom)
+    }
+}
+
 /// Macro for creating the specific TPM handle types
 macro_rules! create_tpm_handle_type {
     ($handle_type_name:ident, $tpm_handle_kind:path, $tpm_handle_type_id:tt, $tpm_handle_type_first:tt, $tpm_handle_type_last:tt) => {
@@ -167,6 +188,25 @@ macro_rules! create_tpm_handle_type {
                 $handle_type_name::new(tss_tpm_handle)
             }
         }
+
+        impl Serialize for $handle_type_name {
+            fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+            where
+                S: Serializer,
+            {
+                self.value.serialize(serializer)
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $handle_type_name {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+            where
+                D: Deserializer<'de>,
+            {
+                let value = TPM2_HANDLE::deserialize(deserializer)?;
+                $handle_type_name::new(value).map_err(serde::de::Error::custom)
+            }
+        }
     };
 }
 
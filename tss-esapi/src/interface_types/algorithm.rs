@@ -3,18 +3,21 @@
 use crate::{
     constants::AlgorithmIdentifier,
     tss2_esys::{
-        TPMI_ALG_ASYM, TPMI_ALG_ECC_SCHEME, TPMI_ALG_HASH, TPMI_ALG_KDF, TPMI_ALG_KEYEDHASH_SCHEME,
-        TPMI_ALG_PUBLIC, TPMI_ALG_RSA_DECRYPT, TPMI_ALG_RSA_SCHEME, TPMI_ALG_SIG_SCHEME,
-        TPMI_ALG_SYM, TPMI_ALG_SYM_MODE, TPMI_ALG_SYM_OBJECT,
+        TPM2_SHA1_DIGEST_SIZE, TPM2_SHA256_DIGEST_SIZE, TPM2_SHA384_DIGEST_SIZE,
+        TPM2_SHA512_DIGEST_SIZE, TPM2_SM3_256_DIGEST_SIZE, TPMI_ALG_ASYM, TPMI_ALG_ECC_SCHEME,
+        TPMI_ALG_HASH, TPMI_ALG_KDF, TPMI_ALG_KEYEDHASH_SCHEME, TPMI_ALG_PUBLIC,
+        TPMI_ALG_RSA_DECRYPT, TPMI_ALG_RSA_SCHEME, TPMI_ALG_SIG_SCHEME, TPMI_ALG_SYM,
+        TPMI_ALG_SYM_MODE, TPMI_ALG_SYM_OBJECT,
     },
     Error, Result, WrapperErrorKind,
 };
+use serde::{Deserialize, Serialize};
 use std::convert::TryFrom;
 /// Enum containing the supported hash algorithms
 ///
 /// # Details
 /// This corresponds to TPMI_ALG_HASH interface type.
-#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum HashingAlgorithm {
     Sha1,
     Sha256,
@@ -27,6 +30,26 @@ pub enum HashingAlgorithm {
     Null,
 }
 
+impl HashingAlgorithm {
+    /// Returns the size in bytes of the digests produced by the algorithm
+    ///
+    /// # Details
+    /// [HashingAlgorithm::Null] does not produce digests and returns `None`.
+    pub const fn digest_size(&self) -> Option<usize> {
+        match self {
+            HashingAlgorithm::Sha1 => Some(TPM2_SHA1_DIGEST_SIZE as usize),
+            HashingAlgorithm::Sha256 => Some(TPM2_SHA256_DIGEST_SIZE as usize),
+            HashingAlgorithm::Sha384 => Some(TPM2_SHA384_DIGEST_SIZE as usize),
+            HashingAlgorithm::Sha512 => Some(TPM2_SHA512_DIGEST_SIZE as usize),
+            HashingAlgorithm::Sm3_256 => Some(TPM2_SM3_256_DIGEST_SIZE as usize),
+            HashingAlgorithm::Sha3_256 => Some(TPM2_SHA256_DIGEST_SIZE as usize),
+            HashingAlgorithm::Sha3_384 => Some(TPM2_SHA384_DIGEST_SIZE as usize),
+            HashingAlgorithm::Sha3_512 => Some(TPM2_SHA512_DIGEST_SIZE as usize),
+            HashingAlgorithm::Null => None,
+        }
+    }
+}
+
 impl From<HashingAlgorithm> for AlgorithmIdentifier {
     fn from(hashing_algorithm: HashingAlgorithm) -> Self {
         match hashing_algorithm {
@@ -683,3 +706,74 @@ impl TryFrom<TPMI_ALG_RSA_DECRYPT> for RsaDecryptAlgorithm {
         RsaDecryptAlgorithm::try_from(AlgorithmIdentifier::try_from(tpmi_alg_rsa_decrypt)?)
     }
 }
+
+/// Enum representing the mac scheme interface type
+///
+/// # Details
+/// This corresponds to TPMI_ALG_MAC_SCHEME
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum MacAlgorithm {
+    Sha1,
+    Sha256,
+    Sha384,
+    Sha512,
+    Sm3_256,
+    Sha3_256,
+    Sha3_384,
+    Sha3_512,
+    Cmac,
+    Null,
+}
+
+impl From<MacAlgorithm> for AlgorithmIdentifier {
+    fn from(mac_algorithm: MacAlgorithm) -> Self {
+        match mac_algorithm {
+            MacAlgorithm::Sha1 => AlgorithmIdentifier::Sha1,
+            MacAlgorithm::Sha256 => AlgorithmIdentifier::Sha256,
+            MacAlgorithm::Sha384 => AlgorithmIdentifier::Sha384,
+            MacAlgorithm::Sha512 => AlgorithmIdentifier::Sha512,
+            MacAlgorithm::Sm3_256 => AlgorithmIdentifier::Sm3_256,
+            MacAlgorithm::Sha3_256 => AlgorithmIdentifier::Sha3_256,
+            MacAlgorithm::Sha3_384 => AlgorithmIdentifier::Sha3_384,
+            MacAlgorithm::Sha3_512 => AlgorithmIdentifier::Sha3_512,
+            MacAlgorithm::Cmac => AlgorithmIdentifier::Cmac,
+            MacAlgorithm::Null => AlgorithmIdentifier::Null,
+        }
+    }
+}
+
+impl TryFrom<AlgorithmIdentifier> for MacAlgorithm {
+    type Error = Error;
+
+    fn try_from(algorithm_identifier: AlgorithmIdentifier) -> Result<Self> {
+        match algorithm_identifier {
+            AlgorithmIdentifier::Sha1 => Ok(MacAlgorithm::Sha1),
+            AlgorithmIdentifier::Sha256 => Ok(MacAlgorithm::Sha256),
+            AlgorithmIdentifier::Sha384 => Ok(MacAlgorithm::Sha384),
+            AlgorithmIdentifier::Sha512 => Ok(MacAlgorithm::Sha512),
+            AlgorithmIdentifier::Sm3_256 => Ok(MacAlgorithm::Sm3_256),
+            AlgorithmIdentifier::Sha3_256 => Ok(MacAlgorithm::Sha3_256),
+            AlgorithmIdentifier::Sha3_384 => Ok(MacAlgorithm::Sha3_384),
+            AlgorithmIdentifier::Sha3_512 => Ok(MacAlgorithm::Sha3_512),
+            AlgorithmIdentifier::Cmac => Ok(MacAlgorithm::Cmac),
+            AlgorithmIdentifier::Null => Ok(MacAlgorithm::Null),
+            _ => Err(Error::local_error(WrapperErrorKind::InvalidParam)),
+        }
+    }
+}
+
+impl From<HashingAlgorithm> for MacAlgorithm {
+    fn from(hashing_algorithm: HashingAlgorithm) -> Self {
+        match hashing_algorithm {
+            HashingAlgorithm::Sha1 => MacAlgorithm::Sha1,
+            HashingAlgorithm::Sha256 => MacAlgorithm::Sha256,
+            HashingAlgorithm::Sha384 => MacAlgorithm::Sha384,
+            HashingAlgorithm::Sha512 => MacAlgorithm::Sha512,
+            HashingAlgorithm::Sm3_256 => MacAlgorithm::Sm3_256,
+            HashingAlgorithm::Sha3_256 => MacAlgorithm::Sha3_256,
+            HashingAlgorithm::Sha3_384 => MacAlgorithm::Sha3_384,
+            HashingAlgorithm::Sha3_512 => MacAlgorithm::Sha3_512,
+            HashingAlgorithm::Null => MacAlgorithm::Null,
+        }
+    }
+}
@@ -0,0 +1,20 @@
+// Copyright 2021 Contributors to the Parsec project.
+// SPDX-License-Identifier: Apache-2.0
+use crate::tss2_esys::TPMI_YES_NO;
+
+/// Enum representing the state a hierarchy is set to
+/// by [Context::hierarchy_control](crate::Context::hierarchy_control).
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum HierarchyState {
+    Enabled,
+    Disabled,
+}
+
+impl From<HierarchyState> for TPMI_YES_NO {
+    fn from(hierarchy_state: HierarchyState) -> TPMI_YES_NO {
+        match hierarchy_state {
+            HierarchyState::Enabled => 1,
+            HierarchyState::Disabled => 0,
+        }
+    }
+}
@@ -6,6 +6,7 @@
 pub mod algorithm;
 pub mod dynamic_handles;
 pub mod ecc;
+pub mod hierarchy_state;
 pub mod key_bits;
 pub mod resource_handles;
 pub mod session_handles;
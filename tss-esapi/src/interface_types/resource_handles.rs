@@ -7,6 +7,7 @@ use crate::{
     },
     Error, Result, WrapperErrorKind,
 };
+use serde::{Deserialize, Serialize};
 use std::convert::TryFrom;
 //////////////////////////////////////////////////////////////////////////////////
 /// Hierarchy
@@ -207,8 +208,68 @@ impl TryFrom<TpmHandle> for HierarchyAuth {
     }
 }
 //////////////////////////////////////////////////////////////////////////////////
-/// TODO: HierarchyPolicy
+/// HierarchyPolicy
 //////////////////////////////////////////////////////////////////////////////////
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HierarchyPolicy {
+    Owner,
+    Platform,
+    Endorsement,
+    Lockout,
+}
+
+impl From<HierarchyPolicy> for ObjectHandle {
+    fn from(hierarchy_policy: HierarchyPolicy) -> ObjectHandle {
+        match hierarchy_policy {
+            HierarchyPolicy::Owner => ObjectHandle::Owner,
+            HierarchyPolicy::Platform => ObjectHandle::Platform,
+            HierarchyPolicy::Endorsement => ObjectHandle::Endorsement,
+            HierarchyPolicy::Lockout => ObjectHandle::Lockout,
+        }
+    }
+}
+
+impl From<HierarchyPolicy> for TpmHandle {
+    fn from(hierarchy_policy: HierarchyPolicy) -> TpmHandle {
+        match hierarchy_policy {
+            HierarchyPolicy::Owner => TpmHandle::Permanent(PermanentTpmHandle::Owner),
+            HierarchyPolicy::Platform => TpmHandle::Permanent(PermanentTpmHandle::Platform),
+            HierarchyPolicy::Endorsement => TpmHandle::Permanent(PermanentTpmHandle::Endorsement),
+            HierarchyPolicy::Lockout => TpmHandle::Permanent(PermanentTpmHandle::Lockout),
+        }
+    }
+}
+
+impl TryFrom<ObjectHandle> for HierarchyPolicy {
+    type Error = Error;
+
+    fn try_from(object_handle: ObjectHandle) -> Result<HierarchyPolicy> {
+        match object_handle {
+            ObjectHandle::Owner => Ok(HierarchyPolicy::Owner),
+            ObjectHandle::Platform => Ok(HierarchyPolicy::Platform),
+            ObjectHandle::Endorsement => Ok(HierarchyPolicy::Endorsement),
+            ObjectHandle::Lockout => Ok(HierarchyPolicy::Lockout),
+            _ => Err(Error::local_error(WrapperErrorKind::InvalidParam)),
+        }
+    }
+}
+
+impl TryFrom<TpmHandle> for HierarchyPolicy {
+    type Error = Error;
+
+    fn try_from(tpm_handle: TpmHandle) -> Result<HierarchyPolicy> {
+        match tpm_handle {
+            TpmHandle::Permanent(permanent_handle) => match permanent_handle {
+                PermanentTpmHandle::Owner => Ok(HierarchyPolicy::Owner),
+                PermanentTpmHandle::Platform => Ok(HierarchyPolicy::Platform),
+                PermanentTpmHandle::Endorsement => Ok(HierarchyPolicy::Endorsement),
+                PermanentTpmHandle::Lockout => Ok(HierarchyPolicy::Lockout),
+                _ => Err(Error::local_error(WrapperErrorKind::InvalidParam)),
+            },
+            _ => Err(Error::local_error(WrapperErrorKind::InvalidParam)),
+        }
+    }
+}
 
 //////////////////////////////////////////////////////////////////////////////////
 /// Platform
@@ -293,9 +354,36 @@ impl TryFrom<ObjectHandle> for Endorsement {
     }
 }
 //////////////////////////////////////////////////////////////////////////////////
-/// Provision
+/// Privacy
+///
+/// The handle used for privacy administration
+/// when retrieving time and audit attestations.
 //////////////////////////////////////////////////////////////////////////////////
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Privacy {
+    Endorsement,
+}
+
+impl From<Privacy> for AuthHandle {
+    fn from(_: Privacy) -> AuthHandle {
+        AuthHandle::Endorsement
+    }
+}
+
+impl TryFrom<AuthHandle> for Privacy {
+    type Error = Error;
+
+    fn try_from(auth_handle: AuthHandle) -> Result<Privacy> {
+        match auth_handle {
+            AuthHandle::Endorsement => Ok(Privacy::Endorsement),
+            _ => Err(Error::local_error(WrapperErrorKind::InvalidParam)),
+        }
+    }
+}
+//////////////////////////////////////////////////////////////////////////////////
+/// Provision
+//////////////////////////////////////////////////////////////////////////////////
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Provision {
     Owner,
     Platform,
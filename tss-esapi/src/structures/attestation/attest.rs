@@ -0,0 +1,200 @@
+// Copyright 2021 Contributors to the Parsec project.
+// SPDX-License-Identifier: Apache-2.0
+use crate::{
+    constants::{tss::TPM2_GENERATED_VALUE, StructureTag},
+    structures::{AttestInfo, ClockInfo, Data, Name},
+    tss2_esys::{
+        size_t, Tss2_MU_TPMS_ATTEST_Marshal, Tss2_MU_TPMS_ATTEST_Unmarshal, TPM2B_ATTEST,
+        TPMS_ATTEST,
+    },
+    Error, Result, WrapperErrorKind,
+};
+use log::error;
+use std::convert::{TryFrom, TryInto};
+
+/// Structure holding the attestation data that
+/// is signed by the TPM.
+///
+/// # Details
+/// This corresponds to the TPMS_ATTEST.
+///
+/// The signature produced by the TPM is computed over
+/// the marshalled form of this structure. Converting
+/// the marshalled data into an [Attest] and back with
+/// [Attest::marshall] will produce the exact same bytes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Attest {
+    qualified_signer: Name,
+    extra_data: Data,
+    clock_info: ClockInfo,
+    firmware_version: u64,
+    attested: AttestInfo,
+}
+
+impl Attest {
+    /// Returns the structure tag that identifies
+    /// the type of the attestation.
+    pub fn attestation_type(&self) -> StructureTag {
+        self.attested.tag()
+    }
+
+    /// Returns the qualified name of the signing key.
+    pub const fn qualified_signer(&self) -> &Name {
+        &self.qualified_signer
+    }
+
+    /// Returns the external information supplied by the caller
+    /// (i.e. the qualifying data).
+    pub const fn extra_data(&self) -> &Data {
+        &self.extra_data
+    }
+
+    /// Returns the clock information of the TPM.
+    pub const fn clock_info(&self) -> &ClockInfo {
+        &self.clock_info
+    }
+
+    /// Returns the TPM vendor specific firmware version.
+    pub const fn firmware_version(&self) -> u64 {
+        self.firmware_version
+    }
+
+    /// Returns the type specific attested data.
+    pub const fn attested(&self) -> &AttestInfo {
+        &self.attested
+    }
+
+    /// Marshalls the attestation data into the byte
+    /// form over which the TPM computes the signature.
+    pub fn marshall(&self) -> Result<Vec<u8>> {
+        let tpms_attest = TPMS_ATTEST::try_from(self.clone())?;
+        let mut buffer = vec![0; std::mem::size_of::<TPMS_ATTEST>()];
+        let mut offset: size_t = 0;
+        let ret = Error::from_tss_rc(unsafe {
+            Tss2_MU_TPMS_ATTEST_Marshal(
+                &tpms_attest,
+                buffer.as_mut_ptr(),
+                buffer.len().try_into().map_err(|e| {
+                    error!("Failed to convert size of buffer to TSS size_t type: {}", e);
+                    Error::local_error(WrapperErrorKind::InvalidParam)
+                })?,
+                &mut offset,
+            )
+        });
+        if !ret.is_success() {
+            error!("Error when marshalling attestation data: {}", ret);
+            return Err(ret);
+        }
+        let checked_offset = usize::try_from(offset).map_err(|e| {
+            error!("Failed to parse offset as usize: {}", e);
+            Error::local_error(WrapperErrorKind::InvalidParam)
+        })?;
+        buffer.truncate(checked_offset);
+        Ok(buffer)
+    }
+
+    /// Unmarshalls the attestation data from the byte
+    /// form over which the TPM computes the signature.
+    ///
+    /// # Errors
+    /// * if the marshalled data contains trailing bytes, a `WrongParamSize`
+    ///   wrapper error is returned.
+    pub fn unmarshall(marshalled_data: &[u8]) -> Result<Self> {
+        let mut tpms_attest = TPMS_ATTEST::default();
+        let mut offset: size_t = 0;
+        let buffer_size: size_t = marshalled_data.len().try_into().map_err(|e| {
+            error!("Failed to convert size of buffer to TSS size_t type: {}", e);
+            Error::local_error(WrapperErrorKind::InvalidParam)
+        })?;
+        let ret = Error::from_tss_rc(unsafe {
+            Tss2_MU_TPMS_ATTEST_Unmarshal(
+                marshalled_data.as_ptr(),
+                buffer_size,
+                &mut offset,
+                &mut tpms_attest,
+            )
+        });
+        if !ret.is_success() {
+            error!("Error when unmarshalling attestation data: {}", ret);
+            return Err(ret);
+        }
+        if offset != buffer_size {
+            error!("Error: Found trailing bytes after the attestation data");
+            return Err(Error::local_error(WrapperErrorKind::WrongParamSize));
+        }
+        Attest::try_from(tpms_attest)
+    }
+}
+
+impl TryFrom<TPMS_ATTEST> for Attest {
+    type Error = Error;
+
+    fn try_from(tpms_attest: TPMS_ATTEST) -> Result<Self> {
+        if tpms_attest.magic != TPM2_GENERATED_VALUE {
+            error!(
+                "Error: Invalid magic value in attestation data ({:#010x})",
+                tpms_attest.magic
+            );
+            return Err(Error::local_error(WrapperErrorKind::InvalidParam));
+        }
+        let tag = StructureTag::try_from(tpms_attest.type_)?;
+        Ok(Attest {
+            qualified_signer: tpms_attest.qualifiedSigner.try_into()?,
+            extra_data: tpms_attest.extraData.try_into()?,
+            clock_info: tpms_attest.clockInfo.try_into()?,
+            firmware_version: tpms_attest.firmwareVersion,
+            attested: AttestInfo::try_from_tpmu_attest(tag, tpms_attest.attested)?,
+        })
+    }
+}
+
+impl TryFrom<Attest> for TPMS_ATTEST {
+    type Error = Error;
+
+    fn try_from(attest: Attest) -> Result<Self> {
+        Ok(TPMS_ATTEST {
+            magic: TPM2_GENERATED_VALUE,
+            type_: attest.attestation_type().into(),
+            qualifiedSigner: attest.qualified_signer.try_into()?,
+            extraData: attest.extra_data.into(),
+            clockInfo: attest.clock_info.into(),
+            firmwareVersion: attest.firmware_version,
+            attested: attest.attested.try_into()?,
+        })
+    }
+}
+
+impl TryFrom<TPM2B_ATTEST> for Attest {
+    type Error = Error;
+
+    fn try_from(tpm2b_attest: TPM2B_ATTEST) -> Result<Self> {
+        let size = tpm2b_attest.size as usize;
+        if size > tpm2b_attest.attestationData.len() {
+            error!(
+                "Error: Invalid TPM2B_ATTEST size(> {})",
+                tpm2b_attest.attestationData.len()
+            );
+            return Err(Error::local_error(WrapperErrorKind::WrongParamSize));
+        }
+        Attest::unmarshall(&tpm2b_attest.attestationData[..size])
+    }
+}
+
+impl TryFrom<Attest> for TPM2B_ATTEST {
+    type Error = Error;
+
+    fn try_from(attest: Attest) -> Result<Self> {
+        let marshalled_data = attest.marshall()?;
+        let mut tpm2b_attest = TPM2B_ATTEST::default();
+        if marshalled_data.len() > tpm2b_attest.attestationData.len() {
+            error!(
+                "Error: Marshalled attestation data is too large(> {})",
+                tpm2b_attest.attestationData.len()
+            );
+            return Err(Error::local_error(WrapperErrorKind::WrongParamSize));
+        }
+        tpm2b_attest.size = marshalled_data.len() as u16;
+        tpm2b_attest.attestationData[..marshalled_data.len()].copy_from_slice(&marshalled_data);
+        Ok(tpm2b_attest)
+    }
+}
@@ -0,0 +1,104 @@
+// Copyright 2021 Contributors to the Parsec project.
+// SPDX-License-Identifier: Apache-2.0
+use crate::{
+    constants::StructureTag,
+    structures::{
+        CertifyInfo, CommandAuditInfo, CreationInfo, NvCertifyInfo, QuoteInfo, SessionAuditInfo,
+        TimeAttestInfo,
+    },
+    tss2_esys::TPMU_ATTEST,
+    Error, Result, WrapperErrorKind,
+};
+use log::error;
+use std::convert::{TryFrom, TryInto};
+
+/// Enum representing the type specific attested data.
+///
+/// # Details
+/// This corresponds to the TPMU_ATTEST union and the
+/// variant is selected by the type of the attestation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AttestInfo {
+    Certify(CertifyInfo),
+    Quote(QuoteInfo),
+    SessionAudit(SessionAuditInfo),
+    CommandAudit(CommandAuditInfo),
+    Time(TimeAttestInfo),
+    Creation(CreationInfo),
+    Nv(NvCertifyInfo),
+}
+
+impl AttestInfo {
+    /// Returns the structure tag that identifies
+    /// the type of the attested data.
+    pub fn tag(&self) -> StructureTag {
+        match self {
+            AttestInfo::Certify(_) => StructureTag::AttestCertify,
+            AttestInfo::Quote(_) => StructureTag::AttestQuote,
+            AttestInfo::SessionAudit(_) => StructureTag::AttestSessionAudit,
+            AttestInfo::CommandAudit(_) => StructureTag::AttestCommandAudit,
+            AttestInfo::Time(_) => StructureTag::AttestTime,
+            AttestInfo::Creation(_) => StructureTag::AttestCreation,
+            AttestInfo::Nv(_) => StructureTag::AttestNv,
+        }
+    }
+
+    /// Parses the attested data from the TPMU_ATTEST
+    /// union using the provided tag as selector.
+    pub(crate) fn try_from_tpmu_attest(
+        tag: StructureTag,
+        tpmu_attest: TPMU_ATTEST,
+    ) -> Result<Self> {
+        match tag {
+            StructureTag::AttestCertify => Ok(AttestInfo::Certify(
+                unsafe { tpmu_attest.certify }.try_into()?,
+            )),
+            StructureTag::AttestQuote => {
+                Ok(AttestInfo::Quote(unsafe { tpmu_attest.quote }.try_into()?))
+            }
+            StructureTag::AttestSessionAudit => Ok(AttestInfo::SessionAudit(
+                unsafe { tpmu_attest.sessionAudit }.try_into()?,
+            )),
+            StructureTag::AttestCommandAudit => Ok(AttestInfo::CommandAudit(
+                unsafe { tpmu_attest.commandAudit }.try_into()?,
+            )),
+            StructureTag::AttestTime => {
+                Ok(AttestInfo::Time(unsafe { tpmu_attest.time }.try_into()?))
+            }
+            StructureTag::AttestCreation => Ok(AttestInfo::Creation(
+                unsafe { tpmu_attest.creation }.try_into()?,
+            )),
+            StructureTag::AttestNv => Ok(AttestInfo::Nv(unsafe { tpmu_attest.nv }.try_into()?)),
+            _ => {
+                error!("Error: Unsupported attestation type {:?}", tag);
+                Err(Error::local_error(WrapperErrorKind::UnsupportedParam))
+            }
+        }
+    }
+}
+
+impl TryFrom<AttestInfo> for TPMU_ATTEST {
+    type Error = Error;
+
+    fn try_from(attest_info: AttestInfo) -> Result<Self> {
+        Ok(match attest_info {
+            AttestInfo::Certify(info) => TPMU_ATTEST {
+                certify: info.try_into()?,
+            },
+            AttestInfo::Quote(info) => TPMU_ATTEST { quote: info.into() },
+            AttestInfo::SessionAudit(info) => TPMU_ATTEST {
+                sessionAudit: info.into(),
+            },
+            AttestInfo::CommandAudit(info) => TPMU_ATTEST {
+                commandAudit: info.into(),
+            },
+            AttestInfo::Time(info) => TPMU_ATTEST { time: info.into() },
+            AttestInfo::Creation(info) => TPMU_ATTEST {
+                creation: info.try_into()?,
+            },
+            AttestInfo::Nv(info) => TPMU_ATTEST {
+                nv: info.try_into()?,
+            },
+        })
+    }
+}
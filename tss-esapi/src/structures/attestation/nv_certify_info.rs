@@ -0,0 +1,60 @@
+// Copyright 2021 Contributors to the Parsec project.
+// SPDX-License-Identifier: Apache-2.0
+use crate::{
+    structures::{MaxNvBuffer, Name},
+    tss2_esys::TPMS_NV_CERTIFY_INFO,
+    Error, Result,
+};
+use std::convert::{TryFrom, TryInto};
+
+/// Structure holding the attested data for TPM2_NV_Certify().
+///
+/// # Details
+/// This corresponds to the TPMS_NV_CERTIFY_INFO.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NvCertifyInfo {
+    index_name: Name,
+    offset: u16,
+    nv_contents: MaxNvBuffer,
+}
+
+impl NvCertifyInfo {
+    /// Returns the name of the NV index.
+    pub const fn index_name(&self) -> &Name {
+        &self.index_name
+    }
+
+    /// Returns the offset of the certified data within the NV index.
+    pub const fn offset(&self) -> u16 {
+        self.offset
+    }
+
+    /// Returns the certified contents of the NV index.
+    pub const fn nv_contents(&self) -> &MaxNvBuffer {
+        &self.nv_contents
+    }
+}
+
+impl TryFrom<TPMS_NV_CERTIFY_INFO> for NvCertifyInfo {
+    type Error = Error;
+
+    fn try_from(tpms_nv_certify_info: TPMS_NV_CERTIFY_INFO) -> Result<Self> {
+        Ok(NvCertifyInfo {
+            index_name: tpms_nv_certify_info.indexName.try_into()?,
+            offset: tpms_nv_certify_info.offset,
+            nv_contents: tpms_nv_certify_info.nvContents.try_into()?,
+        })
+    }
+}
+
+impl TryFrom<NvCertifyInfo> for TPMS_NV_CERTIFY_INFO {
+    type Error = Error;
+
+    fn try_from(nv_certify_info: NvCertifyInfo) -> Result<Self> {
+        Ok(TPMS_NV_CERTIFY_INFO {
+            indexName: nv_certify_info.index_name.try_into()?,
+            offset: nv_certify_info.offset,
+            nvContents: nv_certify_info.nv_contents.into(),
+        })
+    }
+}
@@ -91,6 +91,33 @@ macro_rules! buffer_type {
     };
 }
 
+// Only buffers that are safe to store, such as the ones making up policies, are
+// serializable. Buffers holding secrets must not be.
+#[allow(unused_macros)]
+macro_rules! serializable_buffer_type {
+    ($native_type:ident) => {
+        impl serde::Serialize for $native_type {
+            fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+            where
+                S: serde::Serializer,
+            {
+                serde::Serialize::serialize(self.value(), serializer)
+            }
+        }
+
+        // The size of the buffer is checked when it is deserialized.
+        impl<'de> serde::Deserialize<'de> for $native_type {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                let bytes = <Vec<u8> as serde::Deserialize>::deserialize(deserializer)?;
+                $native_type::try_from(bytes).map_err(serde::de::Error::custom)
+            }
+        }
+    };
+}
+
 pub mod public;
 
 pub mod auth {
@@ -105,11 +132,90 @@ pub mod sensitive_data {
     buffer_type!(SensitiveData, 256, TPM2B_SENSITIVE_DATA);
 }
 
+pub mod operand {
+    buffer_type!(Operand, 64, TPM2B_OPERAND);
+    serializable_buffer_type!(Operand);
+}
+
 pub mod private {
     use tss_esapi_sys::_PRIVATE;
     buffer_type!(Private, ::std::mem::size_of::<_PRIVATE>(), TPM2B_PRIVATE);
 }
 
+pub mod template {
+    use crate::{
+        structures::Public,
+        tss2_esys::{TPM2_LABEL_MAX_BUFFER, TPMT_PUBLIC},
+    };
+    buffer_type!(
+        Template,
+        ::std::mem::size_of::<TPMT_PUBLIC>(),
+        TPM2B_TEMPLATE
+    );
+
+    impl Template {
+        /// Creates the template of an object derived from a derivation
+        /// parent, with the label and context of the derivation
+        ///
+        /// # Details
+        /// The unique field of `public` is replaced by the TPMS_DERIVE
+        /// structure made of `label` and `context`, from which the TPM
+        /// derives the key.
+        ///
+        /// # Errors
+        /// * if `label` or `context` is larger than 32 bytes, a `WrongParamSize` wrapper error is returned
+        /// * if `public` is the public area of an RSA key, which the TPM cannot derive, an
+        /// `InvalidParam` wrapper error is returned
+        /// * if the `sensitive_data_origin` attribute of `public` is set, an `InvalidParam`
+        /// wrapper error is returned
+        pub fn new_derived(public: &Public, label: &[u8], context: &[u8]) -> Result<Self> {
+            if public.object_attributes().sensitive_data_origin() {
+                error!(
+                    "Error: The sensitive data of a derived object cannot originate from the TPM"
+                );
+                return Err(Error::local_error(WrapperErrorKind::InvalidParam));
+            }
+            if label.len() > TPM2_LABEL_MAX_BUFFER as usize
+                || context.len() > TPM2_LABEL_MAX_BUFFER as usize
+            {
+                error!(
+                    "Error: Invalid derivation label or context size(> {})",
+                    TPM2_LABEL_MAX_BUFFER
+                );
+                return Err(Error::local_error(WrapperErrorKind::WrongParamSize));
+            }
+            // The unique field is the last one of the marshalled public area.
+            let unique_size = match public {
+                Public::Rsa { .. } => {
+                    error!("Error: RSA keys cannot be derived");
+                    return Err(Error::local_error(WrapperErrorKind::InvalidParam));
+                }
+                Public::KeyedHash { unique, .. } | Public::SymCipher { unique, .. } => {
+                    2 + unique.value().len()
+                }
+                Public::Ecc { unique, .. } => {
+                    4 + unique.x().value().len() + unique.y().value().len()
+                }
+            };
+            let mut template = public.marshall()?;
+            template.truncate(template.len() - unique_size);
+            for value in &[label, context] {
+                template.extend_from_slice(&(value.len() as u16).to_be_bytes());
+                template.extend_from_slice(value);
+            }
+            Template::try_from(template)
+        }
+    }
+
+    impl TryFrom<Public> for Template {
+        type Error = Error;
+
+        fn try_from(public: Public) -> Result<Self> {
+            Template::try_from(public.marshall()?)
+        }
+    }
+}
+
 pub mod encrypted_secret {
     named_field_buffer_type!(EncryptedSecret, 256, TPM2B_ENCRYPTED_SECRET, secret);
 }
@@ -120,6 +226,7 @@ pub mod id_object {
 
 pub mod digest {
     buffer_type!(Digest, 64, TPM2B_DIGEST);
+    serializable_buffer_type!(Digest);
 
     // Some implementations to get from Digest to [u8; N] for common values of N (sha* primarily)
     // This is used to work around the fact that Rust does not allow custom functions for general values of N in [T; N],
@@ -183,6 +290,10 @@ pub mod digest {
     }
 }
 
+pub mod event {
+    buffer_type!(Event, 1024, TPM2B_EVENT);
+}
+
 pub mod max_buffer {
     use crate::tss2_esys::TPM2_MAX_DIGEST_BUFFER;
     buffer_type!(MaxBuffer, TPM2_MAX_DIGEST_BUFFER as usize, TPM2B_MAX_BUFFER);
@@ -199,6 +310,7 @@ pub mod max_nv_buffer {
 
 pub mod nonce {
     buffer_type!(Nonce, 64, TPM2B_NONCE);
+    serializable_buffer_type!(Nonce);
 }
 
 pub mod public_key_rsa {
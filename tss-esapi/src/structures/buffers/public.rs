@@ -8,7 +8,10 @@ use crate::{
     attributes::ObjectAttributes,
     interface_types::algorithm::{HashingAlgorithm, PublicAlgorithm},
     structures::{Digest, EccPoint, PublicKeyRsa, SymmetricCipherParameters},
-    tss2_esys::{TPM2B_PUBLIC, TPMT_PUBLIC},
+    tss2_esys::{
+        size_t, Tss2_MU_TPMT_PUBLIC_Marshal, Tss2_MU_TPMT_PUBLIC_Unmarshal, TPM2B_PUBLIC,
+        TPMT_PUBLIC,
+    },
     Error, Result, WrapperErrorKind,
 };
 
@@ -367,6 +370,72 @@ impl Public {
             } => *name_hashing_algorithm,
         }
     }
+
+    /// Marshalls the public area into the byte
+    /// form from which the name of the object is computed.
+    pub fn marshall(&self) -> Result<Vec<u8>> {
+        let tpmt_public = TPM2B_PUBLIC::from(self.clone()).publicArea;
+        let mut buffer = vec![0; std::mem::size_of::<TPMT_PUBLIC>()];
+        let mut offset: size_t = 0;
+        let ret = Error::from_tss_rc(unsafe {
+            Tss2_MU_TPMT_PUBLIC_Marshal(
+                &tpmt_public,
+                buffer.as_mut_ptr(),
+                buffer.len().try_into().map_err(|e| {
+                    error!("Failed to convert size of buffer to TSS size_t type: {}", e);
+                    Error::local_error(WrapperErrorKind::InvalidParam)
+                })?,
+                &mut offset,
+            )
+        });
+        if !ret.is_success() {
+            error!("Error when marshalling public area: {}", ret);
+            return Err(ret);
+        }
+        let checked_offset = usize::try_from(offset).map_err(|e| {
+            error!("Failed to parse offset as usize: {}", e);
+            Error::local_error(WrapperErrorKind::InvalidParam)
+        })?;
+        buffer.truncate(checked_offset);
+        Ok(buffer)
+    }
+
+    /// Unmarshalls the public area from the byte
+    /// form from which the name of the object is computed.
+    ///
+    /// # Errors
+    /// * if the marshalled data contains trailing bytes, a `WrongParamSize`
+    ///   wrapper error is returned.
+    pub fn unmarshall(marshalled_data: &[u8]) -> Result<Self> {
+        let mut tpmt_public = TPMT_PUBLIC::default();
+        let mut offset: size_t = 0;
+        let buffer_size: size_t = marshalled_data.len().try_into().map_err(|e| {
+            error!("Failed to convert size of buffer to TSS size_t type: {}", e);
+            Error::local_error(WrapperErrorKind::InvalidParam)
+        })?;
+        let ret = Error::from_tss_rc(unsafe {
+            Tss2_MU_TPMT_PUBLIC_Unmarshal(
+                marshalled_data.as_ptr(),
+                buffer_size,
+                &mut offset,
+                &mut tpmt_public,
+            )
+        });
+        if !ret.is_success() {
+            error!("Error when unmarshalling public area: {}", ret);
+            return Err(ret);
+        }
+        if offset != buffer_size {
+            error!("Error: Found trailing bytes after the public area");
+            return Err(Error::local_error(WrapperErrorKind::WrongParamSize));
+        }
+        Public::try_from(TPM2B_PUBLIC {
+            size: std::mem::size_of::<TPMT_PUBLIC>()
+                .try_into()
+                .expect("Failed to convert usize to u16"), // should not fail on valid targets
+            publicArea: tpmt_public,
+        })
+    }
 }
 
 impl From<Public> for TPM2B_PUBLIC {
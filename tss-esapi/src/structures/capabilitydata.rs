@@ -3,10 +3,12 @@
 use crate::{
     constants::tss::*,
     handles::TpmHandle,
+    interface_types::ecc::EccCurve,
     structures::{PcrSelect, PcrSelectionList},
     tss2_esys::*,
     Error, Result, WrapperErrorKind,
 };
+use log::warn;
 use std::collections::HashMap;
 use std::convert::{TryFrom, TryInto};
 use std::mem::size_of;
@@ -21,7 +23,7 @@ pub enum CapabilityData {
     AssignedPCR(PcrSelectionList),
     TPMProperties(HashMap<TPM2_PT, u32>),
     PCRProperties(HashMap<TPM2_PT_PCR, PcrSelect>),
-    ECCCurves(Vec<TPM2_ECC_CURVE>),
+    ECCCurves(Vec<EccCurve>),
     // These are in the TPM TMU_CAPABILITIES, but are not defined by esapi-2.4.1
     // AuthPolicies(),
     // ActData(),
@@ -156,8 +158,13 @@ fn cd_from_ecc_curves(props: TPML_ECC_CURVE) -> Result<CapabilityData> {
     let mut data = Vec::new();
     data.reserve_exact(props.count as usize);
 
-    for i in 0..props.count {
-        data.push(props.eccCurves[i as usize]);
+    // Curves unknown to this crate, e.g. vendor-specific ones, are skipped
+    // so that the known ones can still be listed.
+    for curve_id in props.eccCurves[..props.count as usize].iter() {
+        match EccCurve::try_from(*curve_id) {
+            Ok(curve) => data.push(curve),
+            Err(_) => warn!("Skipping unknown ECC curve {:#06x}", curve_id),
+        }
     }
 
     Ok(CapabilityData::ECCCurves(data))
@@ -0,0 +1,78 @@
+// Copyright 2021 Contributors to the Parsec project.
+// SPDX-License-Identifier: Apache-2.0
+use crate::{tss2_esys::TPMS_CLOCK_INFO, Error, Result, WrapperErrorKind};
+use log::error;
+use std::convert::TryFrom;
+
+/// Structure holding the clock information of the TPM.
+///
+/// # Details
+/// This corresponds to the TPMS_CLOCK_INFO.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClockInfo {
+    clock: u64,
+    reset_count: u32,
+    restart_count: u32,
+    safe: bool,
+}
+
+impl ClockInfo {
+    /// Returns the time in milliseconds during which the
+    /// TPM has been powered.
+    pub const fn clock(&self) -> u64 {
+        self.clock
+    }
+
+    /// Returns the number of occurrences of TPM Reset
+    /// since the last TPM2_Clear().
+    pub const fn reset_count(&self) -> u32 {
+        self.reset_count
+    }
+
+    /// Returns the number of times that TPM2_Shutdown() or
+    /// _TPM_Hash_Start have occurred since the last TPM Reset
+    /// or TPM2_Clear().
+    pub const fn restart_count(&self) -> u32 {
+        self.restart_count
+    }
+
+    /// Returns true if the value of clock has not been
+    /// reported previously with a smaller value.
+    pub const fn safe(&self) -> bool {
+        self.safe
+    }
+}
+
+impl TryFrom<TPMS_CLOCK_INFO> for ClockInfo {
+    type Error = Error;
+
+    fn try_from(tpms_clock_info: TPMS_CLOCK_INFO) -> Result<Self> {
+        Ok(ClockInfo {
+            clock: tpms_clock_info.clock,
+            reset_count: tpms_clock_info.resetCount,
+            restart_count: tpms_clock_info.restartCount,
+            safe: match tpms_clock_info.safe {
+                0 => false,
+                1 => true,
+                _ => {
+                    error!(
+                        "Error: Invalid TPMI_YES_NO value for safe ({})",
+                        tpms_clock_info.safe
+                    );
+                    return Err(Error::local_error(WrapperErrorKind::InvalidParam));
+                }
+            },
+        })
+    }
+}
+
+impl From<ClockInfo> for TPMS_CLOCK_INFO {
+    fn from(clock_info: ClockInfo) -> Self {
+        TPMS_CLOCK_INFO {
+            clock: clock_info.clock,
+            resetCount: clock_info.reset_count,
+            restartCount: clock_info.restart_count,
+            safe: if clock_info.safe { 1 } else { 0 },
+        }
+    }
+}
@@ -0,0 +1,105 @@
+// Copyright 2021 Contributors to the Parsec project.
+// SPDX-License-Identifier: Apache-2.0
+use crate::{
+    interface_types::ecc::EccCurve,
+    structures::{EccParameter, EccPoint, EccScheme, KeyDerivationFunctionScheme},
+    tss2_esys::TPMS_ALGORITHM_DETAIL_ECC,
+    Error, Result,
+};
+use std::convert::{TryFrom, TryInto};
+
+/// Structure holding the parameters of an ECC curve
+///
+/// # Details
+/// The curve is defined by the equation y^2 = x^3 + ax + b over the
+/// field of size `p`, with the base point `g` of order `n` and the
+/// cofactor `h`.
+///
+/// This corresponds to TPMS_ALGORITHM_DETAIL_ECC
+#[derive(Debug, Clone)]
+pub struct EccCurveParameters {
+    curve: EccCurve,
+    key_size: u16,
+    kdf: KeyDerivationFunctionScheme,
+    sign: EccScheme,
+    p: EccParameter,
+    a: EccParameter,
+    b: EccParameter,
+    g: EccPoint,
+    n: EccParameter,
+    h: EccParameter,
+}
+
+impl EccCurveParameters {
+    /// Returns the curve
+    pub const fn curve(&self) -> EccCurve {
+        self.curve
+    }
+
+    /// Returns the size of the keys in bits
+    pub const fn key_size(&self) -> u16 {
+        self.key_size
+    }
+
+    /// Returns the key derivation function required by the curve
+    pub const fn kdf(&self) -> KeyDerivationFunctionScheme {
+        self.kdf
+    }
+
+    /// Returns the signing scheme required by the curve
+    pub const fn sign(&self) -> EccScheme {
+        self.sign
+    }
+
+    /// Returns the size of the underlying field
+    pub const fn p(&self) -> &EccParameter {
+        &self.p
+    }
+
+    /// Returns the coefficient a of the equation of the curve
+    pub const fn a(&self) -> &EccParameter {
+        &self.a
+    }
+
+    /// Returns the coefficient b of the equation of the curve
+    pub const fn b(&self) -> &EccParameter {
+        &self.b
+    }
+
+    /// Returns the base point of the curve
+    pub const fn g(&self) -> &EccPoint {
+        &self.g
+    }
+
+    /// Returns the order of the base point
+    pub const fn n(&self) -> &EccParameter {
+        &self.n
+    }
+
+    /// Returns the cofactor of the curve
+    pub const fn h(&self) -> &EccParameter {
+        &self.h
+    }
+}
+
+impl TryFrom<TPMS_ALGORITHM_DETAIL_ECC> for EccCurveParameters {
+    type Error = Error;
+
+    fn try_from(tpms_algorithm_detail_ecc: TPMS_ALGORITHM_DETAIL_ECC) -> Result<Self> {
+        Ok(EccCurveParameters {
+            curve: tpms_algorithm_detail_ecc.curveID.try_into()?,
+            key_size: tpms_algorithm_detail_ecc.keySize,
+            kdf: tpms_algorithm_detail_ecc.kdf.try_into()?,
+            sign: tpms_algorithm_detail_ecc.sign.try_into()?,
+            p: tpms_algorithm_detail_ecc.p.try_into()?,
+            a: tpms_algorithm_detail_ecc.a.try_into()?,
+            b: tpms_algorithm_detail_ecc.b.try_into()?,
+            g: EccPoint::new(
+                tpms_algorithm_detail_ecc.gX.try_into()?,
+                tpms_algorithm_detail_ecc.gY.try_into()?,
+            ),
+            n: tpms_algorithm_detail_ecc.n.try_into()?,
+            h: tpms_algorithm_detail_ecc.h.try_into()?,
+        })
+    }
+}
@@ -16,6 +16,16 @@ impl HashAgile {
     pub fn new(algorithm: HashingAlgorithm, digest: Digest) -> Self {
         HashAgile { algorithm, digest }
     }
+
+    /// Returns the hashing algorithm of the digest
+    pub const fn algorithm(&self) -> HashingAlgorithm {
+        self.algorithm
+    }
+
+    /// Returns the digest
+    pub const fn digest(&self) -> &Digest {
+        &self.digest
+    }
 }
 
 impl TryFrom<HashAgile> for TPMT_HA {
@@ -5,11 +5,12 @@ use crate::structures::{PcrSelectSize, PcrSelection, PcrSlot};
 use crate::tss2_esys::TPML_PCR_SELECTION;
 use crate::{Error, Result, WrapperErrorKind};
 use log::error;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::convert::TryFrom;
 /// A struct representing a pcr selection list. This
 /// corresponds to the TSS TPML_PCR_SELECTION.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct PcrSelectionList {
     items: Vec<PcrSelection>,
 }
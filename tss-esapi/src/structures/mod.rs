@@ -28,8 +28,11 @@ pub use names::name::Name;
 /// The result section
 /////////////////////////////////////////////////////////
 mod result;
+pub use result::CommitResult;
 pub use result::CreateKeyResult;
+pub use result::CreateLoadedKeyResult;
 pub use result::CreatePrimaryKeyResult;
+pub use result::PcrAllocationResult;
 /////////////////////////////////////////////////////////
 /// The sized buffers section
 /////////////////////////////////////////////////////////
@@ -40,11 +43,13 @@ pub use self::buffers::{
     digest::Digest,
     ecc_parameter::EccParameter,
     encrypted_secret::EncryptedSecret,
+    event::Event,
     id_object::IDObject,
     initial_value::InitialValue,
     max_buffer::MaxBuffer,
     max_nv_buffer::MaxNvBuffer,
     nonce::Nonce,
+    operand::Operand,
     private::Private,
     public::{
         ecc::{PublicEccParameters, PublicEccParametersBuilder},
@@ -54,6 +59,7 @@ pub use self::buffers::{
     },
     public_key_rsa::PublicKeyRsa,
     sensitive_data::SensitiveData,
+    template::Template,
     timeout::Timeout,
 };
 /////////////////////////////////////////////////////////
@@ -123,14 +129,31 @@ pub use tagged::{
     parameters::PublicParameters,
     schemes::{
         EccScheme, KeyDerivationFunctionScheme, KeyedHashScheme, RsaDecryptionScheme, RsaScheme,
+        SignatureScheme,
     },
     signature::Signature,
     symmetric::{SymmetricDefinition, SymmetricDefinitionObject},
 };
 /////////////////////////////////////////////////////////
+/// The clock section
+/////////////////////////////////////////////////////////
+mod clock;
+pub use clock::{clock_info::ClockInfo, time_info::TimeInfo};
+/////////////////////////////////////////////////////////
+/// The attestation section
+/////////////////////////////////////////////////////////
+mod attestation;
+pub use attestation::{
+    attest::Attest, attest_info::AttestInfo, certify_info::CertifyInfo,
+    command_audit_info::CommandAuditInfo, creation_info::CreationInfo,
+    nv_certify_info::NvCertifyInfo, quote_info::QuoteInfo, session_audit_info::SessionAuditInfo,
+    time_attest_info::TimeAttestInfo,
+};
+/////////////////////////////////////////////////////////
 /// ECC structures
 /////////////////////////////////////////////////////////
 mod ecc;
+pub use ecc::curve_parameters::EccCurveParameters;
 pub use ecc::point::EccPoint;
 /////////////////////////////////////////////////////////
 /// Signatures structures
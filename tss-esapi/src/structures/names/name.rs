@@ -3,6 +3,7 @@
 use crate::tss2_esys::TPM2B_NAME;
 use crate::{Error, Result, WrapperErrorKind};
 use log::error;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::convert::TryFrom;
 /// Structure holding the data representing names
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -28,6 +29,26 @@ impl TryFrom<Vec<u8>> for Name {
     }
 }
 
+impl Serialize for Name {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.value.serialize(serializer)
+    }
+}
+
+// The size of the name is checked when it is deserialized.
+impl<'de> Deserialize<'de> for Name {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let bytes = Vec::<u8>::deserialize(deserializer)?;
+        Name::try_from(bytes).map_err(serde::de::Error::custom)
+    }
+}
+
 impl TryFrom<TPM2B_NAME> for Name {
     type Error = Error;
     fn try_from(tss_name: TPM2B_NAME) -> Result<Self> {
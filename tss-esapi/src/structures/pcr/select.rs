@@ -2,11 +2,12 @@
 // SPDX-License-Identifier: Apache-2.0
 use crate::tss2_esys::{TPM2_PCR_SELECT_MAX, TPMS_PCR_SELECT};
 use crate::{Error, Result, WrapperErrorKind};
-use enumflags2::BitFlags;
 use enumflags2::_internal::RawBitFlags;
+use enumflags2::BitFlags;
 use log::error;
 use num_derive::{FromPrimitive, ToPrimitive};
 use num_traits::{FromPrimitive, ToPrimitive};
+use serde::{Deserialize, Serialize};
 use std::convert::{From, TryFrom};
 /// This module contains necessary representations
 /// of the items belonging to the TPMS_PCR_SELECT
@@ -99,7 +100,7 @@ impl TryFrom<[u8; TPM2_PCR_SELECT_MAX as usize]> for PcrSlot {
 }
 
 /// Enum with the possible values for sizeofSelect.
-#[derive(FromPrimitive, ToPrimitive, Debug, Copy, Clone, PartialEq, Eq)]
+#[derive(FromPrimitive, ToPrimitive, Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[repr(u8)]
 pub enum PcrSelectSize {
     OneByte = 1,
@@ -7,11 +7,12 @@ use crate::{Error, Result, WrapperErrorKind};
 use enumflags2::BitFlags;
 use log::error;
 use num_traits::{FromPrimitive, ToPrimitive};
+use serde::{Deserialize, Serialize};
 use std::convert::{From, TryFrom};
 /// This module contains the PcrSelection struct.
 /// The TSS counterpart of this struct is the
 /// TPMS_PCR_SELECTION.
-#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct PcrSelection {
     hashing_algorithm: HashingAlgorithm,
     size_of_select: PcrSelectSize,
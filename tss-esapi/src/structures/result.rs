@@ -3,7 +3,7 @@
 
 use crate::{
     handles::KeyHandle,
-    structures::{CreationData, CreationTicket, Digest, Private, Public},
+    structures::{CreationData, CreationTicket, Digest, EccPoint, Private, Public},
 };
 
 #[allow(missing_debug_implementations)]
@@ -15,6 +15,13 @@ pub struct CreateKeyResult {
     pub creation_ticket: CreationTicket,
 }
 
+#[allow(missing_debug_implementations)]
+pub struct CreateLoadedKeyResult {
+    pub key_handle: KeyHandle,
+    pub out_private: Private,
+    pub out_public: Public,
+}
+
 #[allow(missing_debug_implementations)]
 pub struct CreatePrimaryKeyResult {
     pub key_handle: KeyHandle,
@@ -23,3 +30,29 @@ pub struct CreatePrimaryKeyResult {
     pub creation_hash: Digest,
     pub creation_ticket: CreationTicket,
 }
+
+/// The result of the first part of an ECC anonymous signing operation
+#[derive(Debug, Clone)]
+pub struct CommitResult {
+    /// The point `K`, only computed when `p1` is provided
+    pub k: EccPoint,
+    /// The point `L`, only computed when `p1` is provided
+    pub l: EccPoint,
+    /// The point `E`
+    pub e: EccPoint,
+    /// The commit counter, to be given to the signing operation
+    pub counter: u16,
+}
+
+/// The result of a PCR bank allocation
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct PcrAllocationResult {
+    /// Whether the allocation was accepted, it takes effect after the next TPM reset
+    pub allocation_success: bool,
+    /// The maximum number of PCRs in any bank
+    pub max_pcr: u32,
+    /// The number of octets required to satisfy the request
+    pub size_needed: u32,
+    /// The number of octets available for PCR banks
+    pub size_available: u32,
+}
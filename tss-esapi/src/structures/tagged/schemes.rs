@@ -3,12 +3,12 @@
 use crate::{
     interface_types::algorithm::{
         EccSchemeAlgorithm, HashingAlgorithm, KeyDerivationFunction, KeyedHashSchemeAlgorithm,
-        RsaDecryptAlgorithm, RsaSchemeAlgorithm,
+        RsaDecryptAlgorithm, RsaSchemeAlgorithm, SignatureSchemeAlgorithm,
     },
     structures::schemes::{EcDaaScheme, HashScheme, HmacScheme, XorScheme},
     tss2_esys::{
         TPMT_ECC_SCHEME, TPMT_KDF_SCHEME, TPMT_KEYEDHASH_SCHEME, TPMT_RSA_DECRYPT, TPMT_RSA_SCHEME,
-        TPMU_ASYM_SCHEME, TPMU_KDF_SCHEME, TPMU_SCHEME_KEYEDHASH,
+        TPMT_SIG_SCHEME, TPMU_ASYM_SCHEME, TPMU_KDF_SCHEME, TPMU_SCHEME_KEYEDHASH, TPMU_SIG_SCHEME,
     },
     Error, Result, WrapperErrorKind,
 };
@@ -543,3 +543,119 @@ impl TryFrom<RsaScheme> for RsaDecryptionScheme {
         }
     }
 }
+
+/// Enum representing the signature scheme
+///
+/// # Details
+/// This corresponds to TPMT_SIG_SCHEME.
+#[derive(Clone, Copy, Debug)]
+pub enum SignatureScheme {
+    RsaSsa(HashScheme),
+    RsaPss(HashScheme),
+    EcDsa(HashScheme),
+    EcDaa(EcDaaScheme),
+    Sm2(HashScheme),
+    EcSchnorr(HashScheme),
+    Hmac(HmacScheme),
+    Null,
+}
+
+impl SignatureScheme {
+    /// Returns the signature scheme algorithm
+    pub fn algorithm(&self) -> SignatureSchemeAlgorithm {
+        match self {
+            SignatureScheme::RsaSsa(_) => SignatureSchemeAlgorithm::RsaSsa,
+            SignatureScheme::RsaPss(_) => SignatureSchemeAlgorithm::RsaPss,
+            SignatureScheme::EcDsa(_) => SignatureSchemeAlgorithm::EcDsa,
+            SignatureScheme::EcDaa(_) => SignatureSchemeAlgorithm::EcDaa,
+            SignatureScheme::Sm2(_) => SignatureSchemeAlgorithm::Sm2,
+            SignatureScheme::EcSchnorr(_) => SignatureSchemeAlgorithm::EcSchnorr,
+            SignatureScheme::Hmac(_) => SignatureSchemeAlgorithm::Hmac,
+            SignatureScheme::Null => SignatureSchemeAlgorithm::Null,
+        }
+    }
+}
+
+impl From<SignatureScheme> for TPMT_SIG_SCHEME {
+    fn from(signature_scheme: SignatureScheme) -> Self {
+        match signature_scheme {
+            SignatureScheme::RsaSsa(hash_scheme) => TPMT_SIG_SCHEME {
+                scheme: signature_scheme.algorithm().into(),
+                details: TPMU_SIG_SCHEME {
+                    rsassa: hash_scheme.into(),
+                },
+            },
+            SignatureScheme::RsaPss(hash_scheme) => TPMT_SIG_SCHEME {
+                scheme: signature_scheme.algorithm().into(),
+                details: TPMU_SIG_SCHEME {
+                    rsapss: hash_scheme.into(),
+                },
+            },
+            SignatureScheme::EcDsa(hash_scheme) => TPMT_SIG_SCHEME {
+                scheme: signature_scheme.algorithm().into(),
+                details: TPMU_SIG_SCHEME {
+                    ecdsa: hash_scheme.into(),
+                },
+            },
+            SignatureScheme::EcDaa(ec_daa_scheme) => TPMT_SIG_SCHEME {
+                scheme: signature_scheme.algorithm().into(),
+                details: TPMU_SIG_SCHEME {
+                    ecdaa: ec_daa_scheme.into(),
+                },
+            },
+            SignatureScheme::Sm2(hash_scheme) => TPMT_SIG_SCHEME {
+                scheme: signature_scheme.algorithm().into(),
+                details: TPMU_SIG_SCHEME {
+                    sm2: hash_scheme.into(),
+                },
+            },
+            SignatureScheme::EcSchnorr(hash_scheme) => TPMT_SIG_SCHEME {
+                scheme: signature_scheme.algorithm().into(),
+                details: TPMU_SIG_SCHEME {
+                    ecschnorr: hash_scheme.into(),
+                },
+            },
+            SignatureScheme::Hmac(hmac_scheme) => TPMT_SIG_SCHEME {
+                scheme: signature_scheme.algorithm().into(),
+                details: TPMU_SIG_SCHEME {
+                    hmac: hmac_scheme.into(),
+                },
+            },
+            SignatureScheme::Null => TPMT_SIG_SCHEME {
+                scheme: signature_scheme.algorithm().into(),
+                details: Default::default(),
+            },
+        }
+    }
+}
+
+impl TryFrom<TPMT_SIG_SCHEME> for SignatureScheme {
+    type Error = Error;
+
+    fn try_from(tpmt_sig_scheme: TPMT_SIG_SCHEME) -> Result<Self> {
+        match SignatureSchemeAlgorithm::try_from(tpmt_sig_scheme.scheme)? {
+            SignatureSchemeAlgorithm::RsaSsa => Ok(SignatureScheme::RsaSsa(
+                unsafe { tpmt_sig_scheme.details.rsassa }.try_into()?,
+            )),
+            SignatureSchemeAlgorithm::RsaPss => Ok(SignatureScheme::RsaPss(
+                unsafe { tpmt_sig_scheme.details.rsapss }.try_into()?,
+            )),
+            SignatureSchemeAlgorithm::EcDsa => Ok(SignatureScheme::EcDsa(
+                unsafe { tpmt_sig_scheme.details.ecdsa }.try_into()?,
+            )),
+            SignatureSchemeAlgorithm::EcDaa => Ok(SignatureScheme::EcDaa(
+                unsafe { tpmt_sig_scheme.details.ecdaa }.try_into()?,
+            )),
+            SignatureSchemeAlgorithm::Sm2 => Ok(SignatureScheme::Sm2(
+                unsafe { tpmt_sig_scheme.details.sm2 }.try_into()?,
+            )),
+            SignatureSchemeAlgorithm::EcSchnorr => Ok(SignatureScheme::EcSchnorr(
+                unsafe { tpmt_sig_scheme.details.ecschnorr }.try_into()?,
+            )),
+            SignatureSchemeAlgorithm::Hmac => Ok(SignatureScheme::Hmac(
+                unsafe { tpmt_sig_scheme.details.hmac }.try_into()?,
+            )),
+            SignatureSchemeAlgorithm::Null => Ok(SignatureScheme::Null),
+        }
+    }
+}
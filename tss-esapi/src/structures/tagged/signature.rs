@@ -3,9 +3,13 @@
 use crate::{
     interface_types::algorithm::SignatureSchemeAlgorithm,
     structures::{EccSignature, HashAgile, RsaSignature},
-    tss2_esys::{TPMT_SIGNATURE, TPMU_SIGNATURE},
-    Error, Result,
+    tss2_esys::{
+        size_t, Tss2_MU_TPMT_SIGNATURE_Marshal, Tss2_MU_TPMT_SIGNATURE_Unmarshal, TPMT_SIGNATURE,
+        TPMU_SIGNATURE,
+    },
+    Error, Result, WrapperErrorKind,
 };
+use log::error;
 use std::convert::{TryFrom, TryInto};
 
 /// Enum representing a Signature
@@ -37,6 +41,65 @@ impl Signature {
             Signature::Null => SignatureSchemeAlgorithm::Null,
         }
     }
+
+    /// Marshalls the signature into its byte form.
+    pub fn marshall(&self) -> Result<Vec<u8>> {
+        let tpmt_signature = TPMT_SIGNATURE::try_from(self.clone())?;
+        let mut buffer = vec![0; std::mem::size_of::<TPMT_SIGNATURE>()];
+        let mut offset: size_t = 0;
+        let ret = Error::from_tss_rc(unsafe {
+            Tss2_MU_TPMT_SIGNATURE_Marshal(
+                &tpmt_signature,
+                buffer.as_mut_ptr(),
+                buffer.len().try_into().map_err(|e| {
+                    error!("Failed to convert size of buffer to TSS size_t type: {}", e);
+                    Error::local_error(WrapperErrorKind::InvalidParam)
+                })?,
+                &mut offset,
+            )
+        });
+        if !ret.is_success() {
+            error!("Error when marshalling signature: {}", ret);
+            return Err(ret);
+        }
+        let checked_offset = usize::try_from(offset).map_err(|e| {
+            error!("Failed to parse offset as usize: {}", e);
+            Error::local_error(WrapperErrorKind::InvalidParam)
+        })?;
+        buffer.truncate(checked_offset);
+        Ok(buffer)
+    }
+
+    /// Unmarshalls the signature from its byte form.
+    ///
+    /// # Errors
+    /// * if the marshalled data contains trailing bytes, a `WrongParamSize`
+    ///   wrapper error is returned.
+    pub fn unmarshall(marshalled_data: &[u8]) -> Result<Self> {
+        let mut tpmt_signature = TPMT_SIGNATURE::default();
+        let mut offset: size_t = 0;
+        let buffer_size: size_t = marshalled_data.len().try_into().map_err(|e| {
+            error!("Failed to convert size of buffer to TSS size_t type: {}", e);
+            Error::local_error(WrapperErrorKind::InvalidParam)
+        })?;
+        let ret = Error::from_tss_rc(unsafe {
+            Tss2_MU_TPMT_SIGNATURE_Unmarshal(
+                marshalled_data.as_ptr(),
+                buffer_size,
+                &mut offset,
+                &mut tpmt_signature,
+            )
+        });
+        if !ret.is_success() {
+            error!("Error when unmarshalling signature: {}", ret);
+            return Err(ret);
+        }
+        if offset != buffer_size {
+            error!("Error: Found trailing bytes after the signature");
+            return Err(Error::local_error(WrapperErrorKind::WrongParamSize));
+        }
+        Signature::try_from(tpmt_signature)
+    }
 }
 
 impl TryFrom<Signature> for TPMT_SIGNATURE {
@@ -0,0 +1,240 @@
+// Copyright 2021 Contributors to the Parsec project.
+// SPDX-License-Identifier: Apache-2.0
+#![cfg(feature = "software-crypto")]
+
+use std::convert::TryFrom;
+
+use rand::rngs::OsRng;
+use rsa::PublicKeyParts;
+use tss_esapi::{
+    abstraction::duplication::DuplicationBlobBuilder,
+    attributes::ObjectAttributesBuilder,
+    interface_types::{
+        algorithm::{HashingAlgorithm, PublicAlgorithm, RsaSchemeAlgorithm},
+        ecc::EccCurve,
+        key_bits::RsaKeyBits,
+        resource_handles::Hierarchy,
+    },
+    structures::{
+        Auth, EccParameter, EccPoint, EccScheme, HashScheme, KeyDerivationFunctionScheme, Public,
+        PublicBuilder, PublicEccParametersBuilder, PublicKeyRsa, PublicRsaParametersBuilder,
+        RsaExponent, RsaScheme, SymmetricDefinitionObject,
+    },
+    Context, Error, WrapperErrorKind,
+};
+
+mod common;
+use common::{create_ctx_with_session, decryption_key_pub, signing_key_pub};
+
+fn ecc_parent_public() -> Public {
+    let object_attributes = ObjectAttributesBuilder::new()
+        .with_fixed_tpm(true)
+        .with_fixed_parent(true)
+        .with_sensitive_data_origin(true)
+        .with_user_with_auth(true)
+        .with_decrypt(true)
+        .with_restricted(true)
+        .build()
+        .expect("Attributes to be valid");
+
+    PublicBuilder::new()
+        .with_public_algorithm(PublicAlgorithm::Ecc)
+        .with_name_hashing_algorithm(HashingAlgorithm::Sha256)
+        .with_object_attributes(object_attributes)
+        .with_ecc_parameters(
+            PublicEccParametersBuilder::new()
+                .with_ecc_scheme(EccScheme::Null)
+                .with_curve(EccCurve::NistP256)
+                .with_is_signing_key(false)
+                .with_is_decryption_key(true)
+                .with_restricted(true)
+                .with_symmetric(SymmetricDefinitionObject::AES_128_CFB)
+                .with_key_derivation_function_scheme(KeyDerivationFunctionScheme::Null)
+                .build()
+                .expect("Params to be valid"),
+        )
+        .with_ecc_unique_identifier(&EccPoint::default())
+        .build()
+        .expect("Public to be valid")
+}
+
+/// Returns the public area and the private scalar of a new software
+/// ECC signing key
+fn software_ecc_key() -> (Public, Vec<u8>) {
+    let secret_key = p256::SecretKey::random(&mut OsRng);
+    let point = p256::EncodedPoint::from(secret_key.public_key());
+
+    let object_attributes = ObjectAttributesBuilder::new()
+        .with_fixed_tpm(false)
+        .with_fixed_parent(false)
+        .with_user_with_auth(true)
+        .with_sign_encrypt(true)
+        .build()
+        .expect("Attributes to be valid");
+
+    let public = PublicBuilder::new()
+        .with_public_algorithm(PublicAlgorithm::Ecc)
+        .with_name_hashing_algorithm(HashingAlgorithm::Sha256)
+        .with_object_attributes(object_attributes)
+        .with_ecc_parameters(
+            PublicEccParametersBuilder::new_unrestricted_signing_key(
+                EccScheme::EcDsa(HashScheme::new(HashingAlgorithm::Sha256)),
+                EccCurve::NistP256,
+            )
+            .build()
+            .expect("Params to be valid"),
+        )
+        .with_ecc_unique_identifier(&EccPoint::new(
+            EccParameter::try_from(point.x().unwrap().to_vec()).unwrap(),
+            EccParameter::try_from(point.y().unwrap().to_vec()).unwrap(),
+        ))
+        .build()
+        .expect("Public to be valid");
+
+    (public, secret_key.to_bytes().to_vec())
+}
+
+/// Returns the public area and the first prime factor of a new software
+/// RSA signing key
+fn software_rsa_key() -> (Public, Vec<u8>) {
+    let private_key = rsa::RsaPrivateKey::new(&mut OsRng, 2048).unwrap();
+
+    let object_attributes = ObjectAttributesBuilder::new()
+        .with_fixed_tpm(false)
+        .with_fixed_parent(false)
+        .with_user_with_auth(true)
+        .with_sign_encrypt(true)
+        .build()
+        .expect("Attributes to be valid");
+
+    let public = PublicBuilder::new()
+        .with_public_algorithm(PublicAlgorithm::Rsa)
+        .with_name_hashing_algorithm(HashingAlgorithm::Sha256)
+        .with_object_attributes(object_attributes)
+        .with_rsa_parameters(
+            PublicRsaParametersBuilder::new_unrestricted_signing_key(
+                RsaScheme::create(RsaSchemeAlgorithm::RsaSsa, Some(HashingAlgorithm::Sha256))
+                    .unwrap(),
+                RsaKeyBits::Rsa2048,
+                RsaExponent::default(),
+            )
+            .build()
+            .expect("Params to be valid"),
+        )
+        .with_rsa_unique_identifier(&PublicKeyRsa::try_from(private_key.n().to_bytes_be()).unwrap())
+        .build()
+        .expect("Public to be valid");
+
+    (public, private_key.primes()[0].to_bytes_be())
+}
+
+fn import_and_load(
+    context: &mut Context,
+    parent_public: Public,
+    symmetric_alg: SymmetricDefinitionObject,
+    (object_public, private_key): (Public, Vec<u8>),
+) {
+    let parent_handle = context
+        .create_primary(Hierarchy::Owner, &parent_public, None, None, None, None)
+        .expect("Failed to create the new parent");
+    let (parent_public, _, _) = context.read_public(parent_handle.key_handle).unwrap();
+
+    let blob = DuplicationBlobBuilder::new()
+        .with_parent_public(parent_public)
+        .with_object_public(object_public.clone())
+        .with_private_key(&private_key)
+        .with_auth_value(Auth::try_from(vec![1, 2, 3, 4]).unwrap())
+        .with_symmetric_alg(symmetric_alg)
+        .build()
+        .expect("Failed to build the duplication blob");
+
+    let private = context
+        .import(
+            parent_handle.key_handle.into(),
+            Some(blob.encryption_key),
+            object_public.clone(),
+            blob.duplicate,
+            blob.in_sym_seed,
+            blob.symmetric_alg,
+        )
+        .expect("Failed to import the duplication blob");
+    let key_handle = context
+        .load(parent_handle.key_handle, private, &object_public)
+        .expect("Failed to load the imported object");
+
+    context.flush_context(key_handle.into()).unwrap();
+    context
+        .flush_context(parent_handle.key_handle.into())
+        .unwrap();
+}
+
+#[test]
+fn import_under_rsa_parent() {
+    let mut context = create_ctx_with_session();
+    import_and_load(
+        &mut context,
+        decryption_key_pub(),
+        SymmetricDefinitionObject::AES_128_CFB,
+        software_ecc_key(),
+    );
+}
+
+#[test]
+fn import_under_ecc_parent() {
+    let mut context = create_ctx_with_session();
+    import_and_load(
+        &mut context,
+        ecc_parent_public(),
+        SymmetricDefinitionObject::AES_128_CFB,
+        software_ecc_key(),
+    );
+}
+
+#[test]
+fn import_without_inner_wrapper() {
+    let mut context = create_ctx_with_session();
+    import_and_load(
+        &mut context,
+        ecc_parent_public(),
+        SymmetricDefinitionObject::Null,
+        software_ecc_key(),
+    );
+}
+
+#[test]
+fn import_software_rsa_key() {
+    let mut context = create_ctx_with_session();
+    import_and_load(
+        &mut context,
+        decryption_key_pub(),
+        SymmetricDefinitionObject::AES_128_CFB,
+        software_rsa_key(),
+    );
+}
+
+#[test]
+fn build_with_missing_params() {
+    let (object_public, private_key) = software_ecc_key();
+    assert_eq!(
+        DuplicationBlobBuilder::new()
+            .with_object_public(object_public)
+            .with_private_key(&private_key)
+            .build()
+            .unwrap_err(),
+        Error::WrapperError(WrapperErrorKind::ParamsMissing)
+    );
+}
+
+#[test]
+fn build_with_non_storage_parent() {
+    let (object_public, private_key) = software_ecc_key();
+    assert_eq!(
+        DuplicationBlobBuilder::new()
+            .with_parent_public(signing_key_pub())
+            .with_object_public(object_public)
+            .with_private_key(&private_key)
+            .build()
+            .unwrap_err(),
+        Error::WrapperError(WrapperErrorKind::InvalidParam)
+    );
+}
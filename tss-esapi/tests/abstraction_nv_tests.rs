@@ -2,17 +2,19 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use std::convert::TryFrom;
+use std::io::{Read, Seek, SeekFrom, Write};
 use tss_esapi::{
-    abstraction::nv,
-    attributes::NvIndexAttributesBuilder,
+    abstraction::nv::{self, NvIndexKind},
+    attributes::{NvIndexAttributes, NvIndexAttributesBuilder},
+    constants::NvIndexType,
     handles::{NvIndexHandle, NvIndexTpmHandle},
     interface_types::{
         algorithm::HashingAlgorithm,
         resource_handles::{NvAuth, Provision},
     },
     nv::storage::NvPublicBuilder,
-    structures::MaxNvBuffer,
-    Context,
+    structures::{Digest, MaxNvBuffer},
+    Context, Error, WrapperErrorKind,
 };
 
 mod common;
@@ -99,3 +101,479 @@ fn read_full() {
     assert_eq!(read_result[0..7], [1, 2, 3, 4, 5, 6, 7]);
     assert_eq!(read_result[1024..1031], [1, 2, 3, 4, 5, 6, 7]);
 }
+
+fn define_nv_index(
+    context: &mut Context,
+    nv_index: NvIndexTpmHandle,
+    nv_index_type: NvIndexType,
+) -> NvIndexHandle {
+    let owner_nv_index_attributes = NvIndexAttributesBuilder::new()
+        .with_owner_write(true)
+        .with_owner_read(true)
+        .with_nv_index_type(nv_index_type)
+        .build()
+        .expect("Failed to create owner nv index attributes");
+
+    let owner_nv_public = NvPublicBuilder::new()
+        .with_nv_index(nv_index)
+        .with_index_name_algorithm(HashingAlgorithm::Sha256)
+        .with_index_attributes(owner_nv_index_attributes)
+        .with_data_area_size(8)
+        .build()
+        .unwrap();
+
+    context
+        .nv_define_space(Provision::Owner, None, &owner_nv_public)
+        .unwrap()
+}
+
+#[test]
+fn read_counter() {
+    let mut context = create_ctx_with_session();
+
+    let nv_index = NvIndexTpmHandle::new(0x01500033).unwrap();
+    let owner_nv_index_handle = define_nv_index(&mut context, nv_index, NvIndexType::Counter);
+
+    context
+        .nv_increment(NvAuth::Owner, owner_nv_index_handle)
+        .unwrap();
+    let first_value = nv::read_counter(&mut context, NvAuth::Owner, nv_index);
+    context
+        .nv_increment(NvAuth::Owner, owner_nv_index_handle)
+        .unwrap();
+    let second_value = nv::read_counter(&mut context, NvAuth::Owner, nv_index);
+    let wrong_type_result = nv::read_bits(&mut context, NvAuth::Owner, nv_index);
+
+    context
+        .nv_undefine_space(Provision::Owner, owner_nv_index_handle)
+        .unwrap();
+
+    assert_eq!(second_value.unwrap(), first_value.unwrap() + 1);
+    assert_eq!(
+        wrong_type_result.unwrap_err(),
+        Error::WrapperError(WrapperErrorKind::InvalidParam)
+    );
+}
+
+#[test]
+fn read_bits() {
+    let mut context = create_ctx_with_session();
+
+    let nv_index = NvIndexTpmHandle::new(0x01500034).unwrap();
+    let owner_nv_index_handle = define_nv_index(&mut context, nv_index, NvIndexType::Bits);
+
+    context
+        .nv_set_bits(NvAuth::Owner, owner_nv_index_handle, 0x8000_0000_0000_0001)
+        .unwrap();
+    let value = nv::read_bits(&mut context, NvAuth::Owner, nv_index);
+    let wrong_type_result = nv::read_counter(&mut context, NvAuth::Owner, nv_index);
+
+    context
+        .nv_undefine_space(Provision::Owner, owner_nv_index_handle)
+        .unwrap();
+
+    assert_eq!(value.unwrap(), 0x8000_0000_0000_0001);
+    assert_eq!(
+        wrong_type_result.unwrap_err(),
+        Error::WrapperError(WrapperErrorKind::InvalidParam)
+    );
+}
+
+#[test]
+fn lock_state() {
+    let mut context = create_ctx_with_session();
+
+    let nv_index = NvIndexTpmHandle::new(0x01500035).unwrap();
+
+    let owner_nv_index_attributes = NvIndexAttributesBuilder::new()
+        .with_owner_write(true)
+        .with_owner_read(true)
+        .with_write_stclear(true)
+        .with_read_stclear(true)
+        .build()
+        .expect("Failed to create owner nv index attributes");
+
+    let owner_nv_public = NvPublicBuilder::new()
+        .with_nv_index(nv_index)
+        .with_index_name_algorithm(HashingAlgorithm::Sha256)
+        .with_index_attributes(owner_nv_index_attributes)
+        .with_data_area_size(8)
+        .build()
+        .unwrap();
+
+    let owner_nv_index_handle = context
+        .nv_define_space(Provision::Owner, None, &owner_nv_public)
+        .unwrap();
+
+    let initial_state = nv::lock_state(&mut context, nv_index);
+    context
+        .nv_write(
+            NvAuth::Owner,
+            owner_nv_index_handle,
+            &MaxNvBuffer::try_from(vec![1, 2, 3, 4, 5, 6, 7, 8]).unwrap(),
+            0,
+        )
+        .unwrap();
+    context
+        .nv_write_lock(NvAuth::Owner, owner_nv_index_handle)
+        .unwrap();
+    let write_locked_state = nv::lock_state(&mut context, nv_index);
+    context
+        .nv_read_lock(NvAuth::Owner, owner_nv_index_handle)
+        .unwrap();
+    let read_locked_state = nv::lock_state(&mut context, nv_index);
+
+    context
+        .nv_undefine_space(Provision::Owner, owner_nv_index_handle)
+        .unwrap();
+
+    assert_eq!(
+        initial_state.unwrap(),
+        nv::NvLockState {
+            written: false,
+            write_locked: false,
+            read_locked: false,
+        }
+    );
+    assert_eq!(
+        write_locked_state.unwrap(),
+        nv::NvLockState {
+            written: true,
+            write_locked: true,
+            read_locked: false,
+        }
+    );
+    assert_eq!(
+        read_locked_state.unwrap(),
+        nv::NvLockState {
+            written: true,
+            write_locked: true,
+            read_locked: true,
+        }
+    );
+}
+
+#[test]
+// `io::Error::other` is not available with the minimum supported Rust version.
+#[allow(unknown_lints, clippy::io_other_error)]
+fn reader_writer() {
+    let mut context = create_ctx_with_session();
+
+    let nv_index = NvIndexTpmHandle::new(0x01500042).unwrap();
+
+    let owner_nv_index_attributes = NvIndexAttributesBuilder::new()
+        .with_owner_write(true)
+        .with_owner_read(true)
+        .build()
+        .expect("Failed to create owner nv index attributes");
+
+    let owner_nv_public = NvPublicBuilder::new()
+        .with_nv_index(nv_index)
+        .with_index_name_algorithm(HashingAlgorithm::Sha256)
+        .with_index_attributes(owner_nv_index_attributes)
+        .with_data_area_size(1540)
+        .build()
+        .unwrap();
+
+    let owner_nv_index_handle = context
+        .nv_define_space(Provision::Owner, None, &owner_nv_public)
+        .unwrap();
+
+    let data: Vec<u8> = (0..1540).map(|i| (i % 251) as u8).collect();
+    let result = (|| -> std::io::Result<(Vec<u8>, Vec<u8>, usize)> {
+        let mut rw = nv::NvReaderWriter::open(&mut context, NvAuth::Owner, nv_index)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))?;
+        assert_eq!(rw.data_size(), 1540);
+
+        rw.write_all(&data)?;
+        // Writing past the end of the data area fails.
+        let overflow = rw.write(&[0xff])?;
+
+        let _ = rw.seek(SeekFrom::Start(0))?;
+        let mut read_back = Vec::new();
+        let _ = rw.read_to_end(&mut read_back)?;
+
+        let _ = rw.seek(SeekFrom::End(-10))?;
+        let mut tail = vec![0; 10];
+        rw.read_exact(&mut tail)?;
+        Ok((read_back, tail, overflow))
+    })();
+
+    context
+        .nv_undefine_space(Provision::Owner, owner_nv_index_handle)
+        .unwrap();
+
+    let (read_back, tail, overflow) = result.unwrap();
+    assert_eq!(overflow, 0);
+    assert_eq!(read_back, data);
+    assert_eq!(tail, &data[1530..]);
+}
+
+#[test]
+fn define() {
+    let mut context = create_ctx_with_session();
+
+    let nv_index = NvIndexTpmHandle::new(0x01500043).unwrap();
+    let attributes = NvIndexAttributesBuilder::new()
+        .with_owner_write(true)
+        .with_owner_read(true)
+        .with_nv_index_type(NvIndexType::Extend)
+        .build()
+        .expect("Failed to create owner nv index attributes");
+
+    let owner_nv_index_handle = nv::define(
+        &mut context,
+        Provision::Owner,
+        nv_index,
+        NvIndexKind::Extend,
+        HashingAlgorithm::Sha256,
+        attributes,
+        None,
+        None,
+    )
+    .unwrap();
+
+    context
+        .nv_extend(
+            NvAuth::Owner,
+            owner_nv_index_handle,
+            &MaxNvBuffer::try_from(vec![1, 2, 3]).unwrap(),
+        )
+        .unwrap();
+    let read_result = nv::read_full(&mut context, NvAuth::Owner, nv_index);
+
+    // The platform_create attribute does not match the Owner hierarchy.
+    let platform_attributes = NvIndexAttributesBuilder::with_attributes(attributes)
+        .with_platform_create(true)
+        .build()
+        .unwrap();
+    let wrong_hierarchy_result = nv::define(
+        &mut context,
+        Provision::Owner,
+        NvIndexTpmHandle::new(0x01500044).unwrap(),
+        NvIndexKind::Extend,
+        HashingAlgorithm::Sha256,
+        platform_attributes,
+        None,
+        None,
+    );
+
+    context
+        .nv_undefine_space(Provision::Owner, owner_nv_index_handle)
+        .unwrap();
+
+    assert_eq!(read_result.unwrap().len(), 32);
+    assert_eq!(
+        wrong_hierarchy_result.unwrap_err(),
+        Error::WrapperError(WrapperErrorKind::InconsistentParams)
+    );
+}
+
+#[test]
+fn nv_public_validation() {
+    let nv_index = NvIndexTpmHandle::new(0x01500045).unwrap();
+    let attributes = |nv_index_type| {
+        NvIndexAttributesBuilder::new()
+            .with_owner_write(true)
+            .with_owner_read(true)
+            .with_nv_index_type(nv_index_type)
+    };
+
+    let counter = nv::nv_public(
+        nv_index,
+        NvIndexKind::Counter,
+        HashingAlgorithm::Sha256,
+        attributes(NvIndexType::Counter).build().unwrap(),
+        None,
+    )
+    .unwrap();
+    assert_eq!(counter.data_size(), 8);
+
+    let extend = nv::nv_public(
+        nv_index,
+        NvIndexKind::Extend,
+        HashingAlgorithm::Sha384,
+        attributes(NvIndexType::Extend).build().unwrap(),
+        None,
+    )
+    .unwrap();
+    assert_eq!(extend.data_size(), 48);
+
+    let ordinary = nv::nv_public(
+        nv_index,
+        NvIndexKind::Ordinary { size: 100 },
+        HashingAlgorithm::Sha256,
+        attributes(NvIndexType::Ordinary).build().unwrap(),
+        None,
+    )
+    .unwrap();
+    assert_eq!(ordinary.data_size(), 100);
+
+    assert_eq!(
+        nv::nv_public(
+            nv_index,
+            NvIndexKind::Counter,
+            HashingAlgorithm::Null,
+            attributes(NvIndexType::Counter).build().unwrap(),
+            None,
+        )
+        .unwrap_err(),
+        Error::WrapperError(WrapperErrorKind::InvalidParam)
+    );
+
+    assert_eq!(
+        nv::nv_public(
+            nv_index,
+            NvIndexKind::Bits,
+            HashingAlgorithm::Sha256,
+            attributes(NvIndexType::Counter).build().unwrap(),
+            None,
+        )
+        .unwrap_err(),
+        Error::WrapperError(WrapperErrorKind::InconsistentParams)
+    );
+
+    assert_eq!(
+        nv::nv_public(
+            nv_index,
+            NvIndexKind::Counter,
+            HashingAlgorithm::Sha256,
+            attributes(NvIndexType::Counter)
+                .with_clear_stclear(true)
+                .build()
+                .unwrap(),
+            None,
+        )
+        .unwrap_err(),
+        Error::WrapperError(WrapperErrorKind::InconsistentParams)
+    );
+
+    assert_eq!(
+        nv::nv_public(
+            nv_index,
+            NvIndexKind::Ordinary { size: 8 },
+            HashingAlgorithm::Sha256,
+            attributes(NvIndexType::Ordinary).build().unwrap(),
+            Some(&Digest::try_from(vec![0xFF; 20]).unwrap()),
+        )
+        .unwrap_err(),
+        Error::WrapperError(WrapperErrorKind::WrongParamSize)
+    );
+
+    assert_eq!(
+        nv::nv_public(
+            nv_index,
+            NvIndexKind::Ordinary { size: 8 },
+            HashingAlgorithm::Sha256,
+            attributes(NvIndexType::Ordinary)
+                .with_policy_read(true)
+                .build()
+                .unwrap(),
+            None,
+        )
+        .unwrap_err(),
+        Error::WrapperError(WrapperErrorKind::ParamsMissing)
+    );
+
+    assert_eq!(
+        nv::nv_public(
+            nv_index,
+            NvIndexKind::Ordinary { size: 0 },
+            HashingAlgorithm::Sha256,
+            attributes(NvIndexType::Ordinary).build().unwrap(),
+            None,
+        )
+        .unwrap_err(),
+        Error::WrapperError(WrapperErrorKind::InvalidParam)
+    );
+
+    // PIN indexes cannot be written with the index authorization, nor locked.
+    let pin_pass = |builder: NvIndexAttributesBuilder| {
+        nv::nv_public(
+            nv_index,
+            NvIndexKind::PinPass,
+            HashingAlgorithm::Sha256,
+            builder.build().unwrap(),
+            None,
+        )
+        .unwrap_err()
+    };
+    assert_eq!(
+        pin_pass(attributes(NvIndexType::PinPass).with_auth_write(true)),
+        Error::WrapperError(WrapperErrorKind::InconsistentParams)
+    );
+    assert_eq!(
+        pin_pass(attributes(NvIndexType::PinPass).with_global_lock(true)),
+        Error::WrapperError(WrapperErrorKind::InconsistentParams)
+    );
+    assert_eq!(
+        pin_pass(attributes(NvIndexType::PinPass).with_write_define(true)),
+        Error::WrapperError(WrapperErrorKind::InconsistentParams)
+    );
+    assert_eq!(
+        nv::nv_public(
+            nv_index,
+            NvIndexKind::PinFail,
+            HashingAlgorithm::Sha256,
+            attributes(NvIndexType::PinFail)
+                .with_no_da(true)
+                .with_auth_write(true)
+                .build()
+                .unwrap(),
+            None,
+        )
+        .unwrap_err(),
+        Error::WrapperError(WrapperErrorKind::InconsistentParams)
+    );
+
+    // The attributes are built without the builder, which would reject them.
+    let owner_write = 1 << 1;
+    let owner_read = 1 << 17;
+    let no_da = 1 << 25;
+    let pin_fail = 0x8 << 4;
+
+    assert_eq!(
+        nv::nv_public(
+            nv_index,
+            NvIndexKind::PinFail,
+            HashingAlgorithm::Sha256,
+            NvIndexAttributes(owner_write | owner_read | pin_fail),
+            None,
+        )
+        .unwrap_err(),
+        Error::WrapperError(WrapperErrorKind::ParamsMissing)
+    );
+    let pin_fail_index = nv::nv_public(
+        nv_index,
+        NvIndexKind::PinFail,
+        HashingAlgorithm::Sha256,
+        NvIndexAttributes(owner_write | owner_read | pin_fail | no_da),
+        None,
+    )
+    .unwrap();
+    assert_eq!(pin_fail_index.data_size(), 8);
+
+    assert_eq!(
+        nv::nv_public(
+            nv_index,
+            NvIndexKind::Ordinary { size: 8 },
+            HashingAlgorithm::Sha256,
+            NvIndexAttributes(owner_write),
+            None,
+        )
+        .unwrap_err(),
+        Error::WrapperError(WrapperErrorKind::ParamsMissing)
+    );
+
+    assert_eq!(
+        nv::nv_public(
+            nv_index,
+            NvIndexKind::Ordinary { size: 8 },
+            HashingAlgorithm::Sha256,
+            NvIndexAttributes(owner_read),
+            None,
+        )
+        .unwrap_err(),
+        Error::WrapperError(WrapperErrorKind::ParamsMissing)
+    );
+}
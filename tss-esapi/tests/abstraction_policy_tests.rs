@@ -0,0 +1,962 @@
+// Copyright 2021 Contributors to the Parsec project.
+// SPDX-License-Identifier: Apache-2.0
+#![cfg(feature = "software-crypto")]
+
+use std::convert::TryFrom;
+use tss_esapi::{
+    abstraction::policy::{NoAuthorizer, Policy, PolicyCalculator, PolicyOrTree, SignedPolicy},
+    attributes::NvIndexAttributesBuilder,
+    constants::{
+        tss::{
+            TPM2_CC_Duplicate, TPM2_CC_Unseal, TPM2_CC_FIRST, TPM2_RH_ENDORSEMENT, TPM2_RH_NULL,
+            TPM2_RH_OWNER, TPM2_ST_AUTH_SECRET, TPM2_ST_AUTH_SIGNED, TPM2_ST_VERIFIED,
+        },
+        ArithmeticOperation, CommandCode, SessionType,
+    },
+    handles::{AuthHandle, NvIndexTpmHandle, ObjectHandle, SessionHandle, TpmHandle},
+    interface_types::{
+        algorithm::HashingAlgorithm,
+        resource_handles::{Hierarchy, NvAuth, Provision},
+        session_handles::{AuthSession, PolicySession},
+    },
+    nv::storage::NvPublicBuilder,
+    structures::{
+        AuthTicket, Digest, DigestList, HashScheme, MaxNvBuffer, Name, Nonce, Operand,
+        PcrSelectionListBuilder, PcrSlot, PublicKeyRsa, RsaSignature, Signature, SignatureScheme,
+        SymmetricDefinition, VerifiedTicket,
+    },
+    tss2_esys::{TPMT_TK_AUTH, TPMT_TK_VERIFIED},
+    Context, Error, WrapperErrorKind,
+};
+
+mod common;
+use common::{create_ctx_with_session, create_ctx_without_session, signing_key_pub};
+
+fn start_trial_session(context: &mut Context) -> PolicySession {
+    let trial_auth_session = context
+        .start_auth_session(
+            None,
+            None,
+            None,
+            SessionType::Trial,
+            SymmetricDefinition::AES_256_CFB,
+            HashingAlgorithm::Sha256,
+        )
+        .expect("Start auth session failed")
+        .expect("Start auth session returned a NONE handle");
+    PolicySession::try_from(trial_auth_session)
+        .expect("Failed to convert auth session into policy session")
+}
+
+fn start_policy_session(context: &mut Context) -> PolicySession {
+    let policy_auth_session = context
+        .start_auth_session(
+            None,
+            None,
+            None,
+            SessionType::Policy,
+            SymmetricDefinition::AES_256_CFB,
+            HashingAlgorithm::Sha256,
+        )
+        .expect("Start auth session failed")
+        .expect("Start auth session returned a NONE handle");
+    PolicySession::try_from(policy_auth_session)
+        .expect("Failed to convert auth session into policy session")
+}
+
+fn flush_trial_session(context: &mut Context, trial_session: PolicySession) {
+    context
+        .flush_context(SessionHandle::from(trial_session).into())
+        .expect("Failed to flush trial session");
+}
+
+#[test]
+fn calculator_known_digests() {
+    // Well known digest of a policy only containing PolicyAuthValue.
+    let mut calculator = PolicyCalculator::new(HashingAlgorithm::Sha256).unwrap();
+    calculator.policy_auth_value().unwrap();
+    assert_eq!(
+        calculator.digest().unwrap().value(),
+        [
+            0x8f, 0xcd, 0x21, 0x69, 0xab, 0x92, 0x69, 0x4e, 0x0c, 0x63, 0x3f, 0x1a, 0xb7, 0x72,
+            0x84, 0x2b, 0x82, 0x41, 0xbb, 0xc2, 0x02, 0x88, 0x98, 0x1f, 0xc7, 0xac, 0x1e, 0xdd,
+            0xc1, 0xfd, 0xdb, 0x0e,
+        ]
+    );
+
+    // PolicyPassword results in the same digest.
+    let mut password_calculator = PolicyCalculator::new(HashingAlgorithm::Sha256).unwrap();
+    password_calculator.policy_password().unwrap();
+    assert_eq!(calculator, password_calculator);
+
+    // The policy of the default EK templates is a PolicySecret for the
+    // endorsement hierarchy.
+    let mut calculator = PolicyCalculator::new(HashingAlgorithm::Sha256).unwrap();
+    calculator
+        .policy_secret(
+            &Name::try_from(vec![0x40, 0x00, 0x00, 0x0B]).unwrap(),
+            &Nonce::default(),
+        )
+        .unwrap();
+    assert_eq!(
+        calculator.digest().unwrap().value(),
+        [
+            0x83, 0x71, 0x97, 0x67, 0x44, 0x84, 0xb3, 0xf8, 0x1a, 0x90, 0xcc, 0x8d, 0x46, 0xa5,
+            0xd7, 0x24, 0xfd, 0x52, 0xd7, 0x6e, 0x06, 0x52, 0x0b, 0x64, 0xf2, 0xa1, 0xda, 0x1b,
+            0x33, 0x14, 0x69, 0xaa,
+        ]
+    );
+
+    let mut calculator = PolicyCalculator::new(HashingAlgorithm::Sha1).unwrap();
+    calculator
+        .policy_template(
+            &Digest::try_from(vec![
+                1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20,
+            ])
+            .unwrap(),
+        )
+        .unwrap();
+    assert_eq!(
+        calculator.digest().unwrap().value(),
+        [
+            0xf6, 0x6d, 0x2a, 0x9c, 0x6e, 0xa8, 0xdf, 0x1a, 0x49, 0x3c, 0x42, 0xcc, 0xac, 0x6e,
+            0x3d, 0x08, 0xc0, 0x84, 0xcf, 0x73,
+        ]
+    );
+}
+
+#[test]
+fn calculator_reset() {
+    let mut calculator = PolicyCalculator::new(HashingAlgorithm::Sha384).unwrap();
+    assert_eq!(calculator.digest().unwrap().value(), [0; 48]);
+    calculator.policy_command_code(TPM2_CC_Unseal).unwrap();
+    assert_ne!(calculator.digest().unwrap().value(), [0; 48]);
+    calculator.reset();
+    assert_eq!(calculator.digest().unwrap().value(), [0; 48]);
+}
+
+#[test]
+fn calculator_errors() {
+    assert_eq!(
+        PolicyCalculator::new(HashingAlgorithm::Null).unwrap_err(),
+        Error::WrapperError(WrapperErrorKind::UnsupportedParam)
+    );
+    assert_eq!(
+        PolicyCalculator::new(HashingAlgorithm::Sm3_256).unwrap_err(),
+        Error::WrapperError(WrapperErrorKind::UnsupportedParam)
+    );
+
+    let mut calculator = PolicyCalculator::new(HashingAlgorithm::Sha256).unwrap();
+    let mut digest_list = DigestList::new();
+    digest_list
+        .add(Digest::try_from(vec![1; 32]).unwrap())
+        .unwrap();
+    assert_eq!(
+        calculator.policy_or(&digest_list).unwrap_err(),
+        Error::WrapperError(WrapperErrorKind::WrongParamSize)
+    );
+}
+
+#[test]
+fn calculator_matches_trial_session() {
+    let mut context = create_ctx_without_session();
+    let trial_session = start_trial_session(&mut context);
+    let mut calculator = PolicyCalculator::new(HashingAlgorithm::Sha256).unwrap();
+
+    let pcr_selection_list = PcrSelectionListBuilder::new()
+        .with_selection(HashingAlgorithm::Sha256, &[PcrSlot::Slot0, PcrSlot::Slot7])
+        .build();
+    let pcr_policy_digest = Digest::try_from(vec![0x5A; 32]).unwrap();
+    context
+        .policy_pcr(
+            trial_session,
+            &pcr_policy_digest,
+            pcr_selection_list.clone(),
+        )
+        .unwrap();
+    calculator
+        .policy_pcr(&pcr_policy_digest, pcr_selection_list)
+        .unwrap();
+
+    context.policy_locality(trial_session, 3).unwrap();
+    calculator.policy_locality(3).unwrap();
+
+    let operand = Operand::try_from(vec![0xFF; 8]).unwrap();
+    context
+        .policy_counter_timer(
+            trial_session,
+            operand.clone(),
+            0,
+            ArithmeticOperation::UnsignedLt,
+        )
+        .unwrap();
+    calculator
+        .policy_counter_timer(&operand, 0, ArithmeticOperation::UnsignedLt)
+        .unwrap();
+
+    context.policy_physical_presence(trial_session).unwrap();
+    calculator.policy_physical_presence().unwrap();
+
+    context.policy_auth_value(trial_session).unwrap();
+    calculator.policy_auth_value().unwrap();
+
+    context.policy_nv_written(trial_session, true).unwrap();
+    calculator.policy_nv_written(true).unwrap();
+
+    let cp_hash = Digest::try_from(vec![0x11; 32]).unwrap();
+    context.policy_cp_hash(trial_session, &cp_hash).unwrap();
+    calculator.policy_cp_hash(&cp_hash).unwrap();
+
+    context
+        .policy_command_code(trial_session, TPM2_CC_Unseal)
+        .unwrap();
+    calculator.policy_command_code(TPM2_CC_Unseal).unwrap();
+
+    assert_eq!(
+        context.policy_get_digest(trial_session).unwrap(),
+        calculator.digest().unwrap()
+    );
+    flush_trial_session(&mut context, trial_session);
+}
+
+#[test]
+fn calculator_matches_trial_session_duplication() {
+    let mut context = create_ctx_without_session();
+    let trial_session = start_trial_session(&mut context);
+    let mut calculator = PolicyCalculator::new(HashingAlgorithm::Sha256).unwrap();
+
+    let mut object_name = vec![0x00, 0x0B];
+    object_name.extend_from_slice(&[0x33; 32]);
+    let object_name = Name::try_from(object_name).unwrap();
+    let mut new_parent_name = vec![0x00, 0x0B];
+    new_parent_name.extend_from_slice(&[0x44; 32]);
+    let new_parent_name = Name::try_from(new_parent_name).unwrap();
+
+    context
+        .policy_duplication_select(
+            trial_session,
+            object_name.clone(),
+            new_parent_name.clone(),
+            true,
+        )
+        .unwrap();
+    calculator
+        .policy_duplication_select(&object_name, &new_parent_name, true)
+        .unwrap();
+    context
+        .policy_command_code(trial_session, TPM2_CC_Duplicate)
+        .unwrap();
+    calculator.policy_command_code(TPM2_CC_Duplicate).unwrap();
+
+    assert_eq!(
+        context.policy_get_digest(trial_session).unwrap(),
+        calculator.digest().unwrap()
+    );
+    flush_trial_session(&mut context, trial_session);
+}
+
+#[test]
+fn calculator_matches_trial_session_secret_and_or() {
+    let mut context = create_ctx_without_session();
+    let trial_session = start_trial_session(&mut context);
+    let mut calculator = PolicyCalculator::new(HashingAlgorithm::Sha256).unwrap();
+
+    let policy_ref = Nonce::try_from(vec![1, 2, 3]).unwrap();
+    let _ = context
+        .execute_with_session(Some(AuthSession::Password), |ctx| {
+            ctx.policy_secret(
+                trial_session,
+                AuthHandle::Owner,
+                Nonce::default(),
+                Digest::default(),
+                policy_ref.clone(),
+                None,
+            )
+        })
+        .unwrap();
+    let owner_name = context.tr_get_name(ObjectHandle::Owner).unwrap();
+    calculator.policy_secret(&owner_name, &policy_ref).unwrap();
+
+    let mut digest_list = DigestList::new();
+    digest_list.add(calculator.digest().unwrap()).unwrap();
+    digest_list
+        .add(Digest::try_from(vec![0x22; 32]).unwrap())
+        .unwrap();
+    digest_list
+        .add(Digest::try_from(vec![0x33; 32]).unwrap())
+        .unwrap();
+    context
+        .policy_or(trial_session, digest_list.clone())
+        .unwrap();
+    calculator.policy_or(&digest_list).unwrap();
+
+    assert_eq!(
+        context.policy_get_digest(trial_session).unwrap(),
+        calculator.digest().unwrap()
+    );
+    flush_trial_session(&mut context, trial_session);
+}
+
+#[test]
+fn calculator_matches_trial_session_signed_and_authorize() {
+    let mut context = create_ctx_with_session();
+    let key_handle = context
+        .create_primary(Hierarchy::Owner, &signing_key_pub(), None, None, None, None)
+        .expect("Failed to create signing key")
+        .key_handle;
+    let key_name = context.tr_get_name(key_handle.into()).unwrap();
+    let trial_session = context.execute_without_session(start_trial_session);
+    let mut calculator = PolicyCalculator::new(HashingAlgorithm::Sha256).unwrap();
+
+    // The signature is not checked in a trial session.
+    let policy_ref = Nonce::try_from(vec![1, 2, 3]).unwrap();
+    let signature = Signature::RsaSsa(
+        RsaSignature::create(
+            HashingAlgorithm::Sha256,
+            PublicKeyRsa::try_from(vec![0xab; 256]).unwrap(),
+        )
+        .unwrap(),
+    );
+    let _ = context
+        .policy_signed(
+            trial_session,
+            key_handle.into(),
+            Nonce::default(),
+            Digest::default(),
+            policy_ref.clone(),
+            None,
+            signature,
+        )
+        .unwrap();
+    calculator.policy_signed(&key_name, &policy_ref).unwrap();
+
+    let name_hash = Digest::try_from(vec![0x55; 32]).unwrap();
+    context.policy_name_hash(trial_session, &name_hash).unwrap();
+    calculator.policy_name_hash(&name_hash).unwrap();
+
+    assert_eq!(
+        context.policy_get_digest(trial_session).unwrap(),
+        calculator.digest().unwrap()
+    );
+
+    // Neither is the ticket of the approval.
+    let check_ticket = VerifiedTicket::try_from(TPMT_TK_VERIFIED {
+        tag: TPM2_ST_VERIFIED,
+        hierarchy: TPM2_RH_NULL,
+        digest: Default::default(),
+    })
+    .unwrap();
+    let approved_policy = calculator.digest().unwrap();
+    context
+        .policy_authorize(
+            trial_session,
+            &approved_policy,
+            &policy_ref,
+            &key_name,
+            check_ticket,
+        )
+        .unwrap();
+    calculator.policy_authorize(&policy_ref, &key_name).unwrap();
+
+    assert_eq!(
+        context.policy_get_digest(trial_session).unwrap(),
+        calculator.digest().unwrap()
+    );
+    flush_trial_session(&mut context, trial_session);
+    context.flush_context(key_handle.into()).unwrap();
+}
+
+#[test]
+fn calculator_matches_trial_session_nv() {
+    let mut context = create_ctx_with_session();
+
+    // The NV index holds a policy as a TPMT_HA, for PolicyAuthorizeNV.
+    let nv_index = NvIndexTpmHandle::new(0x01500048).unwrap();
+    let nv_public = NvPublicBuilder::new()
+        .with_nv_index(nv_index)
+        .with_index_name_algorithm(HashingAlgorithm::Sha256)
+        .with_index_attributes(
+            NvIndexAttributesBuilder::new()
+                .with_owner_write(true)
+                .with_owner_read(true)
+                .build()
+                .unwrap(),
+        )
+        .with_data_area_size(34)
+        .build()
+        .unwrap();
+    let nv_index_handle = context
+        .nv_define_space(Provision::Owner, None, &nv_public)
+        .unwrap();
+    let mut nv_data = vec![0x00, 0x0B];
+    nv_data.extend_from_slice(&[0x66; 32]);
+    context
+        .nv_write(
+            NvAuth::Owner,
+            nv_index_handle,
+            &MaxNvBuffer::try_from(nv_data).unwrap(),
+            0,
+        )
+        .unwrap();
+    // The name changes once the index has been written.
+    let (_, nv_index_name) = context.nv_read_public(nv_index_handle).unwrap();
+
+    let trial_session = context.execute_without_session(start_trial_session);
+    let mut calculator = PolicyCalculator::new(HashingAlgorithm::Sha256).unwrap();
+
+    // The comparison is not made in a trial session.
+    let operand = Operand::try_from(vec![0x10, 0x20]).unwrap();
+    let nv_result = context.policy_nv(
+        trial_session,
+        NvAuth::Owner,
+        nv_index_handle,
+        operand.clone(),
+        4,
+        ArithmeticOperation::UnsignedGe,
+    );
+    let nv_digest = context.policy_get_digest(trial_session);
+    calculator
+        .policy_nv(&operand, 4, ArithmeticOperation::UnsignedGe, &nv_index_name)
+        .unwrap();
+    let expected_nv_digest = calculator.digest().unwrap();
+
+    let authorize_nv_result =
+        context.policy_authorize_nv(trial_session, NvAuth::Owner, nv_index_handle);
+    let authorize_nv_digest = context.policy_get_digest(trial_session);
+    calculator.policy_authorize_nv(&nv_index_name).unwrap();
+
+    flush_trial_session(&mut context, trial_session);
+    context
+        .nv_undefine_space(Provision::Owner, nv_index_handle)
+        .unwrap();
+
+    nv_result.unwrap();
+    assert_eq!(nv_digest.unwrap(), expected_nv_digest);
+    authorize_nv_result.unwrap();
+    assert_eq!(authorize_nv_digest.unwrap(), calculator.digest().unwrap());
+}
+
+#[test]
+fn calculator_policy_ticket() {
+    let owner_name = Name::try_from(vec![0x40, 0x00, 0x00, 0x01]).unwrap();
+    let policy_ref = Nonce::try_from(vec![1, 2, 3]).unwrap();
+    let ticket = |tag| {
+        AuthTicket::try_from(TPMT_TK_AUTH {
+            tag,
+            hierarchy: TPM2_RH_OWNER,
+            digest: Default::default(),
+        })
+        .unwrap()
+    };
+
+    // A ticket replays the command which issued it.
+    let mut calculator = PolicyCalculator::new(HashingAlgorithm::Sha256).unwrap();
+    calculator
+        .policy_ticket(&owner_name, &policy_ref, &ticket(TPM2_ST_AUTH_SECRET))
+        .unwrap();
+    let mut expected = PolicyCalculator::new(HashingAlgorithm::Sha256).unwrap();
+    expected.policy_secret(&owner_name, &policy_ref).unwrap();
+    assert_eq!(calculator, expected);
+
+    let mut calculator = PolicyCalculator::new(HashingAlgorithm::Sha256).unwrap();
+    calculator
+        .policy_ticket(&owner_name, &policy_ref, &ticket(TPM2_ST_AUTH_SIGNED))
+        .unwrap();
+    let mut expected = PolicyCalculator::new(HashingAlgorithm::Sha256).unwrap();
+    expected.policy_signed(&owner_name, &policy_ref).unwrap();
+    assert_eq!(calculator, expected);
+}
+
+#[test]
+fn policy_tree_digest() {
+    let pcr_policy = Policy::Pcr {
+        pcr_selection_list: PcrSelectionListBuilder::new()
+            .with_selection(HashingAlgorithm::Sha256, &[PcrSlot::Slot0, PcrSlot::Slot16])
+            .build(),
+        pcr_digest: Digest::try_from(vec![0x5A; 32]).unwrap(),
+    };
+    let tree = Policy::Or(vec![
+        Policy::And(vec![pcr_policy, Policy::CommandCode(CommandCode::Unseal)]),
+        Policy::And(vec![
+            Policy::Secret {
+                auth_handle: TpmHandle::try_from(TPM2_RH_ENDORSEMENT).unwrap(),
+                auth_name: Name::try_from(vec![0x40, 0x00, 0x00, 0x0B]).unwrap(),
+                policy_ref: Nonce::default(),
+            },
+            Policy::AuthValue,
+        ]),
+    ]);
+
+    let mut pcr_branch = PolicyCalculator::new(HashingAlgorithm::Sha256).unwrap();
+    pcr_branch
+        .policy_pcr(
+            &Digest::try_from(vec![0x5A; 32]).unwrap(),
+            PcrSelectionListBuilder::new()
+                .with_selection(HashingAlgorithm::Sha256, &[PcrSlot::Slot0, PcrSlot::Slot16])
+                .build(),
+        )
+        .unwrap();
+    pcr_branch.policy_command_code(TPM2_CC_Unseal).unwrap();
+    let mut secret_branch = PolicyCalculator::new(HashingAlgorithm::Sha256).unwrap();
+    secret_branch
+        .policy_secret(
+            &Name::try_from(vec![0x40, 0x00, 0x00, 0x0B]).unwrap(),
+            &Nonce::default(),
+        )
+        .unwrap();
+    secret_branch.policy_auth_value().unwrap();
+    let mut digest_list = DigestList::new();
+    digest_list.add(pcr_branch.digest().unwrap()).unwrap();
+    digest_list.add(secret_branch.digest().unwrap()).unwrap();
+    let mut expected = PolicyCalculator::new(HashingAlgorithm::Sha256).unwrap();
+    expected.policy_or(&digest_list).unwrap();
+
+    assert_eq!(
+        tree.digest(HashingAlgorithm::Sha256).unwrap(),
+        expected.digest().unwrap()
+    );
+
+    // A single branch cannot be combined with PolicyOR.
+    assert_eq!(
+        Policy::Or(vec![Policy::AuthValue])
+            .digest(HashingAlgorithm::Sha256)
+            .unwrap_err(),
+        Error::WrapperError(WrapperErrorKind::WrongParamSize)
+    );
+}
+
+#[test]
+fn policy_tree_serialization() {
+    let tree = Policy::And(vec![
+        Policy::Nv {
+            auth_handle: None,
+            nv_index: NvIndexTpmHandle::new(0x0150_0048).unwrap(),
+            nv_index_name: Name::try_from(vec![0x00, 0x0B, 0x01, 0x02]).unwrap(),
+            operand_b: Operand::try_from(vec![5]).unwrap(),
+            offset: 7,
+            operation: ArithmeticOperation::UnsignedGe,
+        },
+        Policy::Or(vec![
+            Policy::Signed {
+                key_name: Name::try_from(vec![0x00, 0x0B, 0x03]).unwrap(),
+                policy_ref: Nonce::try_from(vec![1, 2, 3]).unwrap(),
+            },
+            Policy::Authorize {
+                key_name: Name::try_from(vec![0x00, 0x0B, 0x04]).unwrap(),
+                policy_ref: Nonce::default(),
+            },
+        ]),
+        Policy::Pcr {
+            pcr_selection_list: PcrSelectionListBuilder::new()
+                .with_selection(HashingAlgorithm::Sha256, &[PcrSlot::Slot7])
+                .build(),
+            pcr_digest: Digest::try_from(vec![0x5A; 32]).unwrap(),
+        },
+        Policy::Secret {
+            auth_handle: TpmHandle::try_from(TPM2_RH_OWNER).unwrap(),
+            auth_name: Name::try_from(vec![0x40, 0x00, 0x00, 0x01]).unwrap(),
+            policy_ref: Nonce::default(),
+        },
+        Policy::CommandCode(CommandCode::Unseal),
+    ]);
+
+    let serialized = serde_json::to_string(&tree).unwrap();
+    let deserialized: Policy = serde_json::from_str(&serialized).unwrap();
+    assert_eq!(tree, deserialized);
+
+    // Values that are invalid for their type are rejected.
+    let oversized_nonce = serialized.replace("[1,2,3]", &format!("{:?}", vec![1; 65]));
+    assert!(serde_json::from_str::<Policy>(&oversized_nonce).is_err());
+    let invalid_nv_index = serialized.replace(
+        &u32::from(NvIndexTpmHandle::new(0x0150_0048).unwrap()).to_string(),
+        &TPM2_RH_OWNER.to_string(),
+    );
+    assert!(serde_json::from_str::<Policy>(&invalid_nv_index).is_err());
+}
+
+#[test]
+fn policy_tree_execute_selects_branch() {
+    let mut context = create_ctx_without_session();
+    let policy_session = start_policy_session(&mut context);
+
+    // The PCR branch cannot be satisfied, as the digest does not match
+    // the value of PCR16.
+    let tree = Policy::And(vec![
+        Policy::CommandCode(CommandCode::Unseal),
+        Policy::Or(vec![
+            Policy::Pcr {
+                pcr_selection_list: PcrSelectionListBuilder::new()
+                    .with_selection(HashingAlgorithm::Sha256, &[PcrSlot::Slot16])
+                    .build(),
+                pcr_digest: Digest::try_from(vec![0x5A; 32]).unwrap(),
+            },
+            Policy::AuthValue,
+        ]),
+    ]);
+
+    tree.execute(
+        &mut context,
+        policy_session,
+        HashingAlgorithm::Sha256,
+        None,
+        &mut NoAuthorizer,
+    )
+    .expect("Failed to execute the policy");
+    assert_eq!(
+        context.policy_get_digest(policy_session).unwrap(),
+        tree.digest(HashingAlgorithm::Sha256).unwrap()
+    );
+
+    // A branch failing after one of its assertions succeeded is abandoned
+    // by restarting the session, and the branches taken before it are
+    // replayed.
+    let wrong_pcr = Policy::Pcr {
+        pcr_selection_list: PcrSelectionListBuilder::new()
+            .with_selection(HashingAlgorithm::Sha256, &[PcrSlot::Slot16])
+            .build(),
+        pcr_digest: Digest::try_from(vec![0x5A; 32]).unwrap(),
+    };
+    let pcr_last_tree = Policy::And(vec![
+        Policy::Or(vec![wrong_pcr.clone(), Policy::AuthValue]),
+        Policy::Or(vec![
+            Policy::And(vec![Policy::AuthValue, wrong_pcr]),
+            Policy::CommandCode(CommandCode::Unseal),
+        ]),
+    ]);
+    pcr_last_tree
+        .execute(
+            &mut context,
+            policy_session,
+            HashingAlgorithm::Sha256,
+            None,
+            &mut NoAuthorizer,
+        )
+        .expect("Failed to execute the policy");
+    assert_eq!(
+        context.policy_get_digest(policy_session).unwrap(),
+        pcr_last_tree.digest(HashingAlgorithm::Sha256).unwrap()
+    );
+
+    // Without an authorizer, signed assertions cannot be satisfied.
+    let signed_tree = Policy::Signed {
+        key_name: Name::try_from(vec![0x00, 0x0B, 0x01]).unwrap(),
+        policy_ref: Nonce::default(),
+    };
+    assert_eq!(
+        signed_tree
+            .execute(
+                &mut context,
+                policy_session,
+                HashingAlgorithm::Sha256,
+                None,
+                &mut NoAuthorizer,
+            )
+            .unwrap_err(),
+        Error::WrapperError(WrapperErrorKind::ParamsMissing)
+    );
+}
+
+/// Runs `f` with a policy session satisfying `tree` for `command_code`
+fn execute_with_policy<T>(
+    context: &mut Context,
+    tree: &Policy,
+    command_code: Option<CommandCode>,
+    f: impl FnOnce(&mut Context) -> tss_esapi::Result<T>,
+) -> tss_esapi::Result<T> {
+    let policy_session = context.execute_without_session(start_policy_session);
+    let result = tree
+        .execute(
+            context,
+            policy_session,
+            HashingAlgorithm::Sha256,
+            command_code,
+            &mut NoAuthorizer,
+        )
+        .and_then(|_| context.execute_with_session(Some(policy_session.into()), f));
+    context
+        .flush_context(SessionHandle::from(policy_session).into())
+        .expect("Failed to flush policy session");
+    result
+}
+
+#[test]
+fn policy_tree_execute_for_command() {
+    let mut context = create_ctx_with_session();
+
+    // PolicyCommandCode does not fail when it is issued, so the branch is
+    // selected from the command the session is meant for.
+    let tree = Policy::Or(vec![
+        Policy::CommandCode(CommandCode::NvWrite),
+        Policy::CommandCode(CommandCode::NvRead),
+    ]);
+    let nv_index = NvIndexTpmHandle::new(0x01500049).unwrap();
+    let nv_public = NvPublicBuilder::new()
+        .with_nv_index(nv_index)
+        .with_index_name_algorithm(HashingAlgorithm::Sha256)
+        .with_index_auth_policy(&tree.digest(HashingAlgorithm::Sha256).unwrap())
+        .with_index_attributes(
+            NvIndexAttributesBuilder::new()
+                .with_policy_write(true)
+                .with_policy_read(true)
+                .build()
+                .unwrap(),
+        )
+        .with_data_area_size(8)
+        .build()
+        .unwrap();
+    let nv_index_handle = context
+        .nv_define_space(Provision::Owner, None, &nv_public)
+        .unwrap();
+    let data = MaxNvBuffer::try_from(vec![0x42; 8]).unwrap();
+
+    let write_result =
+        execute_with_policy(&mut context, &tree, Some(CommandCode::NvWrite), |ctx| {
+            ctx.nv_write(NvAuth::NvIndex(nv_index_handle), nv_index_handle, &data, 0)
+        });
+    let read_result = execute_with_policy(&mut context, &tree, Some(CommandCode::NvRead), |ctx| {
+        ctx.nv_read(NvAuth::NvIndex(nv_index_handle), nv_index_handle, 8, 0)
+    });
+    // Without the command, the first branch is used.
+    let default_read_result = execute_with_policy(&mut context, &tree, None, |ctx| {
+        ctx.nv_read(NvAuth::NvIndex(nv_index_handle), nv_index_handle, 8, 0)
+    });
+    let unseal_result =
+        execute_with_policy(&mut context, &tree, Some(CommandCode::Unseal), |_| Ok(()));
+
+    context
+        .nv_undefine_space(Provision::Owner, nv_index_handle)
+        .unwrap();
+
+    write_result.expect("Failed to write with the policy");
+    assert_eq!(read_result.expect("Failed to read with the policy"), data);
+    let _ = default_read_result.expect_err("The read was authorized for writing");
+    assert_eq!(
+        unseal_result.unwrap_err(),
+        Error::WrapperError(WrapperErrorKind::InvalidParam)
+    );
+}
+
+fn digest_list(digests: &[Digest]) -> DigestList {
+    let mut digest_list = DigestList::new();
+    for digest in digests {
+        digest_list.add(digest.clone()).unwrap();
+    }
+    digest_list
+}
+
+fn digest_lists_values(digest_lists: &[DigestList]) -> Vec<Vec<Digest>> {
+    digest_lists
+        .iter()
+        .map(|digest_list| digest_list.value().to_vec())
+        .collect()
+}
+
+#[test]
+fn or_tree_digest() {
+    let branch_digests: Vec<Digest> = (0..20u8)
+        .map(|i| Digest::try_from(vec![i; 32]).unwrap())
+        .collect();
+
+    // Up to eight branches, the tree is a single PolicyOR.
+    let tree = PolicyOrTree::new(HashingAlgorithm::Sha256, branch_digests[..8].to_vec()).unwrap();
+    let mut expected = PolicyCalculator::new(HashingAlgorithm::Sha256).unwrap();
+    expected
+        .policy_or(&digest_list(&branch_digests[..8]))
+        .unwrap();
+    assert_eq!(tree.digest(), &expected.digest().unwrap());
+    assert_eq!(
+        digest_lists_values(&tree.branch_digest_lists(3).unwrap()),
+        vec![branch_digests[..8].to_vec()]
+    );
+
+    // Twenty branches are split in groups of 6, 7 and 7 branches.
+    let tree = PolicyOrTree::new(HashingAlgorithm::Sha256, branch_digests.clone()).unwrap();
+    assert_eq!(tree.branch_count(), 20);
+    let group_digests: Vec<Digest> = [0..6, 6..13, 13..20]
+        .iter()
+        .map(|range| {
+            let mut calculator = PolicyCalculator::new(HashingAlgorithm::Sha256).unwrap();
+            calculator
+                .policy_or(&digest_list(&branch_digests[range.clone()]))
+                .unwrap();
+            calculator.digest().unwrap()
+        })
+        .collect();
+    let root_list = digest_list(&group_digests);
+    let mut expected = PolicyCalculator::new(HashingAlgorithm::Sha256).unwrap();
+    expected.policy_or(&root_list).unwrap();
+    assert_eq!(tree.digest(), &expected.digest().unwrap());
+    assert_eq!(
+        digest_lists_values(&tree.branch_digest_lists(6).unwrap()),
+        vec![branch_digests[6..13].to_vec(), root_list.value().to_vec()]
+    );
+
+    // An OR policy with many branches uses the same tree.
+    let command_codes: Vec<CommandCode> = (TPM2_CC_FIRST..)
+        .filter_map(|code| CommandCode::try_from(code).ok())
+        .take(20)
+        .collect();
+    let policy = Policy::Or(
+        command_codes
+            .iter()
+            .cloned()
+            .map(Policy::CommandCode)
+            .collect(),
+    );
+    let branch_digests = command_codes
+        .iter()
+        .map(|code| {
+            let mut calculator = PolicyCalculator::new(HashingAlgorithm::Sha256).unwrap();
+            calculator.policy_command_code((*code).into()).unwrap();
+            calculator.digest().unwrap()
+        })
+        .collect();
+    assert_eq!(
+        &policy.digest(HashingAlgorithm::Sha256).unwrap(),
+        PolicyOrTree::new(HashingAlgorithm::Sha256, branch_digests)
+            .unwrap()
+            .digest()
+    );
+
+    assert_eq!(
+        PolicyOrTree::new(
+            HashingAlgorithm::Sha256,
+            vec![Digest::try_from(vec![0; 32]).unwrap()]
+        )
+        .unwrap_err(),
+        Error::WrapperError(WrapperErrorKind::WrongParamSize)
+    );
+    assert_eq!(
+        tree.branch_digest_lists(20).unwrap_err(),
+        Error::WrapperError(WrapperErrorKind::InvalidParam)
+    );
+}
+
+#[test]
+fn or_tree_matches_trial_session() {
+    let mut context = create_ctx_without_session();
+    let command_codes = [TPM2_CC_Unseal, TPM2_CC_Duplicate];
+    let branch_digests: Vec<Digest> = (0..30u8)
+        .map(|i| {
+            let mut calculator = PolicyCalculator::new(HashingAlgorithm::Sha256).unwrap();
+            calculator
+                .policy_command_code(command_codes[usize::from(i) % 2])
+                .unwrap();
+            calculator
+                .policy_cp_hash(&Digest::try_from(vec![i; 32]).unwrap())
+                .unwrap();
+            calculator.digest().unwrap()
+        })
+        .collect();
+    let tree = PolicyOrTree::new(HashingAlgorithm::Sha256, branch_digests).unwrap();
+
+    for branch in [0, 17, 29].iter().copied() {
+        let trial_session = start_trial_session(&mut context);
+        context
+            .policy_command_code(trial_session, command_codes[branch % 2])
+            .unwrap();
+        context
+            .policy_cp_hash(
+                trial_session,
+                &Digest::try_from(vec![branch as u8; 32]).unwrap(),
+            )
+            .unwrap();
+        tree.execute(&mut context, trial_session, branch)
+            .expect("Failed to execute the PolicyOR tree");
+        assert_eq!(
+            &context.policy_get_digest(trial_session).unwrap(),
+            tree.digest()
+        );
+        flush_trial_session(&mut context, trial_session);
+    }
+}
+
+#[test]
+fn signed_policy_authorize() {
+    let mut context = create_ctx_with_session();
+    let key_handle = context
+        .create_primary(Hierarchy::Owner, &signing_key_pub(), None, None, None, None)
+        .expect("Failed to create signing key")
+        .key_handle;
+    let (_, key_name, _) = context.read_public(key_handle).unwrap();
+
+    // The authority approves the policy only requiring the authorization value.
+    let mut approved_calculator = PolicyCalculator::new(HashingAlgorithm::Sha256).unwrap();
+    approved_calculator.policy_auth_value().unwrap();
+    let approved_policy = approved_calculator.digest().unwrap();
+    let policy_ref = Nonce::try_from(vec![1, 2, 3, 4]).unwrap();
+    let signed_policy = SignedPolicy::sign(
+        &mut context,
+        key_handle,
+        &approved_policy,
+        &policy_ref,
+        SignatureScheme::RsaSsa(HashScheme::new(HashingAlgorithm::Sha256)),
+    )
+    .expect("Failed to sign the policy");
+    context.flush_context(key_handle.into()).unwrap();
+
+    let serialized = serde_json::to_string(&signed_policy).unwrap();
+    let mut signed_policy: SignedPolicy = serde_json::from_str(&serialized).unwrap();
+    assert_eq!(signed_policy.approved_policy().unwrap(), approved_policy);
+    assert_eq!(signed_policy.policy_ref().unwrap(), policy_ref);
+
+    let mut expected = PolicyCalculator::new(HashingAlgorithm::Sha256).unwrap();
+    expected.policy_authorize(&policy_ref, &key_name).unwrap();
+
+    // The device satisfies the approved policy and proves the approval.
+    context.clear_sessions();
+    let policy_session = start_policy_session(&mut context);
+    context.policy_auth_value(policy_session).unwrap();
+    signed_policy
+        .authorize(&mut context, policy_session)
+        .expect("Failed to authorize the policy");
+    assert_eq!(
+        context.policy_get_digest(policy_session).unwrap(),
+        expected.digest().unwrap()
+    );
+
+    // The document also serves as authorizer of a policy tree.
+    let tree = Policy::And(vec![
+        Policy::AuthValue,
+        Policy::Authorize {
+            key_name: key_name.clone(),
+            policy_ref: policy_ref.clone(),
+        },
+    ]);
+    assert_eq!(
+        tree.digest(HashingAlgorithm::Sha256).unwrap(),
+        expected.digest().unwrap()
+    );
+    tree.execute(
+        &mut context,
+        policy_session,
+        HashingAlgorithm::Sha256,
+        None,
+        &mut signed_policy,
+    )
+    .expect("Failed to execute the policy");
+    assert_eq!(
+        context.policy_get_digest(policy_session).unwrap(),
+        expected.digest().unwrap()
+    );
+
+    // Policies which were not approved are rejected.
+    let tree = Policy::And(vec![
+        Policy::CommandCode(CommandCode::Unseal),
+        Policy::Authorize {
+            key_name: key_name.clone(),
+            policy_ref: policy_ref.clone(),
+        },
+    ]);
+    assert_eq!(
+        tree.execute(
+            &mut context,
+            policy_session,
+            HashingAlgorithm::Sha256,
+            None,
+            &mut signed_policy,
+        )
+        .unwrap_err(),
+        Error::WrapperError(WrapperErrorKind::InconsistentParams)
+    );
+}
@@ -0,0 +1,185 @@
+// Copyright 2021 Contributors to the Parsec project.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::convert::TryFrom;
+use std::io::Write;
+use tss_esapi::{
+    abstraction::sequence::{EventSequence, HashSequence, HmacSequence},
+    attributes::ObjectAttributesBuilder,
+    handles::PcrHandle,
+    interface_types::{
+        algorithm::{HashingAlgorithm, PublicAlgorithm},
+        resource_handles::Hierarchy,
+    },
+    structures::{
+        Digest, KeyedHashScheme, MaxBuffer, PcrSelectionListBuilder, PcrSlot, PublicBuilder,
+        PublicKeyedHashParameters, SensitiveData,
+    },
+};
+
+mod common;
+use common::create_ctx_with_session;
+
+#[test]
+fn hash_sequence_matches_hash() {
+    let mut context = create_ctx_with_session();
+    let data = vec![1, 2, 3, 4, 5, 6, 7, 8];
+
+    let mut sequence = HashSequence::new(&mut context, HashingAlgorithm::Sha256).unwrap();
+    sequence.write_all(&data[..3]).unwrap();
+    sequence.write_all(&data[3..]).unwrap();
+    let (digest, _) = sequence.finish(Hierarchy::Null).unwrap();
+
+    let (expected, _) = context
+        .hash(
+            &MaxBuffer::try_from(data).unwrap(),
+            HashingAlgorithm::Sha256,
+            Hierarchy::Null,
+        )
+        .unwrap();
+    assert_eq!(digest, expected);
+}
+
+#[test]
+fn hash_sequence_large_input() {
+    let mut context = create_ctx_with_session();
+    let data = vec![0x5A; 3 * 1024 + 17];
+    assert!(data.len() > MaxBuffer::MAX_SIZE);
+
+    // SHA-256 of the data, computed independently of the TPM.
+    let expected = Digest::try_from(vec![
+        0x51, 0x77, 0xe3, 0x7e, 0x29, 0xbe, 0x89, 0x19, 0x5e, 0x08, 0x6c, 0xbd, 0x98, 0x92, 0xbf,
+        0x06, 0x6c, 0x8f, 0x71, 0xb5, 0xb1, 0xdb, 0xe7, 0x87, 0xe4, 0xd0, 0x64, 0x93, 0x5a, 0xe6,
+        0x0d, 0xea,
+    ])
+    .unwrap();
+
+    let mut sequence = HashSequence::new(&mut context, HashingAlgorithm::Sha256).unwrap();
+    sequence.write_all(&data).unwrap();
+    let (digest, _) = sequence.finish(Hierarchy::Null).unwrap();
+    assert_eq!(digest, expected);
+
+    let mut sequence = HashSequence::new(&mut context, HashingAlgorithm::Sha256).unwrap();
+    let _ = std::io::copy(&mut data.as_slice(), &mut sequence).unwrap();
+    let (digest, _) = sequence.finish(Hierarchy::Null).unwrap();
+    assert_eq!(digest, expected);
+}
+
+#[test]
+fn hash_sequence_dropped() {
+    let mut context = create_ctx_with_session();
+
+    let mut sequence = HashSequence::new(&mut context, HashingAlgorithm::Sha256).unwrap();
+    sequence.write_all(&[1, 2, 3]).unwrap();
+    drop(sequence);
+
+    // The abandoned sequence should have been flushed.
+    let sequence = HashSequence::new(&mut context, HashingAlgorithm::Sha256).unwrap();
+    let _ = sequence.finish(Hierarchy::Null).unwrap();
+}
+
+#[test]
+fn hmac_sequence_large_input() {
+    let mut context = create_ctx_with_session();
+
+    let object_attributes = ObjectAttributesBuilder::new()
+        .with_sign_encrypt(true)
+        .with_sensitive_data_origin(false)
+        .with_user_with_auth(true)
+        .build()
+        .expect("Failed to build object attributes");
+
+    let key_pub = PublicBuilder::new()
+        .with_public_algorithm(PublicAlgorithm::KeyedHash)
+        .with_name_hashing_algorithm(HashingAlgorithm::Sha256)
+        .with_object_attributes(object_attributes)
+        .with_keyed_hash_parameters(PublicKeyedHashParameters::new(
+            KeyedHashScheme::HMAC_SHA_256,
+        ))
+        .with_keyed_hash_unique_identifier(&Default::default())
+        .build()
+        .expect("Failed to build public structure for key");
+
+    let key_value = SensitiveData::try_from((1..=32).collect::<Vec<u8>>()).unwrap();
+    let key = context
+        .create_primary(
+            Hierarchy::Owner,
+            &key_pub,
+            None,
+            Some(&key_value),
+            None,
+            None,
+        )
+        .unwrap();
+    let data = vec![0xA5; 2 * 1024];
+    assert!(data.len() > MaxBuffer::MAX_SIZE);
+
+    // HMAC-SHA256 of the data with the key value, computed independently
+    // of the TPM.
+    let expected = Digest::try_from(vec![
+        0x49, 0x09, 0xad, 0x75, 0x72, 0xc0, 0xed, 0x92, 0x80, 0xb3, 0xe3, 0xee, 0x2e, 0x3f, 0xfe,
+        0x67, 0xb1, 0xcb, 0xa7, 0xfb, 0x7c, 0x4c, 0x2a, 0xae, 0x0a, 0x28, 0x5e, 0x2d, 0x11, 0xfb,
+        0x7f, 0x8e,
+    ])
+    .unwrap();
+
+    let mut sequence = HmacSequence::new(
+        &mut context,
+        key.key_handle.into(),
+        HashingAlgorithm::Sha256,
+    )
+    .unwrap();
+    sequence.write_all(&data).unwrap();
+    let digest = sequence.finish().unwrap();
+    assert_eq!(digest, expected);
+
+    let mut sequence = HmacSequence::new(
+        &mut context,
+        key.key_handle.into(),
+        HashingAlgorithm::Sha256,
+    )
+    .unwrap();
+    for chunk in data.chunks(100) {
+        sequence.write_all(chunk).unwrap();
+    }
+    let digest = sequence.finish().unwrap();
+    assert_eq!(digest, expected);
+}
+
+#[test]
+fn event_sequence_extends_pcr() {
+    let mut context = create_ctx_with_session();
+    let pcr_session = context.sessions().0;
+    context.execute_with_session(pcr_session, |ctx| ctx.pcr_reset(PcrHandle::Pcr16).unwrap());
+
+    let data = vec![0x3C; 2 * MaxBuffer::MAX_SIZE + 1];
+    let mut sequence = EventSequence::new(&mut context).unwrap();
+    let _ = std::io::copy(&mut data.as_slice(), &mut sequence).unwrap();
+    let digests = sequence.finish(PcrHandle::Pcr16).unwrap();
+
+    let mut sequence = HashSequence::new(&mut context, HashingAlgorithm::Sha256).unwrap();
+    sequence.write_all(&data).unwrap();
+    let (expected, _) = sequence.finish(Hierarchy::Null).unwrap();
+    assert_eq!(digests.get(HashingAlgorithm::Sha256), Some(&expected));
+
+    // The PCR holds the extension of the zero value with the measurement.
+    let pcr_selection_list = PcrSelectionListBuilder::new()
+        .with_selection(HashingAlgorithm::Sha256, &[PcrSlot::Slot16])
+        .build();
+    let (_, _, pcr_data) = context
+        .execute_without_session(|ctx| ctx.pcr_read(&pcr_selection_list))
+        .unwrap();
+    let pcr_value = pcr_data
+        .pcr_bank(HashingAlgorithm::Sha256)
+        .unwrap()
+        .pcr_value(PcrSlot::Slot16)
+        .unwrap()
+        .clone();
+
+    let mut extension = vec![0; 32];
+    extension.extend_from_slice(expected.value());
+    let mut sequence = HashSequence::new(&mut context, HashingAlgorithm::Sha256).unwrap();
+    sequence.write_all(&extension).unwrap();
+    let (expected_pcr_value, _) = sequence.finish(Hierarchy::Null).unwrap();
+    assert_eq!(pcr_value, expected_pcr_value);
+}
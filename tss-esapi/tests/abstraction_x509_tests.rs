@@ -0,0 +1,187 @@
+// Copyright 2021 Contributors to the Parsec project.
+// SPDX-License-Identifier: Apache-2.0
+use std::convert::TryFrom;
+use std::time::{Duration, UNIX_EPOCH};
+use tss_esapi::{
+    abstraction::x509::{
+        assemble_certificate, DistinguishedName, Extension, KeyUsage, PartialCertificateBuilder,
+    },
+    interface_types::algorithm::HashingAlgorithm,
+    structures::{EccParameter, EccSignature, MaxBuffer, PublicKeyRsa, RsaSignature, Signature},
+    Error, WrapperErrorKind,
+};
+
+mod test_x509 {
+    use super::*;
+
+    const PARTIAL_CERTIFICATE: [u8; 125] = [
+        0x30, 0x7b, 0x30, 0x2f, 0x31, 0x0b, 0x30, 0x09, 0x06, 0x03, 0x55, 0x04, 0x06, 0x13, 0x02,
+        0x47, 0x42, 0x31, 0x0f, 0x30, 0x0d, 0x06, 0x03, 0x55, 0x04, 0x0a, 0x0c, 0x06, 0x50, 0x61,
+        0x72, 0x73, 0x65, 0x63, 0x31, 0x0f, 0x30, 0x0d, 0x06, 0x03, 0x55, 0x04, 0x03, 0x0c, 0x06,
+        0x49, 0x73, 0x73, 0x75, 0x65, 0x72, 0x30, 0x20, 0x17, 0x0d, 0x32, 0x31, 0x30, 0x31, 0x30,
+        0x31, 0x30, 0x30, 0x30, 0x30, 0x30, 0x30, 0x5a, 0x18, 0x0f, 0x32, 0x30, 0x35, 0x30, 0x30,
+        0x31, 0x30, 0x31, 0x30, 0x30, 0x30, 0x30, 0x30, 0x30, 0x5a, 0x30, 0x12, 0x31, 0x10, 0x30,
+        0x0e, 0x06, 0x03, 0x55, 0x04, 0x03, 0x0c, 0x07, 0x53, 0x75, 0x62, 0x6a, 0x65, 0x63, 0x74,
+        0xa3, 0x12, 0x30, 0x10, 0x30, 0x0e, 0x06, 0x03, 0x55, 0x1d, 0x0f, 0x01, 0x01, 0xff, 0x04,
+        0x04, 0x03, 0x02, 0x02, 0x84,
+    ];
+    const ADDED_TO_CERTIFICATE: [u8; 60] = [
+        0x30, 0x3a, 0xa0, 0x03, 0x02, 0x01, 0x02, 0x02, 0x04, 0x01, 0x02, 0x03, 0x04, 0x30, 0x0d,
+        0x06, 0x09, 0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x01, 0x01, 0x0b, 0x05, 0x00, 0x30, 0x1e,
+        0x30, 0x0d, 0x06, 0x09, 0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x01, 0x01, 0x01, 0x05, 0x00,
+        0x03, 0x0d, 0x00, 0x30, 0x0a, 0x02, 0x03, 0x00, 0xc1, 0x23, 0x02, 0x03, 0x01, 0x00, 0x01,
+    ];
+    const CERTIFICATE: [u8; 209] = [
+        0x30, 0x81, 0xce, 0x30, 0x81, 0xb5, 0xa0, 0x03, 0x02, 0x01, 0x02, 0x02, 0x04, 0x01, 0x02,
+        0x03, 0x04, 0x30, 0x0d, 0x06, 0x09, 0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x01, 0x01, 0x0b,
+        0x05, 0x00, 0x30, 0x2f, 0x31, 0x0b, 0x30, 0x09, 0x06, 0x03, 0x55, 0x04, 0x06, 0x13, 0x02,
+        0x47, 0x42, 0x31, 0x0f, 0x30, 0x0d, 0x06, 0x03, 0x55, 0x04, 0x0a, 0x0c, 0x06, 0x50, 0x61,
+        0x72, 0x73, 0x65, 0x63, 0x31, 0x0f, 0x30, 0x0d, 0x06, 0x03, 0x55, 0x04, 0x03, 0x0c, 0x06,
+        0x49, 0x73, 0x73, 0x75, 0x65, 0x72, 0x30, 0x20, 0x17, 0x0d, 0x32, 0x31, 0x30, 0x31, 0x30,
+        0x31, 0x30, 0x30, 0x30, 0x30, 0x30, 0x30, 0x5a, 0x18, 0x0f, 0x32, 0x30, 0x35, 0x30, 0x30,
+        0x31, 0x30, 0x31, 0x30, 0x30, 0x30, 0x30, 0x30, 0x30, 0x5a, 0x30, 0x12, 0x31, 0x10, 0x30,
+        0x0e, 0x06, 0x03, 0x55, 0x04, 0x03, 0x0c, 0x07, 0x53, 0x75, 0x62, 0x6a, 0x65, 0x63, 0x74,
+        0x30, 0x1e, 0x30, 0x0d, 0x06, 0x09, 0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x01, 0x01, 0x01,
+        0x05, 0x00, 0x03, 0x0d, 0x00, 0x30, 0x0a, 0x02, 0x03, 0x00, 0xc1, 0x23, 0x02, 0x03, 0x01,
+        0x00, 0x01, 0xa3, 0x12, 0x30, 0x10, 0x30, 0x0e, 0x06, 0x03, 0x55, 0x1d, 0x0f, 0x01, 0x01,
+        0xff, 0x04, 0x04, 0x03, 0x02, 0x02, 0x84, 0x30, 0x0d, 0x06, 0x09, 0x2a, 0x86, 0x48, 0x86,
+        0xf7, 0x0d, 0x01, 0x01, 0x0b, 0x05, 0x00, 0x03, 0x05, 0x00, 0xaa, 0xaa, 0xaa, 0xaa,
+    ];
+
+    fn partial_certificate_builder() -> PartialCertificateBuilder {
+        PartialCertificateBuilder::new()
+            .with_issuer(
+                DistinguishedName::new()
+                    .with_country("GB")
+                    .with_organization("Parsec")
+                    .with_common_name("Issuer"),
+            )
+            // 2021-01-01T00:00:00Z
+            .with_not_before(UNIX_EPOCH + Duration::from_secs(1_609_459_200))
+            // 2050-01-01T00:00:00Z
+            .with_not_after(UNIX_EPOCH + Duration::from_secs(2_524_608_000))
+            .with_subject(DistinguishedName::new().with_common_name("Subject"))
+            .with_extension(
+                Extension::key_usage(true, &[KeyUsage::DigitalSignature, KeyUsage::KeyCertSign])
+                    .expect("Failed to create key usage extension"),
+            )
+    }
+
+    #[test]
+    fn test_key_usage_encoding() {
+        let extension = Extension::key_usage(false, &[KeyUsage::DecipherOnly])
+            .expect("Failed to create key usage extension");
+        assert_eq!(extension.oid(), &[2, 5, 29, 15]);
+        assert!(!extension.critical());
+        assert_eq!(extension.value(), &[0x03, 0x03, 0x07, 0x00, 0x80]);
+
+        assert_eq!(
+            Extension::key_usage(false, &[]).unwrap_err(),
+            Error::WrapperError(WrapperErrorKind::ParamsMissing)
+        );
+    }
+
+    #[test]
+    fn test_build_partial_certificate() {
+        let partial_certificate = partial_certificate_builder()
+            .build()
+            .expect("Failed to build partial certificate");
+        assert_eq!(partial_certificate.value(), &PARTIAL_CERTIFICATE[..]);
+    }
+
+    #[test]
+    fn test_build_partial_certificate_missing_fields() {
+        assert_eq!(
+            PartialCertificateBuilder::new()
+                .with_subject(DistinguishedName::new().with_common_name("Subject"))
+                .build()
+                .unwrap_err(),
+            Error::WrapperError(WrapperErrorKind::ParamsMissing)
+        );
+        assert_eq!(
+            PartialCertificateBuilder::new()
+                .with_issuer(DistinguishedName::new().with_common_name("Issuer"))
+                .with_subject(DistinguishedName::new().with_common_name("Subject"))
+                .with_not_before(UNIX_EPOCH)
+                .with_not_after(UNIX_EPOCH)
+                .build()
+                .unwrap_err(),
+            Error::WrapperError(WrapperErrorKind::ParamsMissing)
+        );
+    }
+
+    #[test]
+    fn test_build_partial_certificate_invalid_validity() {
+        assert_eq!(
+            partial_certificate_builder()
+                .with_not_after(UNIX_EPOCH)
+                .build()
+                .unwrap_err(),
+            Error::WrapperError(WrapperErrorKind::InconsistentParams)
+        );
+    }
+
+    #[test]
+    fn test_assemble_certificate() {
+        let signature = Signature::RsaSsa(
+            RsaSignature::create(
+                HashingAlgorithm::Sha256,
+                PublicKeyRsa::try_from(vec![0xaa; 4]).unwrap(),
+            )
+            .unwrap(),
+        );
+        let certificate = assemble_certificate(
+            &MaxBuffer::try_from(PARTIAL_CERTIFICATE.to_vec()).unwrap(),
+            &MaxBuffer::try_from(ADDED_TO_CERTIFICATE.to_vec()).unwrap(),
+            &signature,
+        )
+        .expect("Failed to assemble certificate");
+        assert_eq!(certificate, CERTIFICATE.to_vec());
+    }
+
+    #[test]
+    fn test_assemble_certificate_ecdsa_signature() {
+        let signature = Signature::EcDsa(
+            EccSignature::create(
+                HashingAlgorithm::Sha256,
+                EccParameter::try_from(vec![0x00, 0x00, 0x80, 0x01]).unwrap(),
+                EccParameter::try_from(vec![0x7f, 0x02]).unwrap(),
+            )
+            .unwrap(),
+        );
+        let certificate = assemble_certificate(
+            &MaxBuffer::try_from(PARTIAL_CERTIFICATE.to_vec()).unwrap(),
+            &MaxBuffer::try_from(ADDED_TO_CERTIFICATE.to_vec()).unwrap(),
+            &signature,
+        )
+        .expect("Failed to assemble certificate");
+        // The signature value is the DER encoded sequence of r and s
+        assert!(certificate.ends_with(&[
+            0x03, 0x0c, 0x00, 0x30, 0x09, 0x02, 0x03, 0x00, 0x80, 0x01, 0x02, 0x02, 0x7f, 0x02
+        ]));
+    }
+
+    #[test]
+    fn test_assemble_certificate_inconsistent_signature_algorithm() {
+        // Both inputs without a signature algorithm
+        let added_to_certificate = [
+            0x30, 0x0a, 0xa0, 0x03, 0x02, 0x01, 0x02, 0x02, 0x01, 0x01, 0x30, 0x00,
+        ];
+        let signature = Signature::RsaSsa(
+            RsaSignature::create(
+                HashingAlgorithm::Sha256,
+                PublicKeyRsa::try_from(vec![0xaa; 4]).unwrap(),
+            )
+            .unwrap(),
+        );
+        assert_eq!(
+            assemble_certificate(
+                &MaxBuffer::try_from(PARTIAL_CERTIFICATE.to_vec()).unwrap(),
+                &MaxBuffer::try_from(added_to_certificate.to_vec()).unwrap(),
+                &signature,
+            )
+            .unwrap_err(),
+            Error::WrapperError(WrapperErrorKind::InconsistentParams)
+        );
+    }
+}
@@ -45,4 +45,4 @@ RUST_BACKTRACE=1 cargo build --features generate-bindings
 #################
 # Run the tests #
 #################
-TEST_TCTI=tabrmd:bus_type=session RUST_BACKTRACE=1 RUST_LOG=info cargo test --features generate-bindings --  --test-threads=1 --nocapture
+TEST_TCTI=tabrmd:bus_type=session RUST_BACKTRACE=1 RUST_LOG=info cargo test --features generate-bindings,software-crypto --  --test-threads=1 --nocapture
@@ -15,13 +15,14 @@ use tss_esapi::{
         algorithm::{HashingAlgorithm, PublicAlgorithm, RsaSchemeAlgorithm},
         key_bits::RsaKeyBits,
         resource_handles::Hierarchy,
-        session_handles::PolicySession,
+        session_handles::{AuthSession, PolicySession},
     },
     structures::{
         Digest, KeyedHashScheme, MaxBuffer, PcrSelectionListBuilder, PcrSlot, Public,
         PublicBuilder, PublicKeyedHashParameters, RsaExponent, RsaScheme, SymmetricDefinition,
     },
     tcti_ldr::TctiNameConf,
+    tss2_esys::TPM2_CC,
     utils, Context,
 };
 
@@ -286,6 +287,50 @@ pub fn get_pcr_policy_digest(
     }
 }
 
+/// Starts a trial or policy session restricted to the given command code
+/// and returns it together with its policy digest.
+#[allow(dead_code)]
+pub fn get_command_code_policy(
+    context: &mut Context,
+    session_type: SessionType,
+    command_code: TPM2_CC,
+) -> (Digest, AuthSession) {
+    context.execute_without_session(|ctx| {
+        let policy_auth_session = ctx
+            .start_auth_session(
+                None,
+                None,
+                None,
+                session_type,
+                SymmetricDefinition::AES_256_CFB,
+                HashingAlgorithm::Sha256,
+            )
+            .expect("Start auth session failed")
+            .expect("Start auth session returned a NONE handle");
+        let (policy_auth_session_attributes, policy_auth_session_attributes_mask) =
+            SessionAttributesBuilder::new()
+                .with_decrypt(true)
+                .with_encrypt(true)
+                .build();
+        ctx.tr_sess_set_attributes(
+            policy_auth_session,
+            policy_auth_session_attributes,
+            policy_auth_session_attributes_mask,
+        )
+        .expect("tr_sess_set_attributes call failed");
+
+        let policy_session = PolicySession::try_from(policy_auth_session)
+            .expect("Failed to convert auth session into policy session");
+        ctx.policy_command_code(policy_session, command_code)
+            .expect("Failed to call policy_command_code");
+        let digest = ctx
+            .policy_get_digest(policy_session)
+            .expect("Failed to call policy_get_digest");
+
+        (digest, policy_auth_session)
+    })
+}
+
 #[allow(dead_code)]
 pub fn create_public_sealed_object() -> Public {
     let object_attributes = ObjectAttributesBuilder::new()
@@ -0,0 +1,49 @@
+// Copyright 2021 Contributors to the Parsec project.
+// SPDX-License-Identifier: Apache-2.0
+mod test_arithmetic_operation {
+    use std::convert::TryFrom;
+    use tss_esapi::{
+        constants::{
+            tss::{
+                TPM2_EO_BITCLEAR, TPM2_EO_BITSET, TPM2_EO_EQ, TPM2_EO_NEQ, TPM2_EO_SIGNED_GE,
+                TPM2_EO_SIGNED_GT, TPM2_EO_SIGNED_LE, TPM2_EO_SIGNED_LT, TPM2_EO_UNSIGNED_GE,
+                TPM2_EO_UNSIGNED_GT, TPM2_EO_UNSIGNED_LE, TPM2_EO_UNSIGNED_LT,
+            },
+            ArithmeticOperation,
+        },
+        tss2_esys::TPM2_EO,
+    };
+    macro_rules! test_conversion {
+        ($tpm_eo:ident, $operation:ident) => {
+            assert_eq!($tpm_eo, TPM2_EO::from(ArithmeticOperation::$operation));
+            assert_eq!(
+                ArithmeticOperation::$operation,
+                ArithmeticOperation::try_from($tpm_eo).expect(&format!(
+                    "Failed to convert tpm_eo for {}",
+                    stringify!($tpm_eo)
+                ))
+            );
+        };
+    }
+    #[test]
+    fn test_arithmetic_operation_conversion() {
+        test_conversion!(TPM2_EO_EQ, Eq);
+        test_conversion!(TPM2_EO_NEQ, Neq);
+        test_conversion!(TPM2_EO_SIGNED_GT, SignedGt);
+        test_conversion!(TPM2_EO_UNSIGNED_GT, UnsignedGt);
+        test_conversion!(TPM2_EO_SIGNED_LT, SignedLt);
+        test_conversion!(TPM2_EO_UNSIGNED_LT, UnsignedLt);
+        test_conversion!(TPM2_EO_SIGNED_GE, SignedGe);
+        test_conversion!(TPM2_EO_UNSIGNED_GE, UnsignedGe);
+        test_conversion!(TPM2_EO_SIGNED_LE, SignedLe);
+        test_conversion!(TPM2_EO_UNSIGNED_LE, UnsignedLe);
+        test_conversion!(TPM2_EO_BITSET, Bitset);
+        test_conversion!(TPM2_EO_BITCLEAR, Bitclear);
+    }
+
+    #[test]
+    fn test_invalid_arithmetic_operation() {
+        const INVALID_VALUE: TPM2_EO = 0x000C;
+        let _ = ArithmeticOperation::try_from(INVALID_VALUE).unwrap_err();
+    }
+}
@@ -104,3 +104,111 @@ mod test_rsa_encrypt_decrypt {
         assert_eq!(z_point.x().value(), param.x().value());
     }
 }
+
+mod test_zgen_2phase {
+    use crate::common::create_ctx_with_session;
+    use std::convert::TryFrom;
+    use tss_esapi::{
+        attributes::ObjectAttributesBuilder,
+        interface_types::{
+            algorithm::{EccSchemeAlgorithm, HashingAlgorithm, PublicAlgorithm},
+            ecc::EccCurve,
+            resource_handles::Hierarchy,
+        },
+        structures::{
+            EccParameter, EccPoint, EccScheme, HashScheme, KeyDerivationFunctionScheme, Public,
+            PublicBuilder, PublicEccParametersBuilder,
+        },
+    };
+
+    fn key_exchange_public(unique: u8) -> Public {
+        let ecc_parms = PublicEccParametersBuilder::new()
+            .with_ecc_scheme(EccScheme::EcDh(HashScheme::new(HashingAlgorithm::Sha256)))
+            .with_curve(EccCurve::NistP256)
+            .with_is_signing_key(false)
+            .with_is_decryption_key(true)
+            .with_restricted(false)
+            .with_key_derivation_function_scheme(KeyDerivationFunctionScheme::Null)
+            .build()
+            .unwrap();
+
+        let object_attributes = ObjectAttributesBuilder::new()
+            .with_fixed_tpm(true)
+            .with_fixed_parent(true)
+            .with_sensitive_data_origin(true)
+            .with_user_with_auth(true)
+            .with_decrypt(true)
+            .with_sign_encrypt(false)
+            .with_restricted(false)
+            .build()
+            .unwrap();
+
+        PublicBuilder::new()
+            .with_public_algorithm(PublicAlgorithm::Ecc)
+            .with_name_hashing_algorithm(HashingAlgorithm::Sha256)
+            .with_object_attributes(object_attributes)
+            .with_ecc_parameters(ecc_parms)
+            .with_ecc_unique_identifier(&EccPoint::new(
+                EccParameter::try_from(vec![unique; 32]).unwrap(),
+                Default::default(),
+            ))
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_zgen_2phase() {
+        let mut context = create_ctx_with_session();
+
+        let mut parties = Vec::new();
+        for unique in 1..=2 {
+            let result = context
+                .create_primary(
+                    Hierarchy::Owner,
+                    &key_exchange_public(unique),
+                    None,
+                    None,
+                    None,
+                    None,
+                )
+                .unwrap();
+            let static_point = match result.out_public {
+                Public::Ecc { unique, .. } => unique,
+                _ => panic!("Expected an ECC public area"),
+            };
+            let (ephemeral_point, counter) = context.ec_ephemeral(EccCurve::NistP256).unwrap();
+            parties.push((result.key_handle, static_point, ephemeral_point, counter));
+        }
+
+        let (key_a, qs_a, qe_a, counter_a) = parties[0].clone();
+        let (key_b, qs_b, qe_b, counter_b) = parties[1].clone();
+        let (z1_a, z2_a) = context
+            .zgen_2phase(key_a, qs_b, qe_b, EccSchemeAlgorithm::EcDh, counter_a)
+            .unwrap();
+        let (z1_b, z2_b) = context
+            .zgen_2phase(key_b, qs_a, qe_a, EccSchemeAlgorithm::EcDh, counter_b)
+            .unwrap();
+
+        assert_eq!(z1_a.x().value(), z1_b.x().value());
+        assert_eq!(z2_a.x().value(), z2_b.x().value());
+    }
+}
+
+mod test_ecc_parameters {
+    use crate::common::create_ctx_without_session;
+    use tss_esapi::interface_types::ecc::EccCurve;
+
+    #[test]
+    fn test_ecc_parameters() {
+        let mut context = create_ctx_without_session();
+
+        let parameters = context.ecc_parameters(EccCurve::NistP256).unwrap();
+        assert_eq!(parameters.curve(), EccCurve::NistP256);
+        assert_eq!(parameters.key_size(), 256);
+        assert_eq!(parameters.p().len(), 32);
+        assert_eq!(parameters.g().x().len(), 32);
+        assert_eq!(parameters.g().y().len(), 32);
+        assert_eq!(parameters.n().len(), 32);
+        assert_eq!(parameters.h().value(), &[1]);
+    }
+}
@@ -1,12 +1,141 @@
 // Copyright 2021 Contributors to the Parsec project.
 // SPDX-License-Identifier: Apache-2.0
+mod test_certify {
+    use crate::common::{create_ctx_with_session, decryption_key_pub, signing_key_pub};
+    use std::convert::TryFrom;
+    use tss_esapi::{
+        constants::StructureTag,
+        handles::ObjectHandle,
+        interface_types::{
+            algorithm::HashingAlgorithm, resource_handles::Hierarchy, session_handles::AuthSession,
+        },
+        structures::{AttestInfo, Data, HashScheme, Signature, SignatureScheme},
+    };
+
+    #[test]
+    fn certify() {
+        let mut context = create_ctx_with_session();
+        let qualifying_data = vec![0xff; 16];
+
+        let signing_key_handle = context
+            .create_primary(Hierarchy::Owner, &signing_key_pub(), None, None, None, None)
+            .unwrap()
+            .key_handle;
+        let object_handle = context
+            .create_primary(
+                Hierarchy::Owner,
+                &decryption_key_pub(),
+                None,
+                None,
+                None,
+                None,
+            )
+            .unwrap()
+            .key_handle;
+        let (_, object_name, object_qualified_name) = context
+            .read_public(object_handle)
+            .expect("Call to read_public failed");
+
+        let (attest, signature) = context
+            .execute_with_sessions(
+                (
+                    Some(AuthSession::Password),
+                    Some(AuthSession::Password),
+                    None,
+                ),
+                |ctx| {
+                    ctx.certify(
+                        ObjectHandle::from(object_handle),
+                        signing_key_handle,
+                        Data::try_from(qualifying_data.clone()).unwrap(),
+                        SignatureScheme::RsaSsa(HashScheme::new(HashingAlgorithm::Sha256)),
+                    )
+                },
+            )
+            .expect("Failed to certify object");
+
+        assert_eq!(attest.attestation_type(), StructureTag::AttestCertify);
+        assert_eq!(attest.extra_data().value(), &qualifying_data[..]);
+        match attest.attested() {
+            AttestInfo::Certify(certify_info) => {
+                assert_eq!(certify_info.name(), &object_name);
+                assert_eq!(certify_info.qualified_name(), &object_qualified_name);
+            }
+            _ => panic!("Attested data was not a certify info"),
+        }
+        match signature {
+            Signature::RsaSsa(_) => {}
+            _ => panic!("Signature was not an RSA SSA signature"),
+        }
+    }
+}
+
+mod test_certify_creation {
+    use crate::common::{create_ctx_with_session, decryption_key_pub, signing_key_pub};
+    use std::convert::TryFrom;
+    use tss_esapi::{
+        constants::StructureTag,
+        handles::ObjectHandle,
+        interface_types::resource_handles::Hierarchy,
+        structures::{AttestInfo, Data, SignatureScheme},
+    };
+
+    #[test]
+    fn certify_creation() {
+        let mut context = create_ctx_with_session();
+        let qualifying_data = vec![0xff; 16];
+
+        let signing_key_handle = context
+            .create_primary(Hierarchy::Owner, &signing_key_pub(), None, None, None, None)
+            .unwrap()
+            .key_handle;
+        let create_primary_result = context
+            .create_primary(
+                Hierarchy::Owner,
+                &decryption_key_pub(),
+                None,
+                None,
+                None,
+                None,
+            )
+            .unwrap();
+        let (_, object_name, _) = context
+            .read_public(create_primary_result.key_handle)
+            .expect("Call to read_public failed");
+
+        let (attest, _signature) = context
+            .certify_creation(
+                signing_key_handle,
+                ObjectHandle::from(create_primary_result.key_handle),
+                Data::try_from(qualifying_data.clone()).unwrap(),
+                create_primary_result.creation_hash.clone(),
+                SignatureScheme::Null,
+                create_primary_result.creation_ticket,
+            )
+            .expect("Failed to certify creation");
+
+        assert_eq!(attest.attestation_type(), StructureTag::AttestCreation);
+        assert_eq!(attest.extra_data().value(), &qualifying_data[..]);
+        match attest.attested() {
+            AttestInfo::Creation(creation_info) => {
+                assert_eq!(creation_info.object_name(), &object_name);
+                assert_eq!(
+                    creation_info.creation_hash(),
+                    &create_primary_result.creation_hash
+                );
+            }
+            _ => panic!("Attested data was not a creation info"),
+        }
+    }
+}
+
 mod test_quote {
     use crate::common::{create_ctx_with_session, signing_key_pub};
     use std::convert::TryFrom;
     use tss_esapi::{
-        constants::tss::TPM2_ALG_NULL,
+        constants::{tss::TPM2_ALG_NULL, StructureTag},
         interface_types::{algorithm::HashingAlgorithm, resource_handles::Hierarchy},
-        structures::{Data, PcrSelectionListBuilder, PcrSlot},
+        structures::{Attest, AttestInfo, Data, PcrSelectionListBuilder, PcrSlot},
         tss2_esys::TPMT_SIG_SCHEME,
     };
 
@@ -29,14 +158,271 @@ mod test_quote {
             .unwrap()
             .key_handle;
 
-        let res = context
+        let (attest, _signature) = context
             .quote(
                 key_handle,
-                &Data::try_from(qualifying_data).unwrap(),
+                &Data::try_from(qualifying_data.clone()).unwrap(),
                 scheme,
-                pcr_selection_list,
+                pcr_selection_list.clone(),
             )
             .expect("Failed to get a quote");
-        assert!(res.0.size != 0);
+        assert_eq!(attest.attestation_type(), StructureTag::AttestQuote);
+        assert_eq!(attest.extra_data().value(), &qualifying_data[..]);
+        match attest.attested() {
+            AttestInfo::Quote(quote_info) => {
+                assert_eq!(quote_info.pcr_selection(), &pcr_selection_list);
+                assert!(!quote_info.pcr_digest().is_empty());
+            }
+            _ => panic!("Attested data was not a quote"),
+        }
+        let marshalled_attest = attest.marshall().expect("Failed to marshall attest");
+        assert_eq!(
+            attest,
+            Attest::unmarshall(&marshalled_attest).expect("Failed to unmarshall attest")
+        );
+    }
+}
+
+mod test_get_session_audit_digest {
+    use crate::common::{create_ctx_without_session, signing_key_pub};
+    use std::convert::TryFrom;
+    use tss_esapi::{
+        attributes::SessionAttributesBuilder,
+        constants::{SessionType, StructureTag},
+        interface_types::{
+            algorithm::HashingAlgorithm,
+            resource_handles::{Hierarchy, Privacy},
+            session_handles::{AuthSession, HmacSession},
+        },
+        structures::{AttestInfo, Data, SignatureScheme, SymmetricDefinition},
+    };
+
+    #[test]
+    fn get_session_audit_digest() {
+        let mut context = create_ctx_without_session();
+        let signing_key_handle = context
+            .execute_with_session(Some(AuthSession::Password), |ctx| {
+                ctx.create_primary(Hierarchy::Owner, &signing_key_pub(), None, None, None, None)
+            })
+            .unwrap()
+            .key_handle;
+
+        let audit_session = context
+            .start_auth_session(
+                None,
+                None,
+                None,
+                SessionType::Hmac,
+                SymmetricDefinition::Null,
+                HashingAlgorithm::Sha256,
+            )
+            .expect("Call to start_auth_session failed")
+            .expect("The auth session returned was NONE");
+        let (session_attributes, session_attributes_mask) = SessionAttributesBuilder::new()
+            .with_continue_session(true)
+            .with_audit(true)
+            .build();
+        context
+            .tr_sess_set_attributes(audit_session, session_attributes, session_attributes_mask)
+            .expect("Call to tr_sess_set_attributes failed");
+
+        // Audit a command with the session
+        let _ = context
+            .execute_with_session(Some(audit_session), |ctx| ctx.get_random(16))
+            .expect("Call to get_random failed");
+
+        let (attest, _signature) = context
+            .execute_with_sessions(
+                (
+                    Some(AuthSession::Password),
+                    Some(AuthSession::Password),
+                    None,
+                ),
+                |ctx| {
+                    ctx.get_session_audit_digest(
+                        Privacy::Endorsement,
+                        signing_key_handle,
+                        HmacSession::try_from(audit_session).unwrap(),
+                        Data::default(),
+                        SignatureScheme::Null,
+                    )
+                },
+            )
+            .expect("Failed to get session audit digest");
+
+        assert_eq!(attest.attestation_type(), StructureTag::AttestSessionAudit);
+        match attest.attested() {
+            AttestInfo::SessionAudit(session_audit_info) => {
+                assert!(!session_audit_info.session_digest().is_empty());
+            }
+            _ => panic!("Attested data was not a session audit info"),
+        }
+    }
+}
+
+mod test_get_command_audit_digest {
+    use crate::common::{create_ctx_without_session, signing_key_pub};
+    use tss_esapi::{
+        constants::StructureTag,
+        interface_types::{
+            resource_handles::{Hierarchy, Privacy},
+            session_handles::AuthSession,
+        },
+        structures::{AttestInfo, Data, SignatureScheme},
+    };
+
+    #[test]
+    fn get_command_audit_digest() {
+        let mut context = create_ctx_without_session();
+        let signing_key_handle = context
+            .execute_with_session(Some(AuthSession::Password), |ctx| {
+                ctx.create_primary(Hierarchy::Owner, &signing_key_pub(), None, None, None, None)
+            })
+            .unwrap()
+            .key_handle;
+
+        let (attest, _signature) = context
+            .execute_with_sessions(
+                (
+                    Some(AuthSession::Password),
+                    Some(AuthSession::Password),
+                    None,
+                ),
+                |ctx| {
+                    ctx.get_command_audit_digest(
+                        Privacy::Endorsement,
+                        signing_key_handle,
+                        Data::default(),
+                        SignatureScheme::Null,
+                    )
+                },
+            )
+            .expect("Failed to get command audit digest");
+
+        assert_eq!(attest.attestation_type(), StructureTag::AttestCommandAudit);
+        match attest.attested() {
+            AttestInfo::CommandAudit(_) => {}
+            _ => panic!("Attested data was not a command audit info"),
+        }
+    }
+}
+
+mod test_get_time {
+    use crate::common::{create_ctx_without_session, signing_key_pub};
+    use std::convert::TryFrom;
+    use tss_esapi::{
+        constants::StructureTag,
+        interface_types::{
+            algorithm::HashingAlgorithm,
+            resource_handles::{Hierarchy, Privacy},
+            session_handles::AuthSession,
+        },
+        structures::{AttestInfo, Data, HashScheme, SignatureScheme},
+    };
+
+    #[test]
+    fn get_time() {
+        let mut context = create_ctx_without_session();
+        let qualifying_data = vec![0xff; 16];
+        let signing_key_handle = context
+            .execute_with_session(Some(AuthSession::Password), |ctx| {
+                ctx.create_primary(Hierarchy::Owner, &signing_key_pub(), None, None, None, None)
+            })
+            .unwrap()
+            .key_handle;
+
+        let (attest, _signature) = context
+            .execute_with_sessions(
+                (
+                    Some(AuthSession::Password),
+                    Some(AuthSession::Password),
+                    None,
+                ),
+                |ctx| {
+                    ctx.get_time(
+                        Privacy::Endorsement,
+                        signing_key_handle,
+                        Data::try_from(qualifying_data.clone()).unwrap(),
+                        SignatureScheme::RsaSsa(HashScheme::new(HashingAlgorithm::Sha256)),
+                    )
+                },
+            )
+            .expect("Failed to get time");
+
+        assert_eq!(attest.attestation_type(), StructureTag::AttestTime);
+        assert_eq!(attest.extra_data().value(), &qualifying_data[..]);
+        match attest.attested() {
+            AttestInfo::Time(time_attest_info) => {
+                assert_eq!(
+                    time_attest_info.time_info().clock_info(),
+                    attest.clock_info()
+                );
+            }
+            _ => panic!("Attested data was not a time attest info"),
+        }
+    }
+}
+
+#[cfg(tpm2_tss_version = "3")]
+mod test_certify_x509 {
+    use crate::common::{create_ctx_without_session, signing_key_pub};
+    use std::time::{Duration, SystemTime};
+    use tss_esapi::{
+        abstraction::x509::{
+            assemble_certificate, DistinguishedName, Extension, KeyUsage, PartialCertificateBuilder,
+        },
+        handles::ObjectHandle,
+        interface_types::{
+            algorithm::HashingAlgorithm, resource_handles::Hierarchy, session_handles::AuthSession,
+        },
+        structures::{HashScheme, SignatureScheme},
+    };
+
+    #[test]
+    fn certify_x509() {
+        let mut context = create_ctx_without_session();
+        let signing_key_handle = context
+            .execute_with_session(Some(AuthSession::Password), |ctx| {
+                ctx.create_primary(Hierarchy::Owner, &signing_key_pub(), None, None, None, None)
+            })
+            .unwrap()
+            .key_handle;
+
+        let not_before = SystemTime::now();
+        let partial_certificate = PartialCertificateBuilder::new()
+            .with_issuer(DistinguishedName::new().with_common_name("Issuer"))
+            .with_not_before(not_before)
+            .with_not_after(not_before + Duration::from_secs(3600))
+            .with_subject(DistinguishedName::new().with_common_name("Subject"))
+            .with_extension(
+                Extension::key_usage(true, &[KeyUsage::DigitalSignature])
+                    .expect("Failed to create key usage extension"),
+            )
+            .build()
+            .expect("Failed to build partial certificate");
+
+        let (added_to_certificate, tbs_digest, signature) = context
+            .execute_with_sessions(
+                (
+                    Some(AuthSession::Password),
+                    Some(AuthSession::Password),
+                    None,
+                ),
+                |ctx| {
+                    ctx.certify_x509(
+                        ObjectHandle::from(signing_key_handle),
+                        signing_key_handle,
+                        SignatureScheme::RsaSsa(HashScheme::new(HashingAlgorithm::Sha256)),
+                        partial_certificate.clone(),
+                    )
+                },
+            )
+            .expect("Failed to certify object");
+        assert_eq!(tbs_digest.len(), 32);
+
+        let certificate =
+            assemble_certificate(&partial_certificate, &added_to_certificate, &signature)
+                .expect("Failed to assemble certificate");
+        assert_eq!(certificate[0], 0x30);
     }
 }
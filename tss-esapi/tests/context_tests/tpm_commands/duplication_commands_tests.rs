@@ -209,3 +209,284 @@ mod test_duplicate {
         eprintln!("D: {:?}, P: {:?}, S: {:?}", data, private, secret);
     }
 }
+
+mod test_import {
+    use crate::common::create_ctx_with_session;
+    use std::convert::TryFrom;
+    use tss_esapi::attributes::{ObjectAttributesBuilder, SessionAttributesBuilder};
+    use tss_esapi::constants::{tss::TPM2_CC_Duplicate, SessionType};
+    use tss_esapi::handles::{KeyHandle, ObjectHandle, SessionHandle};
+    use tss_esapi::interface_types::{
+        algorithm::{HashingAlgorithm, PublicAlgorithm},
+        ecc::EccCurve,
+        resource_handles::Hierarchy,
+        session_handles::PolicySession,
+    };
+    use tss_esapi::structures::{
+        Data, Digest, EccParameter, EccPoint, EccScheme, EncryptedSecret,
+        KeyDerivationFunctionScheme, Private, Public, PublicBuilder, PublicEccParametersBuilder,
+        SymmetricDefinition, SymmetricDefinitionObject,
+    };
+    use tss_esapi::Context;
+
+    fn start_policy_session(context: &mut Context, session_type: SessionType) -> PolicySession {
+        let policy_auth_session = context
+            .start_auth_session(
+                None,
+                None,
+                None,
+                session_type,
+                SymmetricDefinition::AES_256_CFB,
+                HashingAlgorithm::Sha256,
+            )
+            .expect("Start auth session failed")
+            .expect("Start auth session returned a NONE handle");
+        let (policy_auth_session_attributes, policy_auth_session_attributes_mask) =
+            SessionAttributesBuilder::new()
+                .with_decrypt(true)
+                .with_encrypt(true)
+                .build();
+        context
+            .tr_sess_set_attributes(
+                policy_auth_session,
+                policy_auth_session_attributes,
+                policy_auth_session_attributes_mask,
+            )
+            .expect("tr_sess_set_attributes call failed");
+        let policy_session = PolicySession::try_from(policy_auth_session)
+            .expect("Failed to convert auth session into policy session");
+        context
+            .policy_auth_value(policy_session)
+            .expect("Policy auth value");
+        context
+            .policy_command_code(policy_session, TPM2_CC_Duplicate)
+            .expect("Policy command code");
+        policy_session
+    }
+
+    fn duplication_policy_digest(context: &mut Context) -> Digest {
+        context.execute_without_session(|ctx| {
+            let trial_session = start_policy_session(ctx, SessionType::Trial);
+            let digest = ctx
+                .policy_get_digest(trial_session)
+                .expect("Could retrieve digest");
+            ctx.flush_context(SessionHandle::from(trial_session).into())
+                .expect("Failed to flush trial session");
+            digest
+        })
+    }
+
+    fn parent_public(unique: u8) -> Public {
+        let parent_object_attributes = ObjectAttributesBuilder::new()
+            .with_fixed_tpm(true)
+            .with_fixed_parent(true)
+            .with_sensitive_data_origin(true)
+            .with_user_with_auth(true)
+            .with_decrypt(true)
+            .with_sign_encrypt(false)
+            .with_restricted(true)
+            .build()
+            .expect("Attributes to be valid");
+
+        PublicBuilder::new()
+            .with_public_algorithm(PublicAlgorithm::Ecc)
+            .with_name_hashing_algorithm(HashingAlgorithm::Sha256)
+            .with_object_attributes(parent_object_attributes)
+            .with_ecc_parameters(
+                PublicEccParametersBuilder::new()
+                    .with_ecc_scheme(EccScheme::Null)
+                    .with_curve(EccCurve::NistP256)
+                    .with_is_signing_key(false)
+                    .with_is_decryption_key(true)
+                    .with_restricted(true)
+                    .with_symmetric(SymmetricDefinitionObject::AES_128_CFB)
+                    .with_key_derivation_function_scheme(KeyDerivationFunctionScheme::Null)
+                    .build()
+                    .expect("Params to be valid"),
+            )
+            .with_ecc_unique_identifier(&EccPoint::new(
+                EccParameter::try_from(vec![unique; 32]).unwrap(),
+                Default::default(),
+            ))
+            .build()
+            .expect("public to be valid")
+    }
+
+    fn duplicable_public(digest: &Digest) -> Public {
+        // Fixed TPM and Fixed Parent should be "false" for an object
+        // to be elligible for duplication
+        let object_attributes = ObjectAttributesBuilder::new()
+            .with_fixed_tpm(false)
+            .with_fixed_parent(false)
+            .with_sensitive_data_origin(true)
+            .with_user_with_auth(true)
+            .with_decrypt(true)
+            .with_sign_encrypt(true)
+            .with_restricted(false)
+            .build()
+            .expect("Attributes to be valid");
+
+        PublicBuilder::new()
+            .with_public_algorithm(PublicAlgorithm::Ecc)
+            .with_name_hashing_algorithm(HashingAlgorithm::Sha256)
+            .with_object_attributes(object_attributes)
+            .with_auth_policy(digest)
+            .with_ecc_parameters(
+                PublicEccParametersBuilder::new()
+                    .with_ecc_scheme(EccScheme::Null)
+                    .with_curve(EccCurve::NistP256)
+                    .with_is_signing_key(false)
+                    .with_is_decryption_key(true)
+                    .with_restricted(false)
+                    .with_key_derivation_function_scheme(KeyDerivationFunctionScheme::Null)
+                    .build()
+                    .expect("Params to be valid"),
+            )
+            .with_ecc_unique_identifier(&EccPoint::default())
+            .build()
+            .expect("public to be valid")
+    }
+
+    /// Creates a duplicable object and duplicates it for `new_parent_handle`
+    fn create_and_duplicate(
+        context: &mut Context,
+        new_parent_handle: ObjectHandle,
+        encryption_key_in: Option<Data>,
+        symmetric_alg: SymmetricDefinitionObject,
+    ) -> (Public, Data, Private, EncryptedSecret) {
+        let policy_digest = duplication_policy_digest(context);
+        let parent_handle = context
+            .create_primary(Hierarchy::Owner, &parent_public(0), None, None, None, None)
+            .unwrap()
+            .key_handle;
+        let result = context
+            .create(
+                parent_handle,
+                &duplicable_public(&policy_digest),
+                None,
+                None,
+                None,
+                None,
+            )
+            .unwrap();
+        let object_handle: ObjectHandle = context
+            .load(parent_handle, result.out_private, &result.out_public)
+            .unwrap()
+            .into();
+
+        let sessions = context.sessions();
+        context.clear_sessions();
+        let policy_session = start_policy_session(context, SessionType::Policy);
+        let (encryption_key_out, duplicate, out_sym_seed) = context
+            .execute_with_sessions((Some(policy_session.into()), None, None), |ctx| {
+                ctx.duplicate(
+                    object_handle,
+                    new_parent_handle,
+                    encryption_key_in,
+                    symmetric_alg,
+                )
+            })
+            .unwrap();
+        context
+            .flush_context(SessionHandle::from(policy_session).into())
+            .expect("Failed to flush policy session");
+        context.set_sessions(sessions);
+
+        context.flush_context(object_handle).unwrap();
+        context.flush_context(parent_handle.into()).unwrap();
+        (
+            result.out_public,
+            encryption_key_out,
+            duplicate,
+            out_sym_seed,
+        )
+    }
+
+    fn load_imported(
+        context: &mut Context,
+        new_parent_handle: KeyHandle,
+        public: &Public,
+        private: Private,
+    ) {
+        let key_handle = context
+            .load(new_parent_handle, private, public)
+            .expect("Failed to load the imported object");
+        context.flush_context(key_handle.into()).unwrap();
+    }
+
+    #[test]
+    fn test_import() {
+        let mut context = create_ctx_with_session();
+        let new_parent_handle = context
+            .create_primary(Hierarchy::Owner, &parent_public(1), None, None, None, None)
+            .unwrap()
+            .key_handle;
+
+        let (public, encryption_key, duplicate, in_sym_seed) = create_and_duplicate(
+            &mut context,
+            new_parent_handle.into(),
+            None,
+            SymmetricDefinitionObject::AES_128_CFB,
+        );
+        let private = context
+            .import(
+                new_parent_handle.into(),
+                Some(encryption_key),
+                public.clone(),
+                duplicate,
+                in_sym_seed,
+                SymmetricDefinitionObject::AES_128_CFB,
+            )
+            .expect("Failed to import the duplicated object");
+        load_imported(&mut context, new_parent_handle, &public, private);
+    }
+
+    #[test]
+    fn test_rewrap() {
+        let mut context = create_ctx_with_session();
+        let intermediate_parent_handle = context
+            .create_primary(Hierarchy::Owner, &parent_public(2), None, None, None, None)
+            .unwrap()
+            .key_handle;
+        let new_parent_handle = context
+            .create_primary(Hierarchy::Owner, &parent_public(3), None, None, None, None)
+            .unwrap()
+            .key_handle;
+
+        let (public, _, duplicate, in_sym_seed) = create_and_duplicate(
+            &mut context,
+            intermediate_parent_handle.into(),
+            None,
+            SymmetricDefinitionObject::Null,
+        );
+        let name = {
+            let key_handle = context
+                .load_external_public(&public, Hierarchy::Owner)
+                .unwrap();
+            let (_, name, _) = context.read_public(key_handle).unwrap();
+            context.flush_context(key_handle.into()).unwrap();
+            name
+        };
+
+        let (duplicate, in_sym_seed) = context
+            .rewrap(
+                intermediate_parent_handle.into(),
+                new_parent_handle.into(),
+                duplicate,
+                name,
+                in_sym_seed,
+            )
+            .expect("Failed to rewrap the duplicated object");
+        let private = context
+            .import(
+                new_parent_handle.into(),
+                None,
+                public.clone(),
+                duplicate,
+                in_sym_seed,
+                SymmetricDefinitionObject::Null,
+            )
+            .expect("Failed to import the rewrapped object");
+        load_imported(&mut context, new_parent_handle, &public, private);
+    }
+}
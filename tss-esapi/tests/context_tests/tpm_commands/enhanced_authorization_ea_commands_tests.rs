@@ -141,6 +141,80 @@ mod test_policy_secret {
     }
 }
 
+mod test_policy_ticket {
+    use crate::common::create_ctx_with_session;
+    use std::{convert::TryFrom, time::Duration};
+    use tss_esapi::{
+        attributes::SessionAttributesBuilder,
+        constants::SessionType,
+        handles::{AuthHandle, ObjectHandle},
+        interface_types::{algorithm::HashingAlgorithm, session_handles::PolicySession},
+        structures::{Digest, Nonce, SymmetricDefinition, Ticket},
+    };
+    #[test]
+    fn test_policy_ticket_null_ticket() {
+        let mut context = create_ctx_with_session();
+
+        let policy_auth_session = context
+            .execute_without_session(|ctx| {
+                ctx.start_auth_session(
+                    None,
+                    None,
+                    None,
+                    SessionType::Policy,
+                    SymmetricDefinition::AES_256_CFB,
+                    HashingAlgorithm::Sha256,
+                )
+            })
+            .expect("Start auth session failed")
+            .expect("Start auth session returned a NONE handle");
+        let (policy_auth_session_attributes, policy_auth_session_attributes_mask) =
+            SessionAttributesBuilder::new()
+                .with_decrypt(true)
+                .with_encrypt(true)
+                .build();
+        context
+            .tr_sess_set_attributes(
+                policy_auth_session,
+                policy_auth_session_attributes,
+                policy_auth_session_attributes_mask,
+            )
+            .expect("tr_sess_set_attributes call failed");
+
+        let policy_session = PolicySession::try_from(policy_auth_session)
+            .expect("Failed to convert auth session into policy session");
+        let cp_hash_a = Digest::default();
+        let policy_ref = Nonce::try_from(vec![1, 2, 3]).unwrap();
+
+        // A positive expiration only produces a null ticket.
+        let (timeout, ticket) = context
+            .policy_secret(
+                policy_session,
+                AuthHandle::Endorsement,
+                Nonce::default(),
+                cp_hash_a.clone(),
+                policy_ref.clone(),
+                Some(Duration::from_secs(3600)),
+            )
+            .expect("Failed to call policy_secret");
+        assert!(ticket.digest().is_empty());
+
+        let auth_name = context
+            .execute_without_session(|ctx| ctx.tr_get_name(ObjectHandle::Endorsement))
+            .expect("Failed to get the name of the endorsement hierarchy");
+        let _ = context
+            .policy_ticket(
+                policy_session,
+                timeout,
+                cp_hash_a,
+                policy_ref,
+                auth_name,
+                ticket,
+            )
+            .expect_err("The TPM accepted a null ticket");
+    }
+}
+
 mod test_policy_or {
     use crate::common::{create_ctx_without_session, get_pcr_policy_digest};
     use std::convert::TryFrom;
@@ -328,6 +402,165 @@ mod test_policy_locality {
     }
 }
 
+mod test_policy_nv {
+    use crate::common::create_ctx_with_session;
+    use std::convert::TryFrom;
+    use tss_esapi::{
+        attributes::{NvIndexAttributesBuilder, SessionAttributesBuilder},
+        constants::{ArithmeticOperation, SessionType},
+        handles::NvIndexTpmHandle,
+        interface_types::{
+            algorithm::HashingAlgorithm,
+            resource_handles::{NvAuth, Provision},
+            session_handles::PolicySession,
+        },
+        nv::storage::NvPublicBuilder,
+        structures::{MaxNvBuffer, Operand, SymmetricDefinition},
+    };
+    #[test]
+    fn test_policy_nv() {
+        let mut context = create_ctx_with_session();
+
+        let nv_index = NvIndexTpmHandle::new(0x01500046).unwrap();
+        let owner_nv_index_attributes = NvIndexAttributesBuilder::new()
+            .with_owner_write(true)
+            .with_owner_read(true)
+            .build()
+            .expect("Failed to create owner nv index attributes");
+        let owner_nv_public = NvPublicBuilder::new()
+            .with_nv_index(nv_index)
+            .with_index_name_algorithm(HashingAlgorithm::Sha256)
+            .with_index_attributes(owner_nv_index_attributes)
+            .with_data_area_size(8)
+            .build()
+            .expect("Failed to build NvPublic for owner");
+        let owner_nv_index_handle = context
+            .nv_define_space(Provision::Owner, None, &owner_nv_public)
+            .expect("Call to nv_define_space failed");
+        context
+            .nv_write(
+                NvAuth::Owner,
+                owner_nv_index_handle,
+                &MaxNvBuffer::try_from(vec![0, 0, 0, 0, 0, 0, 0, 5]).unwrap(),
+                0,
+            )
+            .expect("Call to nv_write failed");
+
+        let policy_auth_session = context
+            .execute_without_session(|ctx| {
+                ctx.start_auth_session(
+                    None,
+                    None,
+                    None,
+                    SessionType::Policy,
+                    SymmetricDefinition::AES_256_CFB,
+                    HashingAlgorithm::Sha256,
+                )
+            })
+            .expect("Start auth session failed")
+            .expect("Start auth session returned a NONE handle");
+        let (policy_auth_session_attributes, policy_auth_session_attributes_mask) =
+            SessionAttributesBuilder::new()
+                .with_decrypt(true)
+                .with_encrypt(true)
+                .build();
+        context
+            .tr_sess_set_attributes(
+                policy_auth_session,
+                policy_auth_session_attributes,
+                policy_auth_session_attributes_mask,
+            )
+            .expect("tr_sess_set_attributes call failed");
+        let policy_session = PolicySession::try_from(policy_auth_session)
+            .expect("Failed to convert auth session into policy session");
+
+        // The last byte of the index is greater than 4 ...
+        let greater_result = context.policy_nv(
+            policy_session,
+            NvAuth::Owner,
+            owner_nv_index_handle,
+            Operand::try_from(vec![4]).unwrap(),
+            7,
+            ArithmeticOperation::UnsignedGt,
+        );
+        // ... but not equal to it.
+        let equal_result = context.policy_nv(
+            policy_session,
+            NvAuth::Owner,
+            owner_nv_index_handle,
+            Operand::try_from(vec![4]).unwrap(),
+            7,
+            ArithmeticOperation::Eq,
+        );
+
+        context
+            .nv_undefine_space(Provision::Owner, owner_nv_index_handle)
+            .expect("Call to nv_undefine_space failed");
+
+        greater_result.expect("Failed to call policy_nv");
+        let _ = equal_result.expect_err("The NV index comparison should have failed");
+    }
+}
+
+mod test_policy_counter_timer {
+    use crate::common::create_ctx_without_session;
+    use std::convert::TryFrom;
+    use tss_esapi::{
+        attributes::SessionAttributesBuilder,
+        constants::{ArithmeticOperation, SessionType},
+        interface_types::{algorithm::HashingAlgorithm, session_handles::PolicySession},
+        structures::{Operand, SymmetricDefinition},
+    };
+    #[test]
+    fn test_policy_counter_timer() {
+        let mut context = create_ctx_without_session();
+        let policy_auth_session = context
+            .start_auth_session(
+                None,
+                None,
+                None,
+                SessionType::Policy,
+                SymmetricDefinition::AES_256_CFB,
+                HashingAlgorithm::Sha256,
+            )
+            .expect("Start auth session failed")
+            .expect("Start auth session returned a NONE handle");
+        let (policy_auth_session_attributes, policy_auth_session_attributes_mask) =
+            SessionAttributesBuilder::new()
+                .with_decrypt(true)
+                .with_encrypt(true)
+                .build();
+        context
+            .tr_sess_set_attributes(
+                policy_auth_session,
+                policy_auth_session_attributes,
+                policy_auth_session_attributes_mask,
+            )
+            .expect("tr_sess_set_attributes call failed");
+        let policy_session = PolicySession::try_from(policy_auth_session)
+            .expect("Failed to convert auth session into policy session");
+
+        // The time, at offset 0, is a 64 bit value which is always less
+        // than the maximum value.
+        context
+            .policy_counter_timer(
+                policy_session,
+                Operand::try_from(vec![0xFF; 8]).unwrap(),
+                0,
+                ArithmeticOperation::UnsignedLt,
+            )
+            .expect("Failed to call policy_counter_timer");
+        let _ = context
+            .policy_counter_timer(
+                policy_session,
+                Operand::try_from(vec![0xFF; 8]).unwrap(),
+                0,
+                ArithmeticOperation::UnsignedGe,
+            )
+            .expect_err("The time comparison should have failed");
+    }
+}
+
 mod test_policy_command_code {
     use crate::common::create_ctx_without_session;
     use std::convert::TryFrom;
@@ -516,6 +749,67 @@ mod test_policy_name_hash {
     }
 }
 
+mod test_policy_duplication_select {
+    use crate::common::create_ctx_without_session;
+    use std::convert::TryFrom;
+    use tss_esapi::{
+        constants::SessionType,
+        handles::SessionHandle,
+        interface_types::{algorithm::HashingAlgorithm, session_handles::PolicySession},
+        structures::{Digest, Name, SymmetricDefinition},
+        Context,
+    };
+
+    fn duplication_select_digest(context: &mut Context, include_object: bool) -> Digest {
+        let trial_policy_auth_session = context
+            .start_auth_session(
+                None,
+                None,
+                None,
+                SessionType::Trial,
+                SymmetricDefinition::AES_256_CFB,
+                HashingAlgorithm::Sha256,
+            )
+            .expect("Start auth session failed")
+            .expect("Start auth session returned a NONE handle");
+        let trial_policy_session = PolicySession::try_from(trial_policy_auth_session)
+            .expect("Failed to convert auth session into policy session");
+
+        let mut object_name = vec![0x00, 0x0B];
+        object_name.extend_from_slice(&[0x11; 32]);
+        let mut new_parent_name = vec![0x00, 0x0B];
+        new_parent_name.extend_from_slice(&[0x22; 32]);
+
+        context
+            .policy_duplication_select(
+                trial_policy_session,
+                Name::try_from(object_name).unwrap(),
+                Name::try_from(new_parent_name).unwrap(),
+                include_object,
+            )
+            .expect("Failed to call policy_duplication_select");
+        let digest = context
+            .policy_get_digest(trial_policy_session)
+            .expect("Failed to call policy_get_digest");
+        context
+            .flush_context(SessionHandle::from(trial_policy_auth_session).into())
+            .expect("Failed to flush trial session");
+        digest
+    }
+
+    #[test]
+    fn test_policy_duplication_select() {
+        let mut context = create_ctx_without_session();
+
+        let with_object = duplication_select_digest(&mut context, true);
+        let without_object = duplication_select_digest(&mut context, false);
+
+        assert_ne!(with_object.value(), &[0; 32]);
+        assert_ne!(without_object.value(), &[0; 32]);
+        assert_ne!(with_object, without_object);
+    }
+}
+
 mod test_policy_authorize {
     use crate::common::{create_ctx_with_session, get_pcr_policy_digest, signing_key_pub};
     use std::convert::{TryFrom, TryInto};
@@ -874,3 +1168,87 @@ mod test_policy_template {
         assert_eq!(expected_policy_template, policy_digest);
     }
 }
+
+mod test_policy_authorize_nv {
+    use crate::common::{create_ctx_with_session, get_command_code_policy};
+    use std::convert::TryFrom;
+    use tss_esapi::{
+        attributes::NvIndexAttributesBuilder,
+        constants::{
+            tss::{TPM2_CC_NV_Read, TPM2_CC_Unseal},
+            SessionType,
+        },
+        handles::{NvIndexTpmHandle, SessionHandle},
+        interface_types::{
+            algorithm::HashingAlgorithm,
+            resource_handles::{NvAuth, Provision},
+            session_handles::PolicySession,
+        },
+        nv::storage::NvPublicBuilder,
+        structures::MaxNvBuffer,
+    };
+
+    #[test]
+    fn test_policy_authorize_nv() {
+        let mut context = create_ctx_with_session();
+
+        // The NV index holds the authorized policy as a TPMT_HA.
+        let nv_index = NvIndexTpmHandle::new(0x01500047).unwrap();
+        let owner_nv_index_attributes = NvIndexAttributesBuilder::new()
+            .with_owner_write(true)
+            .with_owner_read(true)
+            .build()
+            .expect("Failed to create owner nv index attributes");
+        let owner_nv_public = NvPublicBuilder::new()
+            .with_nv_index(nv_index)
+            .with_index_name_algorithm(HashingAlgorithm::Sha256)
+            .with_index_attributes(owner_nv_index_attributes)
+            .with_data_area_size(34)
+            .build()
+            .expect("Failed to build NvPublic for owner");
+        let owner_nv_index_handle = context
+            .nv_define_space(Provision::Owner, None, &owner_nv_public)
+            .expect("Call to nv_define_space failed");
+
+        let (authorized_digest, trial_session) =
+            get_command_code_policy(&mut context, SessionType::Trial, TPM2_CC_Unseal);
+        context
+            .flush_context(SessionHandle::from(trial_session).into())
+            .expect("Failed to flush trial session");
+        let mut authorized_policy = vec![0x00, 0x0B];
+        authorized_policy.extend_from_slice(authorized_digest.value());
+        context
+            .nv_write(
+                NvAuth::Owner,
+                owner_nv_index_handle,
+                &MaxNvBuffer::try_from(authorized_policy).unwrap(),
+                0,
+            )
+            .expect("Call to nv_write failed");
+
+        // A policy session matching the authorized policy is accepted ...
+        let (_, policy_auth_session) =
+            get_command_code_policy(&mut context, SessionType::Policy, TPM2_CC_Unseal);
+        let policy_session = PolicySession::try_from(policy_auth_session)
+            .expect("Failed to convert auth session into policy session");
+        let authorized_result =
+            context.policy_authorize_nv(policy_session, NvAuth::Owner, owner_nv_index_handle);
+        let authorized_policy_digest = context.policy_get_digest(policy_session);
+
+        // ... but a different one is not.
+        let (_, other_auth_session) =
+            get_command_code_policy(&mut context, SessionType::Policy, TPM2_CC_NV_Read);
+        let other_session = PolicySession::try_from(other_auth_session)
+            .expect("Failed to convert auth session into policy session");
+        let unauthorized_result =
+            context.policy_authorize_nv(other_session, NvAuth::Owner, owner_nv_index_handle);
+
+        context
+            .nv_undefine_space(Provision::Owner, owner_nv_index_handle)
+            .expect("Call to nv_undefine_space failed");
+
+        authorized_result.expect("Failed to call policy_authorize_nv");
+        assert_ne!(authorized_policy_digest.unwrap(), authorized_digest);
+        let _ = unauthorized_result.expect_err("An unauthorized policy was accepted");
+    }
+}
@@ -1,2 +1,84 @@
 // Copyright 2021 Contributors to the Parsec project.
 // SPDX-License-Identifier: Apache-2.0
+mod test_commit {
+    use crate::common::create_ctx_with_session;
+    use tss_esapi::{
+        attributes::ObjectAttributesBuilder,
+        interface_types::{
+            algorithm::{EccSchemeAlgorithm, HashingAlgorithm, PublicAlgorithm},
+            ecc::EccCurve,
+            resource_handles::Hierarchy,
+        },
+        structures::{
+            EccPoint, EccScheme, KeyDerivationFunctionScheme, PublicBuilder,
+            PublicEccParametersBuilder,
+        },
+    };
+
+    #[test]
+    fn test_commit() {
+        let mut context = create_ctx_with_session();
+
+        let ecc_parms = PublicEccParametersBuilder::new()
+            .with_ecc_scheme(
+                EccScheme::create(
+                    EccSchemeAlgorithm::EcDaa,
+                    Some(HashingAlgorithm::Sha256),
+                    Some(0),
+                )
+                .unwrap(),
+            )
+            .with_curve(EccCurve::BnP256)
+            .with_is_signing_key(true)
+            .with_is_decryption_key(false)
+            .with_restricted(false)
+            .with_key_derivation_function_scheme(KeyDerivationFunctionScheme::Null)
+            .build()
+            .unwrap();
+        let object_attributes = ObjectAttributesBuilder::new()
+            .with_fixed_tpm(true)
+            .with_fixed_parent(true)
+            .with_sensitive_data_origin(true)
+            .with_user_with_auth(true)
+            .with_sign_encrypt(true)
+            .build()
+            .unwrap();
+        let public = PublicBuilder::new()
+            .with_public_algorithm(PublicAlgorithm::Ecc)
+            .with_name_hashing_algorithm(HashingAlgorithm::Sha256)
+            .with_object_attributes(object_attributes)
+            .with_ecc_parameters(ecc_parms)
+            .with_ecc_unique_identifier(&EccPoint::default())
+            .build()
+            .unwrap();
+        let key_handle = context
+            .create_primary(Hierarchy::Owner, &public, None, None, None, None)
+            .unwrap()
+            .key_handle;
+
+        let result = context.commit(key_handle, None, None, None).unwrap();
+        assert!(result.k.x().is_empty() && result.l.x().is_empty());
+        assert!(!result.e.x().is_empty());
+
+        let next_result = context.commit(key_handle, None, None, None).unwrap();
+        assert_ne!(result.counter, next_result.counter);
+    }
+}
+
+mod test_ec_ephemeral {
+    use crate::common::create_ctx_without_session;
+    use tss_esapi::interface_types::ecc::EccCurve;
+
+    #[test]
+    fn test_ec_ephemeral() {
+        let mut context = create_ctx_without_session();
+
+        let (point, counter) = context.ec_ephemeral(EccCurve::NistP256).unwrap();
+        assert_eq!(point.x().len(), 32);
+        assert_eq!(point.y().len(), 32);
+
+        let (next_point, next_counter) = context.ec_ephemeral(EccCurve::NistP256).unwrap();
+        assert_ne!(counter, next_counter);
+        assert_ne!(point.x().value(), next_point.x().value());
+    }
+}
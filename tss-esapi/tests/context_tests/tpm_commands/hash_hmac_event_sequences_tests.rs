@@ -1,2 +1,201 @@
 // Copyright 2021 Contributors to the Parsec project.
 // SPDX-License-Identifier: Apache-2.0
+mod test_hash_sequence {
+    use crate::common::create_ctx_with_session;
+    use std::convert::TryFrom;
+    use tss_esapi::{
+        interface_types::{algorithm::HashingAlgorithm, resource_handles::Hierarchy},
+        structures::{Auth, MaxBuffer, Ticket},
+    };
+
+    #[test]
+    fn test_hash_sequence() {
+        let mut context = create_ctx_with_session();
+        let data = vec![0xEE; 2000];
+
+        let sequence_auth = Auth::try_from(vec![1, 2, 3, 4]).unwrap();
+        let sequence_handle = context
+            .hash_sequence_start(Some(sequence_auth.clone()), HashingAlgorithm::Sha256)
+            .unwrap();
+        context
+            .tr_set_auth(sequence_handle, &sequence_auth)
+            .unwrap();
+
+        context
+            .sequence_update(
+                sequence_handle,
+                MaxBuffer::try_from(data[..MaxBuffer::MAX_SIZE].to_vec()).unwrap(),
+            )
+            .unwrap();
+        let (digest, ticket) = context
+            .sequence_complete(
+                sequence_handle,
+                MaxBuffer::try_from(data[MaxBuffer::MAX_SIZE..].to_vec()).unwrap(),
+                Hierarchy::Owner,
+            )
+            .unwrap();
+        assert_eq!(ticket.hierarchy(), Hierarchy::Owner);
+
+        // Hash the same data in two parts to have a reference value.
+        let sequence_handle = context
+            .hash_sequence_start(None, HashingAlgorithm::Sha256)
+            .unwrap();
+        context
+            .sequence_update(
+                sequence_handle,
+                MaxBuffer::try_from(data[..1000].to_vec()).unwrap(),
+            )
+            .unwrap();
+        let (expected, _) = context
+            .sequence_complete(
+                sequence_handle,
+                MaxBuffer::try_from(data[1000..].to_vec()).unwrap(),
+                Hierarchy::Null,
+            )
+            .unwrap();
+        assert_eq!(digest, expected);
+    }
+
+    #[test]
+    fn test_hash_sequence_matches_hash() {
+        let mut context = create_ctx_with_session();
+        let data = MaxBuffer::try_from(vec![1, 2, 3, 4, 5, 6, 7, 8]).unwrap();
+
+        let sequence_handle = context
+            .hash_sequence_start(None, HashingAlgorithm::Sha256)
+            .unwrap();
+        let (digest, _) = context
+            .sequence_complete(sequence_handle, data.clone(), Hierarchy::Null)
+            .unwrap();
+
+        let (expected, _) = context
+            .hash(&data, HashingAlgorithm::Sha256, Hierarchy::Null)
+            .unwrap();
+        assert_eq!(digest, expected);
+    }
+
+    #[test]
+    fn test_hash_sequence_ticket_hierarchy() {
+        // The hierarchy is given to the ESAPI as a different type of handle
+        // depending on the version of the TSS libraries.
+        let mut context = create_ctx_with_session();
+        let data = MaxBuffer::try_from(vec![1, 2, 3, 4, 5, 6, 7, 8]).unwrap();
+
+        for hierarchy in [Hierarchy::Owner, Hierarchy::Endorsement, Hierarchy::Null].iter() {
+            let sequence_handle = context
+                .hash_sequence_start(None, HashingAlgorithm::Sha256)
+                .unwrap();
+            let (_, ticket) = context
+                .sequence_complete(sequence_handle, data.clone(), *hierarchy)
+                .unwrap();
+            assert_eq!(ticket.hierarchy(), *hierarchy);
+        }
+    }
+}
+
+mod test_hmac_sequence {
+    use crate::common::create_ctx_with_session;
+    use std::convert::TryFrom;
+    use tss_esapi::{
+        attributes::ObjectAttributesBuilder,
+        interface_types::{
+            algorithm::{HashingAlgorithm, PublicAlgorithm},
+            resource_handles::Hierarchy,
+            session_handles::AuthSession,
+        },
+        structures::{KeyedHashScheme, MaxBuffer, PublicBuilder, PublicKeyedHashParameters},
+    };
+
+    #[test]
+    fn test_hmac_sequence() {
+        let mut context = create_ctx_with_session();
+
+        let object_attributes = ObjectAttributesBuilder::new()
+            .with_sign_encrypt(true)
+            .with_sensitive_data_origin(true)
+            .with_user_with_auth(true)
+            .build()
+            .expect("Failed to build object attributes");
+
+        let key_pub = PublicBuilder::new()
+            .with_public_algorithm(PublicAlgorithm::KeyedHash)
+            .with_name_hashing_algorithm(HashingAlgorithm::Sha256)
+            .with_object_attributes(object_attributes)
+            .with_keyed_hash_parameters(PublicKeyedHashParameters::new(
+                KeyedHashScheme::HMAC_SHA_256,
+            ))
+            .with_keyed_hash_unique_identifier(&Default::default())
+            .build()
+            .expect("Failed to build public strucuture for key.");
+
+        let key = context
+            .create_primary(Hierarchy::Owner, &key_pub, None, None, None, None)
+            .unwrap();
+
+        let data = MaxBuffer::try_from(vec![1, 2, 3, 4]).unwrap();
+        let expected = context
+            .hmac(key.key_handle.into(), &data, HashingAlgorithm::Sha256)
+            .unwrap();
+
+        let sequence_handle = context
+            .hmac_start(key.key_handle.into(), None, HashingAlgorithm::Sha256)
+            .unwrap();
+        let (digest, _) = context
+            .execute_with_session(Some(AuthSession::Password), |ctx| {
+                ctx.sequence_update(sequence_handle, MaxBuffer::try_from(vec![1, 2])?)?;
+                ctx.sequence_complete(
+                    sequence_handle,
+                    MaxBuffer::try_from(vec![3, 4])?,
+                    Hierarchy::Null,
+                )
+            })
+            .unwrap();
+        assert_eq!(digest, expected);
+    }
+}
+
+mod test_event_sequence {
+    use crate::common::create_ctx_with_session;
+    use std::convert::TryFrom;
+    use tss_esapi::{
+        handles::PcrHandle,
+        interface_types::{
+            algorithm::HashingAlgorithm, resource_handles::Hierarchy, session_handles::AuthSession,
+        },
+        structures::MaxBuffer,
+    };
+
+    #[test]
+    fn test_event_sequence_complete() {
+        let mut context = create_ctx_with_session();
+        let data = MaxBuffer::try_from(vec![0xAB; 64]).unwrap();
+
+        let sequence_handle = context
+            .hash_sequence_start(None, HashingAlgorithm::Null)
+            .unwrap();
+        context
+            .sequence_update(sequence_handle, data.clone())
+            .unwrap();
+        let digests = context
+            .execute_with_sessions(
+                (
+                    Some(AuthSession::Password),
+                    Some(AuthSession::Password),
+                    None,
+                ),
+                |ctx| {
+                    ctx.event_sequence_complete(
+                        PcrHandle::Pcr16,
+                        sequence_handle,
+                        MaxBuffer::default(),
+                    )
+                },
+            )
+            .unwrap();
+
+        let (expected, _) = context
+            .hash(&data, HashingAlgorithm::Sha256, Hierarchy::Null)
+            .unwrap();
+        assert_eq!(digests.get(HashingAlgorithm::Sha256), Some(&expected));
+    }
+}
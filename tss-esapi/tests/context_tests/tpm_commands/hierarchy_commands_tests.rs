@@ -28,6 +28,181 @@ mod test_create_primary {
     }
 }
 
+mod test_hierarchy_control {
+    use crate::common::{create_ctx_with_session, decryption_key_pub};
+    use tss_esapi::{
+        interface_types::{
+            hierarchy_state::HierarchyState,
+            resource_handles::{Enables, Hierarchy},
+        },
+        Context, Error, WrapperErrorKind,
+    };
+
+    /// Context enabling the endorsement hierarchy again when dropped, so
+    /// that it is not left disabled for the other tests if this one fails
+    struct EndorsementGuard(Context);
+
+    impl Drop for EndorsementGuard {
+        fn drop(&mut self) {
+            let _ = self.0.hierarchy_control(
+                Hierarchy::Platform,
+                Enables::Endorsement,
+                HierarchyState::Enabled,
+            );
+        }
+    }
+
+    #[test]
+    fn test_hierarchy_control() {
+        let mut guard = EndorsementGuard(create_ctx_with_session());
+        let context = &mut guard.0;
+
+        context
+            .hierarchy_control(
+                Hierarchy::Platform,
+                Enables::Endorsement,
+                HierarchyState::Disabled,
+            )
+            .unwrap();
+        assert!(context
+            .create_primary(
+                Hierarchy::Endorsement,
+                &decryption_key_pub(),
+                None,
+                None,
+                None,
+                None,
+            )
+            .is_err());
+
+        // Only the platform can enable the hierarchy again.
+        let _ = context
+            .hierarchy_control(
+                Hierarchy::Endorsement,
+                Enables::Endorsement,
+                HierarchyState::Enabled,
+            )
+            .unwrap_err();
+        context
+            .hierarchy_control(
+                Hierarchy::Platform,
+                Enables::Endorsement,
+                HierarchyState::Enabled,
+            )
+            .unwrap();
+        let key_handle = context
+            .create_primary(
+                Hierarchy::Endorsement,
+                &decryption_key_pub(),
+                None,
+                None,
+                None,
+                None,
+            )
+            .unwrap()
+            .key_handle;
+        context.flush_context(key_handle.into()).unwrap();
+    }
+
+    #[test]
+    fn test_hierarchy_control_null_auth() {
+        let mut context = create_ctx_with_session();
+
+        assert_eq!(
+            context
+                .hierarchy_control(Hierarchy::Null, Enables::Owner, HierarchyState::Disabled)
+                .unwrap_err(),
+            Error::WrapperError(WrapperErrorKind::InvalidParam)
+        );
+    }
+}
+
+mod test_set_primary_policy {
+    use crate::common::create_ctx_with_session;
+    use std::convert::TryFrom;
+    use tss_esapi::{
+        interface_types::{algorithm::HashingAlgorithm, resource_handles::HierarchyPolicy},
+        structures::Digest,
+    };
+
+    #[test]
+    fn test_set_primary_policy() {
+        let mut context = create_ctx_with_session();
+
+        context
+            .set_primary_policy(
+                HierarchyPolicy::Owner,
+                Digest::try_from(vec![0x5A; 32]).unwrap(),
+                HashingAlgorithm::Sha256,
+            )
+            .unwrap();
+        // The size of the policy has to match the hashing algorithm.
+        let _ = context
+            .set_primary_policy(
+                HierarchyPolicy::Owner,
+                Digest::try_from(vec![0x5A; 20]).unwrap(),
+                HashingAlgorithm::Sha256,
+            )
+            .unwrap_err();
+        context
+            .set_primary_policy(
+                HierarchyPolicy::Owner,
+                Digest::default(),
+                HashingAlgorithm::Null,
+            )
+            .unwrap();
+    }
+}
+
+mod test_change_pps {
+    use crate::common::create_ctx_with_session;
+    use tss_esapi::interface_types::resource_handles::Platform;
+
+    #[test]
+    fn test_change_pps() {
+        let mut context = create_ctx_with_session();
+
+        context.change_pps(Platform::Platform).unwrap();
+    }
+}
+
+mod test_change_eps {
+    use crate::common::{create_ctx_with_session, decryption_key_pub};
+    use tss_esapi::interface_types::resource_handles::{Hierarchy, Platform};
+
+    #[test]
+    fn test_change_eps() {
+        let mut context = create_ctx_with_session();
+        let create_key = |context: &mut tss_esapi::Context| {
+            let result = context
+                .create_primary(
+                    Hierarchy::Endorsement,
+                    &decryption_key_pub(),
+                    None,
+                    None,
+                    None,
+                    None,
+                )
+                .unwrap();
+            context.flush_context(result.key_handle.into()).unwrap();
+            result.out_public
+        };
+
+        // The new seed is kept for the rest of the run, so the endorsement
+        // primary keys created by the other tests do not match the ones
+        // created before this test.
+        let old_public = create_key(&mut context);
+        context.change_eps(Platform::Platform).unwrap();
+        let new_public = create_key(&mut context);
+
+        // Primary keys are derived from the seed of their hierarchy.
+        assert_ne!(
+            old_public.marshall().unwrap(),
+            new_public.marshall().unwrap()
+        );
+    }
+}
+
 mod test_clear {
     use crate::common::create_ctx_with_session;
     use tss_esapi::handles::AuthHandle;
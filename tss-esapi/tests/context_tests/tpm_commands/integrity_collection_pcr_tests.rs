@@ -211,3 +211,152 @@ mod test_pcr_read {
         assert_ne!(pcr_selection_list_in, pcr_selection_list_out);
     }
 }
+
+mod test_pcr_event {
+    use crate::common::create_ctx_with_session;
+    use std::convert::TryFrom;
+    use tss_esapi::{
+        handles::PcrHandle,
+        interface_types::{algorithm::HashingAlgorithm, resource_handles::Hierarchy},
+        structures::{Event, MaxBuffer, PcrSelectionListBuilder, PcrSlot},
+    };
+
+    #[test]
+    fn test_pcr_event_command() {
+        let mut context = create_ctx_with_session();
+        let pcr_ses = context.sessions().0;
+        context.execute_with_session(pcr_ses, |ctx| ctx.pcr_reset(PcrHandle::Pcr16).unwrap());
+
+        let event_data = vec![0x01, 0x02, 0x03, 0x04];
+        let digests = context
+            .pcr_event(
+                PcrHandle::Pcr16,
+                Event::try_from(event_data.clone()).unwrap(),
+            )
+            .unwrap();
+
+        // The returned digests are the digests of the event data.
+        let (expected_digest, _) = context
+            .execute_without_session(|ctx| {
+                ctx.hash(
+                    &MaxBuffer::try_from(event_data).unwrap(),
+                    HashingAlgorithm::Sha256,
+                    Hierarchy::Null,
+                )
+            })
+            .unwrap();
+        assert_eq!(
+            digests.get(HashingAlgorithm::Sha256),
+            Some(&expected_digest)
+        );
+
+        // The PCR has been extended with the returned digest.
+        let pcr_selection_list = PcrSelectionListBuilder::new()
+            .with_selection(HashingAlgorithm::Sha256, &[PcrSlot::Slot16])
+            .build();
+        let (_, _, pcr_data) =
+            context.execute_without_session(|ctx| ctx.pcr_read(&pcr_selection_list).unwrap());
+        let pcr_value = pcr_data
+            .pcr_bank(HashingAlgorithm::Sha256)
+            .unwrap()
+            .pcr_value(PcrSlot::Slot16)
+            .unwrap()
+            .clone();
+
+        let mut extension = vec![0; 32];
+        extension.extend_from_slice(expected_digest.value());
+        let (expected_pcr_value, _) = context
+            .execute_without_session(|ctx| {
+                ctx.hash(
+                    &MaxBuffer::try_from(extension).unwrap(),
+                    HashingAlgorithm::Sha256,
+                    Hierarchy::Null,
+                )
+            })
+            .unwrap();
+        assert_eq!(pcr_value, expected_pcr_value);
+    }
+}
+
+mod test_pcr_allocate {
+    use crate::common::create_ctx_with_session;
+    use tss_esapi::{
+        constants::CapabilityType, interface_types::resource_handles::Platform,
+        structures::CapabilityData,
+    };
+
+    #[test]
+    fn test_pcr_allocate_current_allocation() {
+        let mut context = create_ctx_with_session();
+        let (capability_data, _) = context
+            .get_capability(CapabilityType::AssignedPCR, 0, 1)
+            .unwrap();
+        let pcr_allocation = match capability_data {
+            CapabilityData::AssignedPCR(pcr_selection_list) => pcr_selection_list,
+            _ => panic!("Unexpected capability data"),
+        };
+
+        // Requesting the current allocation leaves the TPM unchanged.
+        let result = context
+            .pcr_allocate(Platform::Platform, pcr_allocation)
+            .unwrap();
+        assert!(result.allocation_success);
+        assert!(result.max_pcr >= 24);
+        assert!(result.size_needed <= result.size_available);
+    }
+}
+
+mod test_pcr_set_auth {
+    use crate::common::create_ctx_with_session;
+    use std::convert::TryFrom;
+    use tss_esapi::{
+        handles::{PcrHandle, PcrTpmHandle},
+        interface_types::{algorithm::HashingAlgorithm, resource_handles::Platform},
+        structures::{Auth, Digest},
+    };
+
+    #[test]
+    fn test_pcr_set_auth_value() {
+        // PCR 20 belongs to the group of PCRs that have an authorization value.
+        let mut context = create_ctx_with_session();
+        let auth = Auth::try_from(vec![1, 2, 3, 4]).unwrap();
+        context
+            .pcr_set_auth_value(PcrHandle::Pcr20, auth.clone())
+            .unwrap();
+        context.tr_set_auth(PcrHandle::Pcr20.into(), &auth).unwrap();
+
+        context.pcr_reset(PcrHandle::Pcr20).unwrap();
+
+        // Restore the empty authorization value.
+        context
+            .pcr_set_auth_value(PcrHandle::Pcr20, Auth::default())
+            .unwrap();
+        context
+            .tr_set_auth(PcrHandle::Pcr20.into(), &Auth::default())
+            .unwrap();
+    }
+
+    #[test]
+    fn test_pcr_set_auth_policy() {
+        let mut context = create_ctx_with_session();
+        let pcr_handle = PcrTpmHandle::new(20).unwrap();
+        context
+            .pcr_set_auth_policy(
+                Platform::Platform,
+                Digest::try_from(vec![0xAA; 32]).unwrap(),
+                HashingAlgorithm::Sha256,
+                pcr_handle,
+            )
+            .unwrap();
+
+        // Remove the policy.
+        context
+            .pcr_set_auth_policy(
+                Platform::Platform,
+                Digest::default(),
+                HashingAlgorithm::Null,
+                pcr_handle,
+            )
+            .unwrap();
+    }
+}
@@ -326,3 +326,590 @@ mod test_nv_read {
         assert_eq!(expected_data, actual_data);
     }
 }
+
+mod test_nv_increment {
+    use crate::common::create_ctx_with_session;
+    use std::convert::TryInto;
+    use tss_esapi::{
+        attributes::NvIndexAttributesBuilder,
+        constants::NvIndexType,
+        handles::NvIndexTpmHandle,
+        interface_types::{
+            algorithm::HashingAlgorithm,
+            resource_handles::{NvAuth, Provision},
+        },
+        nv::storage::NvPublicBuilder,
+    };
+    #[test]
+    fn test_nv_increment() {
+        let mut context = create_ctx_with_session();
+
+        let nv_index = NvIndexTpmHandle::new(0x01500030).unwrap();
+
+        let owner_nv_index_attributes = NvIndexAttributesBuilder::new()
+            .with_owner_write(true)
+            .with_owner_read(true)
+            .with_nv_index_type(NvIndexType::Counter)
+            .build()
+            .expect("Failed to create owner nv index attributes");
+
+        let owner_nv_public = NvPublicBuilder::new()
+            .with_nv_index(nv_index)
+            .with_index_name_algorithm(HashingAlgorithm::Sha256)
+            .with_index_attributes(owner_nv_index_attributes)
+            .with_data_area_size(8)
+            .build()
+            .expect("Failed to build NvPublic for owner");
+
+        let owner_nv_index_handle = context
+            .nv_define_space(Provision::Owner, None, &owner_nv_public)
+            .expect("Call to nv_define_space failed");
+
+        let first_increment_result = context.nv_increment(NvAuth::Owner, owner_nv_index_handle);
+        let first_read_result = context.nv_read(NvAuth::Owner, owner_nv_index_handle, 8, 0);
+        let second_increment_result = context.nv_increment(NvAuth::Owner, owner_nv_index_handle);
+        let second_read_result = context.nv_read(NvAuth::Owner, owner_nv_index_handle, 8, 0);
+
+        context
+            .nv_undefine_space(Provision::Owner, owner_nv_index_handle)
+            .expect("Call to nv_undefine_space failed");
+
+        first_increment_result.expect("Failed to increment counter");
+        second_increment_result.expect("Failed to increment counter");
+        let first_value = u64::from_be_bytes(
+            first_read_result
+                .expect("Failed to read counter")
+                .value()
+                .try_into()
+                .unwrap(),
+        );
+        let second_value = u64::from_be_bytes(
+            second_read_result
+                .expect("Failed to read counter")
+                .value()
+                .try_into()
+                .unwrap(),
+        );
+        assert_eq!(second_value, first_value + 1);
+    }
+}
+
+mod test_nv_extend {
+    use crate::common::create_ctx_with_session;
+    use std::convert::TryFrom;
+    use tss_esapi::{
+        attributes::NvIndexAttributesBuilder,
+        constants::NvIndexType,
+        handles::NvIndexTpmHandle,
+        interface_types::{
+            algorithm::HashingAlgorithm,
+            resource_handles::{Hierarchy, NvAuth, Provision},
+        },
+        nv::storage::NvPublicBuilder,
+        structures::{MaxBuffer, MaxNvBuffer},
+    };
+    #[test]
+    fn test_nv_extend() {
+        let mut context = create_ctx_with_session();
+
+        let nv_index = NvIndexTpmHandle::new(0x01500031).unwrap();
+
+        let owner_nv_index_attributes = NvIndexAttributesBuilder::new()
+            .with_owner_write(true)
+            .with_owner_read(true)
+            .with_nv_index_type(NvIndexType::Extend)
+            .build()
+            .expect("Failed to create owner nv index attributes");
+
+        let owner_nv_public = NvPublicBuilder::new()
+            .with_nv_index(nv_index)
+            .with_index_name_algorithm(HashingAlgorithm::Sha256)
+            .with_index_attributes(owner_nv_index_attributes)
+            .with_data_area_size(32)
+            .build()
+            .expect("Failed to build NvPublic for owner");
+
+        let owner_nv_index_handle = context
+            .nv_define_space(Provision::Owner, None, &owner_nv_public)
+            .expect("Call to nv_define_space failed");
+
+        let data = vec![1, 2, 3, 4, 5, 6, 7];
+        let extend_result = context.nv_extend(
+            NvAuth::Owner,
+            owner_nv_index_handle,
+            &MaxNvBuffer::try_from(data.clone()).unwrap(),
+        );
+        let read_result = context.nv_read(NvAuth::Owner, owner_nv_index_handle, 32, 0);
+
+        context
+            .nv_undefine_space(Provision::Owner, owner_nv_index_handle)
+            .expect("Call to nv_undefine_space failed");
+
+        extend_result.expect("Failed to extend NV index");
+
+        // The new value is the digest of the old value (zeroes) and the data.
+        let mut extension = vec![0; 32];
+        extension.extend_from_slice(&data);
+        let (expected, _) = context
+            .hash(
+                &MaxBuffer::try_from(extension).unwrap(),
+                HashingAlgorithm::Sha256,
+                Hierarchy::Null,
+            )
+            .unwrap();
+        assert_eq!(
+            read_result.expect("Failed to read NV index").value(),
+            expected.value()
+        );
+    }
+}
+
+mod test_nv_set_bits {
+    use crate::common::create_ctx_with_session;
+    use std::convert::TryInto;
+    use tss_esapi::{
+        attributes::NvIndexAttributesBuilder,
+        constants::NvIndexType,
+        handles::NvIndexTpmHandle,
+        interface_types::{
+            algorithm::HashingAlgorithm,
+            resource_handles::{NvAuth, Provision},
+        },
+        nv::storage::NvPublicBuilder,
+    };
+    #[test]
+    fn test_nv_set_bits() {
+        let mut context = create_ctx_with_session();
+
+        let nv_index = NvIndexTpmHandle::new(0x01500032).unwrap();
+
+        let owner_nv_index_attributes = NvIndexAttributesBuilder::new()
+            .with_owner_write(true)
+            .with_owner_read(true)
+            .with_nv_index_type(NvIndexType::Bits)
+            .build()
+            .expect("Failed to create owner nv index attributes");
+
+        let owner_nv_public = NvPublicBuilder::new()
+            .with_nv_index(nv_index)
+            .with_index_name_algorithm(HashingAlgorithm::Sha256)
+            .with_index_attributes(owner_nv_index_attributes)
+            .with_data_area_size(8)
+            .build()
+            .expect("Failed to build NvPublic for owner");
+
+        let owner_nv_index_handle = context
+            .nv_define_space(Provision::Owner, None, &owner_nv_public)
+            .expect("Call to nv_define_space failed");
+
+        let first_set_bits_result =
+            context.nv_set_bits(NvAuth::Owner, owner_nv_index_handle, 0b0101);
+        let second_set_bits_result =
+            context.nv_set_bits(NvAuth::Owner, owner_nv_index_handle, 0b1000_0000);
+        let read_result = context.nv_read(NvAuth::Owner, owner_nv_index_handle, 8, 0);
+
+        context
+            .nv_undefine_space(Provision::Owner, owner_nv_index_handle)
+            .expect("Call to nv_undefine_space failed");
+
+        first_set_bits_result.expect("Failed to set bits");
+        second_set_bits_result.expect("Failed to set bits");
+        let value = u64::from_be_bytes(
+            read_result
+                .expect("Failed to read NV index")
+                .value()
+                .try_into()
+                .unwrap(),
+        );
+        assert_eq!(value, 0b1000_0101);
+    }
+}
+
+mod test_nv_write_lock {
+    use crate::common::create_ctx_with_session;
+    use std::convert::TryFrom;
+    use tss_esapi::{
+        attributes::NvIndexAttributesBuilder,
+        handles::NvIndexTpmHandle,
+        interface_types::{
+            algorithm::HashingAlgorithm,
+            resource_handles::{NvAuth, Provision},
+        },
+        nv::storage::NvPublicBuilder,
+        structures::MaxNvBuffer,
+    };
+    #[test]
+    fn test_nv_write_lock() {
+        let mut context = create_ctx_with_session();
+
+        let nv_index = NvIndexTpmHandle::new(0x01500036).unwrap();
+
+        let owner_nv_index_attributes = NvIndexAttributesBuilder::new()
+            .with_owner_write(true)
+            .with_owner_read(true)
+            .with_write_stclear(true)
+            .build()
+            .expect("Failed to create owner nv index attributes");
+
+        let owner_nv_public = NvPublicBuilder::new()
+            .with_nv_index(nv_index)
+            .with_index_name_algorithm(HashingAlgorithm::Sha256)
+            .with_index_attributes(owner_nv_index_attributes)
+            .with_data_area_size(32)
+            .build()
+            .expect("Failed to build NvPublic for owner");
+
+        let owner_nv_index_handle = context
+            .nv_define_space(Provision::Owner, None, &owner_nv_public)
+            .expect("Call to nv_define_space failed");
+
+        let data = MaxNvBuffer::try_from([1, 2, 3, 4, 5, 6, 7].to_vec()).unwrap();
+        let write_result = context.nv_write(NvAuth::Owner, owner_nv_index_handle, &data, 0);
+        let write_lock_result = context.nv_write_lock(NvAuth::Owner, owner_nv_index_handle);
+        let locked_write_result = context.nv_write(NvAuth::Owner, owner_nv_index_handle, &data, 0);
+
+        context
+            .nv_undefine_space(Provision::Owner, owner_nv_index_handle)
+            .expect("Call to nv_undefine_space failed");
+
+        write_result.expect("Failed to perform nv write");
+        write_lock_result.expect("Failed to perform nv write lock");
+        let _ = locked_write_result.expect_err("Write to a locked index should fail");
+    }
+}
+
+mod test_nv_global_write_lock {
+    use crate::common::create_ctx_with_session;
+    use std::convert::TryFrom;
+    use tss_esapi::{
+        attributes::NvIndexAttributesBuilder,
+        handles::NvIndexTpmHandle,
+        interface_types::{
+            algorithm::HashingAlgorithm,
+            resource_handles::{NvAuth, Provision},
+        },
+        nv::storage::NvPublicBuilder,
+        structures::MaxNvBuffer,
+    };
+    #[test]
+    fn test_nv_global_write_lock() {
+        let mut context = create_ctx_with_session();
+
+        let nv_index = NvIndexTpmHandle::new(0x01500037).unwrap();
+
+        let owner_nv_index_attributes = NvIndexAttributesBuilder::new()
+            .with_owner_write(true)
+            .with_owner_read(true)
+            .with_global_lock(true)
+            .build()
+            .expect("Failed to create owner nv index attributes");
+
+        let owner_nv_public = NvPublicBuilder::new()
+            .with_nv_index(nv_index)
+            .with_index_name_algorithm(HashingAlgorithm::Sha256)
+            .with_index_attributes(owner_nv_index_attributes)
+            .with_data_area_size(32)
+            .build()
+            .expect("Failed to build NvPublic for owner");
+
+        let owner_nv_index_handle = context
+            .nv_define_space(Provision::Owner, None, &owner_nv_public)
+            .expect("Call to nv_define_space failed");
+
+        let data = MaxNvBuffer::try_from([1, 2, 3, 4, 5, 6, 7].to_vec()).unwrap();
+        let global_write_lock_result = context.nv_global_write_lock(Provision::Owner);
+        let locked_write_result = context.nv_write(NvAuth::Owner, owner_nv_index_handle, &data, 0);
+
+        context
+            .nv_undefine_space(Provision::Owner, owner_nv_index_handle)
+            .expect("Call to nv_undefine_space failed");
+
+        global_write_lock_result.expect("Failed to perform nv global write lock");
+        let _ = locked_write_result.expect_err("Write to a locked index should fail");
+    }
+}
+
+mod test_nv_read_lock {
+    use crate::common::create_ctx_with_session;
+    use std::convert::TryFrom;
+    use tss_esapi::{
+        attributes::NvIndexAttributesBuilder,
+        handles::NvIndexTpmHandle,
+        interface_types::{
+            algorithm::HashingAlgorithm,
+            resource_handles::{NvAuth, Provision},
+        },
+        nv::storage::NvPublicBuilder,
+        structures::MaxNvBuffer,
+    };
+    #[test]
+    fn test_nv_read_lock() {
+        let mut context = create_ctx_with_session();
+
+        let nv_index = NvIndexTpmHandle::new(0x01500038).unwrap();
+
+        let owner_nv_index_attributes = NvIndexAttributesBuilder::new()
+            .with_owner_write(true)
+            .with_owner_read(true)
+            .with_read_stclear(true)
+            .build()
+            .expect("Failed to create owner nv index attributes");
+
+        let owner_nv_public = NvPublicBuilder::new()
+            .with_nv_index(nv_index)
+            .with_index_name_algorithm(HashingAlgorithm::Sha256)
+            .with_index_attributes(owner_nv_index_attributes)
+            .with_data_area_size(32)
+            .build()
+            .expect("Failed to build NvPublic for owner");
+
+        let owner_nv_index_handle = context
+            .nv_define_space(Provision::Owner, None, &owner_nv_public)
+            .expect("Call to nv_define_space failed");
+
+        let write_result = context.nv_write(
+            NvAuth::Owner,
+            owner_nv_index_handle,
+            &MaxNvBuffer::try_from([1, 2, 3, 4, 5, 6, 7].to_vec()).unwrap(),
+            0,
+        );
+        let read_result = context.nv_read(NvAuth::Owner, owner_nv_index_handle, 7, 0);
+        let read_lock_result = context.nv_read_lock(NvAuth::Owner, owner_nv_index_handle);
+        let locked_read_result = context.nv_read(NvAuth::Owner, owner_nv_index_handle, 7, 0);
+
+        context
+            .nv_undefine_space(Provision::Owner, owner_nv_index_handle)
+            .expect("Call to nv_undefine_space failed");
+
+        write_result.expect("Failed to perform nv write");
+        read_result.expect("Failed to perform nv read");
+        read_lock_result.expect("Failed to perform nv read lock");
+        let _ = locked_read_result.expect_err("Read of a locked index should fail");
+    }
+}
+
+mod test_nv_undefine_space_special {
+    use crate::common::{create_ctx_with_session, get_command_code_policy};
+    use tss_esapi::{
+        attributes::NvIndexAttributesBuilder,
+        constants::{tss::TPM2_CC_NV_UndefineSpaceSpecial, SessionType},
+        handles::{NvIndexTpmHandle, ObjectHandle, SessionHandle},
+        interface_types::{
+            algorithm::HashingAlgorithm,
+            resource_handles::{Platform, Provision},
+            session_handles::AuthSession,
+        },
+        nv::storage::NvPublicBuilder,
+    };
+    #[test]
+    fn test_nv_undefine_space_special() {
+        let mut context = create_ctx_with_session();
+
+        let nv_index = NvIndexTpmHandle::new(0x01500039).unwrap();
+
+        let (policy_digest, trial_session) = get_command_code_policy(
+            &mut context,
+            SessionType::Trial,
+            TPM2_CC_NV_UndefineSpaceSpecial,
+        );
+        context
+            .flush_context(ObjectHandle::from(SessionHandle::from(trial_session)))
+            .expect("Failed to flush trial session");
+
+        // If you see this line fail, you are likely running it against a live TPM.
+        // The Platform hierarchy is usually unavailable once the operating system has booted.
+        let platform_nv_index_attributes = NvIndexAttributesBuilder::new()
+            .with_pp_write(true)
+            .with_pp_read(true)
+            .with_platform_create(true)
+            .with_policy_delete(true)
+            .build()
+            .expect("Failed to create platform nv index attributes");
+
+        let platform_nv_public = NvPublicBuilder::new()
+            .with_nv_index(nv_index)
+            .with_index_name_algorithm(HashingAlgorithm::Sha256)
+            .with_index_attributes(platform_nv_index_attributes)
+            .with_index_auth_policy(&policy_digest)
+            .with_data_area_size(32)
+            .build()
+            .expect("Failed to build NvPublic for platform");
+
+        let platform_nv_index_handle = context
+            .nv_define_space(Provision::Platform, None, &platform_nv_public)
+            .expect("Call to nv_define_space failed");
+
+        // An index with the policy delete attribute cannot be removed normally.
+        let _ = context
+            .nv_undefine_space(Provision::Platform, platform_nv_index_handle)
+            .expect_err("Call to nv_undefine_space should fail");
+
+        let (_, policy_session) = get_command_code_policy(
+            &mut context,
+            SessionType::Policy,
+            TPM2_CC_NV_UndefineSpaceSpecial,
+        );
+        context
+            .execute_with_sessions(
+                (Some(policy_session), Some(AuthSession::Password), None),
+                |ctx| ctx.nv_undefine_space_special(platform_nv_index_handle, Platform::Platform),
+            )
+            .expect("Call to nv_undefine_space_special failed");
+    }
+}
+
+mod test_nv_change_auth {
+    use crate::common::{create_ctx_with_session, get_command_code_policy};
+    use std::convert::TryFrom;
+    use tss_esapi::{
+        attributes::NvIndexAttributesBuilder,
+        constants::{tss::TPM2_CC_NV_ChangeAuth, SessionType},
+        handles::{NvIndexTpmHandle, ObjectHandle, SessionHandle},
+        interface_types::{
+            algorithm::HashingAlgorithm,
+            resource_handles::{NvAuth, Provision},
+        },
+        nv::storage::NvPublicBuilder,
+        structures::{Auth, MaxNvBuffer},
+    };
+    #[test]
+    fn test_nv_change_auth() {
+        let mut context = create_ctx_with_session();
+
+        let nv_index = NvIndexTpmHandle::new(0x01500040).unwrap();
+
+        let (policy_digest, trial_session) =
+            get_command_code_policy(&mut context, SessionType::Trial, TPM2_CC_NV_ChangeAuth);
+        context
+            .flush_context(ObjectHandle::from(SessionHandle::from(trial_session)))
+            .expect("Failed to flush trial session");
+
+        let nv_index_attributes = NvIndexAttributesBuilder::new()
+            .with_auth_write(true)
+            .with_auth_read(true)
+            .build()
+            .expect("Failed to create nv index attributes");
+
+        let nv_public = NvPublicBuilder::new()
+            .with_nv_index(nv_index)
+            .with_index_name_algorithm(HashingAlgorithm::Sha256)
+            .with_index_attributes(nv_index_attributes)
+            .with_index_auth_policy(&policy_digest)
+            .with_data_area_size(32)
+            .build()
+            .expect("Failed to build NvPublic");
+
+        let nv_index_handle = context
+            .nv_define_space(Provision::Owner, None, &nv_public)
+            .expect("Call to nv_define_space failed");
+
+        let (_, policy_session) =
+            get_command_code_policy(&mut context, SessionType::Policy, TPM2_CC_NV_ChangeAuth);
+        let new_auth = Auth::try_from(vec![1, 2, 3, 4]).unwrap();
+        let change_auth_result = context.execute_with_session(Some(policy_session), |ctx| {
+            ctx.nv_change_auth(nv_index_handle, new_auth)
+        });
+
+        // The new authorization value is used for the index.
+        let write_result = context.nv_write(
+            NvAuth::NvIndex(nv_index_handle),
+            nv_index_handle,
+            &MaxNvBuffer::try_from([1, 2, 3, 4, 5, 6, 7].to_vec()).unwrap(),
+            0,
+        );
+
+        context
+            .nv_undefine_space(Provision::Owner, nv_index_handle)
+            .expect("Call to nv_undefine_space failed");
+
+        change_auth_result.expect("Call to nv_change_auth failed");
+        write_result.expect("Failed to perform nv write with new auth");
+    }
+}
+
+mod test_nv_certify {
+    use crate::common::{create_ctx_with_session, signing_key_pub};
+    use std::convert::TryFrom;
+    use tss_esapi::{
+        attributes::NvIndexAttributesBuilder,
+        constants::StructureTag,
+        handles::NvIndexTpmHandle,
+        interface_types::{
+            algorithm::HashingAlgorithm,
+            resource_handles::{Hierarchy, NvAuth, Provision},
+            session_handles::AuthSession,
+        },
+        nv::storage::NvPublicBuilder,
+        structures::{AttestInfo, Data, HashScheme, MaxNvBuffer, SignatureScheme},
+    };
+    #[test]
+    fn test_nv_certify() {
+        let mut context = create_ctx_with_session();
+
+        let nv_index = NvIndexTpmHandle::new(0x01500041).unwrap();
+
+        let signing_key_handle = context
+            .create_primary(Hierarchy::Owner, &signing_key_pub(), None, None, None, None)
+            .unwrap()
+            .key_handle;
+
+        let owner_nv_index_attributes = NvIndexAttributesBuilder::new()
+            .with_owner_write(true)
+            .with_owner_read(true)
+            .build()
+            .expect("Failed to create owner nv index attributes");
+
+        let owner_nv_public = NvPublicBuilder::new()
+            .with_nv_index(nv_index)
+            .with_index_name_algorithm(HashingAlgorithm::Sha256)
+            .with_index_attributes(owner_nv_index_attributes)
+            .with_data_area_size(32)
+            .build()
+            .expect("Failed to build NvPublic for owner");
+
+        let owner_nv_index_handle = context
+            .nv_define_space(Provision::Owner, None, &owner_nv_public)
+            .expect("Call to nv_define_space failed");
+
+        let data = vec![1, 2, 3, 4, 5, 6, 7];
+        let write_result = context.nv_write(
+            NvAuth::Owner,
+            owner_nv_index_handle,
+            &MaxNvBuffer::try_from(data.clone()).unwrap(),
+            0,
+        );
+        let certify_result = context.execute_with_sessions(
+            (
+                Some(AuthSession::Password),
+                Some(AuthSession::Password),
+                None,
+            ),
+            |ctx| {
+                ctx.nv_certify(
+                    signing_key_handle,
+                    NvAuth::Owner,
+                    owner_nv_index_handle,
+                    Data::try_from(vec![0xff; 16]).unwrap(),
+                    SignatureScheme::RsaSsa(HashScheme::new(HashingAlgorithm::Sha256)),
+                    4,
+                    2,
+                )
+            },
+        );
+
+        context
+            .nv_undefine_space(Provision::Owner, owner_nv_index_handle)
+            .expect("Call to nv_undefine_space failed");
+
+        write_result.expect("Failed to perform nv write");
+        let (attest, _) = certify_result.expect("Call to nv_certify failed");
+        assert_eq!(attest.attestation_type(), StructureTag::AttestNv);
+        match attest.attested() {
+            AttestInfo::Nv(nv_certify_info) => {
+                assert_eq!(nv_certify_info.offset(), 2);
+                assert_eq!(nv_certify_info.nv_contents().value(), &data[2..6]);
+            }
+            _ => panic!("Attested data was not an NV certify info"),
+        }
+    }
+}
@@ -129,6 +129,26 @@ mod test_load_external_public {
             .load_external_public(&pub_key, Hierarchy::Owner)
             .unwrap();
     }
+
+    #[test]
+    fn test_load_external_public_in_every_hierarchy() {
+        // The hierarchy is given to the ESAPI as a different type of handle
+        // depending on the version of the TSS libraries.
+        let mut context = create_ctx_with_session();
+        let pub_key = get_ext_rsa_pub();
+
+        for hierarchy in [
+            Hierarchy::Owner,
+            Hierarchy::Platform,
+            Hierarchy::Endorsement,
+            Hierarchy::Null,
+        ]
+        .iter()
+        {
+            let key_handle = context.load_external_public(&pub_key, *hierarchy).unwrap();
+            context.flush_context(key_handle.into()).unwrap();
+        }
+    }
 }
 
 mod test_read_public {
@@ -328,3 +348,139 @@ mod test_unseal {
         assert!(unsealed == testbytes);
     }
 }
+
+mod test_create_loaded {
+    use crate::common::{create_ctx_with_session, decryption_key_pub, signing_key_pub};
+    use std::convert::TryFrom;
+    use tss_esapi::{
+        attributes::ObjectAttributesBuilder,
+        interface_types::{
+            algorithm::{HashingAlgorithm, KeyDerivationFunction, PublicAlgorithm},
+            ecc::EccCurve,
+            resource_handles::Hierarchy,
+        },
+        structures::{
+            Auth, EccPoint, EccScheme, HashScheme, KeyedHashScheme, Public, PublicBuilder,
+            PublicEccParametersBuilder, PublicKeyedHashParameters, Template, XorScheme,
+        },
+        Context,
+    };
+
+    fn derivation_parent_public() -> Public {
+        let object_attributes = ObjectAttributesBuilder::new()
+            .with_fixed_tpm(true)
+            .with_fixed_parent(true)
+            .with_sensitive_data_origin(true)
+            .with_user_with_auth(true)
+            .with_decrypt(true)
+            .with_sign_encrypt(true)
+            .with_restricted(true)
+            .build()
+            .expect("Failed to build object attributes");
+
+        PublicBuilder::new()
+            .with_public_algorithm(PublicAlgorithm::KeyedHash)
+            .with_name_hashing_algorithm(HashingAlgorithm::Sha256)
+            .with_object_attributes(object_attributes)
+            .with_keyed_hash_parameters(PublicKeyedHashParameters::new(KeyedHashScheme::Xor {
+                xor_scheme: XorScheme::new(
+                    HashingAlgorithm::Sha256,
+                    KeyDerivationFunction::Kdf1Sp800_108,
+                ),
+            }))
+            .with_keyed_hash_unique_identifier(&Default::default())
+            .build()
+            .expect("Failed to build the public structure of the derivation parent")
+    }
+
+    fn derive(context: &mut Context, template: &Template) -> Vec<u8> {
+        let parent_handle = context
+            .create_loaded(
+                Hierarchy::Owner.into(),
+                Template::try_from(derivation_parent_public()).unwrap(),
+                None,
+                None,
+            )
+            .unwrap()
+            .key_handle;
+        let result = context
+            .create_loaded(parent_handle.into(), template.clone(), None, None)
+            .unwrap();
+        context.flush_context(result.key_handle.into()).unwrap();
+        context.flush_context(parent_handle.into()).unwrap();
+        result.out_public.marshall().unwrap()
+    }
+
+    #[test]
+    fn test_create_loaded_primary_and_ordinary() {
+        let mut context = create_ctx_with_session();
+        let random_digest = context.get_random(16).unwrap();
+        let key_auth = Auth::try_from(random_digest.value().to_vec()).unwrap();
+
+        let primary = context
+            .create_loaded(
+                Hierarchy::Owner.into(),
+                Template::try_from(decryption_key_pub()).unwrap(),
+                Some(&key_auth),
+                None,
+            )
+            .unwrap();
+        let result = context
+            .create_loaded(
+                primary.key_handle.into(),
+                Template::try_from(signing_key_pub()).unwrap(),
+                Some(&key_auth),
+                None,
+            )
+            .unwrap();
+
+        let (public, _, _) = context.read_public(result.key_handle).unwrap();
+        assert_eq!(
+            public.marshall().unwrap(),
+            result.out_public.marshall().unwrap()
+        );
+        context.flush_context(result.key_handle.into()).unwrap();
+
+        // The returned private area can be loaded again under the parent.
+        let key_handle = context
+            .load(primary.key_handle, result.out_private, &result.out_public)
+            .unwrap();
+        context.flush_context(key_handle.into()).unwrap();
+        context.flush_context(primary.key_handle.into()).unwrap();
+    }
+
+    #[test]
+    fn test_create_loaded_derived() {
+        let mut context = create_ctx_with_session();
+        // The sensitive data of a derived key comes from its parent.
+        let object_attributes = ObjectAttributesBuilder::new()
+            .with_fixed_tpm(true)
+            .with_fixed_parent(true)
+            .with_sensitive_data_origin(false)
+            .with_user_with_auth(true)
+            .with_sign_encrypt(true)
+            .build()
+            .expect("Failed to build object attributes");
+        let public = PublicBuilder::new()
+            .with_public_algorithm(PublicAlgorithm::Ecc)
+            .with_name_hashing_algorithm(HashingAlgorithm::Sha256)
+            .with_object_attributes(object_attributes)
+            .with_ecc_parameters(
+                PublicEccParametersBuilder::new_unrestricted_signing_key(
+                    EccScheme::EcDsa(HashScheme::new(HashingAlgorithm::Sha256)),
+                    EccCurve::NistP256,
+                )
+                .build()
+                .unwrap(),
+            )
+            .with_ecc_unique_identifier(&EccPoint::default())
+            .build()
+            .unwrap();
+        let template = Template::new_derived(&public, b"label", b"context").unwrap();
+        let other_template = Template::new_derived(&public, b"label", b"other context").unwrap();
+
+        let derived = derive(&mut context, &template);
+        assert_eq!(derived, derive(&mut context, &template));
+        assert_ne!(derived, derive(&mut context, &other_template));
+    }
+}
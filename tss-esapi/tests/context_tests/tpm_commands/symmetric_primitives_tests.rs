@@ -187,6 +187,28 @@ mod test_hash {
         assert_eq!(ticket.hierarchy(), expected_hierarchy);
         assert_ne!(ticket.digest().len(), 0); // Should do some better checking of the digest
     }
+
+    #[test]
+    fn test_hash_in_every_hierarchy() {
+        // The hierarchy is given to the ESAPI as a different type of handle
+        // depending on the version of the TSS libraries.
+        let mut context = create_ctx_without_session();
+        let data = MaxBuffer::try_from(b"There is no spoon".to_vec()).unwrap();
+        for hierarchy in [
+            Hierarchy::Owner,
+            Hierarchy::Platform,
+            Hierarchy::Endorsement,
+            Hierarchy::Null,
+        ]
+        .iter()
+        {
+            let (_, ticket) = context
+                .hash(&data, HashingAlgorithm::Sha256, *hierarchy)
+                .unwrap();
+            assert_eq!(ticket.hierarchy(), *hierarchy);
+            assert_eq!(ticket.digest().is_empty(), *hierarchy == Hierarchy::Null);
+        }
+    }
 }
 
 mod test_hmac {
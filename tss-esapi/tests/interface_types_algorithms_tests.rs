@@ -53,6 +53,19 @@ mod test_hashing_algorithm_interface_type {
         test_conversion!(TPM2_ALG_SHA3_384, HashingAlgorithm::Sha3_384);
         test_conversion!(TPM2_ALG_SHA3_512, HashingAlgorithm::Sha3_512);
     }
+
+    #[test]
+    fn test_hashing_algorithm_digest_size() {
+        assert_eq!(HashingAlgorithm::Sha1.digest_size(), Some(20));
+        assert_eq!(HashingAlgorithm::Sha256.digest_size(), Some(32));
+        assert_eq!(HashingAlgorithm::Sha384.digest_size(), Some(48));
+        assert_eq!(HashingAlgorithm::Sha512.digest_size(), Some(64));
+        assert_eq!(HashingAlgorithm::Sm3_256.digest_size(), Some(32));
+        assert_eq!(HashingAlgorithm::Sha3_256.digest_size(), Some(32));
+        assert_eq!(HashingAlgorithm::Sha3_384.digest_size(), Some(48));
+        assert_eq!(HashingAlgorithm::Sha3_512.digest_size(), Some(64));
+        assert_eq!(HashingAlgorithm::Null.digest_size(), None);
+    }
 }
 
 mod test_keyed_hash_scheme_interface_type {
@@ -4,8 +4,8 @@ use std::convert::TryFrom;
 use tss_esapi::{
     handles::{AuthHandle, NvIndexHandle, ObjectHandle, PermanentTpmHandle, TpmHandle},
     interface_types::resource_handles::{
-        Clear, Enables, Endorsement, Hierarchy, HierarchyAuth, Lockout, NvAuth, Owner, Platform,
-        Provision,
+        Clear, Enables, Endorsement, Hierarchy, HierarchyAuth, HierarchyPolicy, Lockout, NvAuth,
+        Owner, Platform, Privacy, Provision,
     },
     tss2_esys::ESYS_TR,
 };
@@ -162,6 +162,57 @@ mod test_hierarchy_auth {
     }
 }
 
+mod test_hierarchy_policy {
+    use super::*;
+    #[test]
+    fn test_conversions() {
+        let test_conversion = |hierarchy_policy: HierarchyPolicy,
+                               tpm_rh: TpmHandle,
+                               esys_rh: ObjectHandle,
+                               name: &str| {
+            assert_eq!(ObjectHandle::from(hierarchy_policy), esys_rh);
+            assert_eq!(TpmHandle::from(hierarchy_policy), tpm_rh);
+            let from_esys_rh = HierarchyPolicy::try_from(esys_rh).unwrap_or_else(|_| {
+                panic!("Failed to create HierarchyPolicy from ESYS_TR_RH={}", name)
+            });
+            assert_eq!(from_esys_rh, hierarchy_policy);
+            assert_eq!(ObjectHandle::from(from_esys_rh), esys_rh);
+            assert_eq!(TpmHandle::from(from_esys_rh), tpm_rh);
+            let from_tpm_rh = HierarchyPolicy::try_from(tpm_rh).unwrap_or_else(|_| {
+                panic!("Failed to create HierarchyPolicy from TPM2_RH={}", name)
+            });
+            assert_eq!(from_tpm_rh, hierarchy_policy);
+            assert_eq!(ObjectHandle::from(from_tpm_rh), esys_rh);
+            assert_eq!(TpmHandle::from(from_tpm_rh), tpm_rh);
+        };
+
+        test_conversion(
+            HierarchyPolicy::Owner,
+            TpmHandle::Permanent(PermanentTpmHandle::Owner),
+            ObjectHandle::Owner,
+            "OWNER",
+        );
+        test_conversion(
+            HierarchyPolicy::Platform,
+            TpmHandle::Permanent(PermanentTpmHandle::Platform),
+            ObjectHandle::Platform,
+            "PLATFORM",
+        );
+        test_conversion(
+            HierarchyPolicy::Endorsement,
+            TpmHandle::Permanent(PermanentTpmHandle::Endorsement),
+            ObjectHandle::Endorsement,
+            "ENDORSEMENT",
+        );
+        test_conversion(
+            HierarchyPolicy::Lockout,
+            TpmHandle::Permanent(PermanentTpmHandle::Lockout),
+            ObjectHandle::Lockout,
+            "LOCKOUT",
+        );
+    }
+}
+
 mod test_platform {
     use super::*;
     #[test]
@@ -216,6 +267,23 @@ mod test_endorsement {
     }
 }
 
+mod test_privacy {
+    use super::*;
+    #[test]
+    fn test_conversions() {
+        assert_eq!(
+            AuthHandle::from(Privacy::Endorsement),
+            AuthHandle::Endorsement
+        );
+        assert_eq!(
+            Privacy::try_from(AuthHandle::Endorsement)
+                .expect("Failed to convert AuthHandle into Privacy"),
+            Privacy::Endorsement
+        );
+        assert!(Privacy::try_from(AuthHandle::Owner).is_err());
+    }
+}
+
 mod test_provision {
     use super::*;
     #[test]
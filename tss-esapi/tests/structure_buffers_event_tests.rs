@@ -0,0 +1,29 @@
+// Copyright 2021 Contributors to the Parsec project.
+// SPDX-License-Identifier: Apache-2.0
+use std::convert::TryFrom;
+use tss_esapi::structures::Event;
+use tss_esapi::tss2_esys::TPM2B_EVENT;
+
+mod test_event {
+    use super::*;
+
+    #[test]
+    fn test_max_sized_data() {
+        let _ = Event::try_from([0xff; Event::MAX_SIZE].to_vec()).unwrap();
+    }
+
+    #[test]
+    fn test_to_large_data() {
+        let _ = Event::try_from([0xff; Event::MAX_SIZE + 1].to_vec()).unwrap_err();
+    }
+
+    #[test]
+    fn test_conversion() {
+        let expected = Event::try_from(vec![1, 2, 3, 4]).unwrap();
+        let tss_event = TPM2B_EVENT::from(expected.clone());
+        assert_eq!(tss_event.size, 4);
+        assert_eq!(tss_event.buffer[..4], [1, 2, 3, 4]);
+        let actual = Event::try_from(tss_event).unwrap();
+        assert_eq!(expected, actual);
+    }
+}
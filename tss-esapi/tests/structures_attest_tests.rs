@@ -0,0 +1,214 @@
+// Copyright 2021 Contributors to the Parsec project.
+// SPDX-License-Identifier: Apache-2.0
+use std::convert::TryFrom;
+use tss_esapi::{
+    constants::StructureTag,
+    interface_types::algorithm::HashingAlgorithm,
+    structures::{Attest, AttestInfo},
+    tss2_esys::TPM2B_ATTEST,
+};
+
+mod test_attest {
+    use super::*;
+
+    const QUALIFIED_SIGNER: [u8; 34] = [
+        0x00, 0x0b, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d,
+        0x0e, 0x0f, 0x10, 0x11, 0x12, 0x13, 0x14, 0x15, 0x16, 0x17, 0x18, 0x19, 0x1a, 0x1b, 0x1c,
+        0x1d, 0x1e, 0x1f, 0x20,
+    ];
+
+    /// Returns a marshalled TPMS_ATTEST of the given type, holding the
+    /// given marshalled attested data
+    fn marshalled_attest(attestation_type: [u8; 2], attested: &[u8]) -> Vec<u8> {
+        let mut data = vec![0xff, 0x54, 0x43, 0x47]; // magic
+        data.extend_from_slice(&attestation_type);
+        data.extend_from_slice(&[0x00, 0x22]); // qualifiedSigner.size
+        data.extend_from_slice(&QUALIFIED_SIGNER);
+        data.extend_from_slice(&[
+            0x00, 0x04, 0xde, 0xad, 0xbe, 0xef, // extraData
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x01, 0xe2, 0x40, // clockInfo.clock
+            0x00, 0x00, 0x00, 0x05, // clockInfo.resetCount
+            0x00, 0x00, 0x00, 0x02, // clockInfo.restartCount
+            0x01, // clockInfo.safe
+            0x20, 0x19, 0x10, 0x23, 0x00, 0x16, 0x36, 0x36, // firmwareVersion
+        ]);
+        data.extend_from_slice(attested);
+        data
+    }
+
+    fn marshalled_quote() -> Vec<u8> {
+        let mut attested = vec![
+            0x00, 0x00, 0x00, 0x01, // pcrSelect.count
+            0x00, 0x0b, 0x03, 0x01, 0x00, 0x00, // pcrSelect.pcrSelections[0]
+            0x00, 0x20, // pcrDigest.size
+        ];
+        attested.extend_from_slice(&[0xaa; 32]);
+        // TPM2_ST_ATTEST_QUOTE
+        marshalled_attest([0x80, 0x18], &attested)
+    }
+
+    /// Returns a marshalled TPM2B_NAME holding a SHA-256 name
+    fn marshalled_name(fill: u8) -> Vec<u8> {
+        let mut name = vec![0x00, 0x22, 0x00, 0x0b];
+        name.extend_from_slice(&[fill; 32]);
+        name
+    }
+
+    /// Returns a marshalled TPM2B_DIGEST holding a SHA-256 digest
+    fn marshalled_digest(fill: u8) -> Vec<u8> {
+        let mut digest = vec![0x00, 0x20];
+        digest.extend_from_slice(&[fill; 32]);
+        digest
+    }
+
+    /// Checks that `data` is unmarshalled into the expected type of
+    /// attestation, and marshalled back into the same bytes
+    fn assert_round_trip(data: Vec<u8>, attestation_type: StructureTag) -> Attest {
+        let attest = Attest::unmarshall(&data).expect("Failed to unmarshall attest");
+        assert_eq!(attest.attestation_type(), attestation_type);
+        assert_eq!(attest.attested().tag(), attestation_type);
+        assert_eq!(attest.marshall().expect("Failed to marshall attest"), data);
+
+        let tpm2b_attest = TPM2B_ATTEST::try_from(attest.clone())
+            .expect("Failed to convert Attest into TPM2B_ATTEST");
+        assert_eq!(
+            attest,
+            Attest::try_from(tpm2b_attest).expect("Failed to convert TPM2B_ATTEST into Attest")
+        );
+        attest
+    }
+
+    #[test]
+    fn test_unmarshall_quote() {
+        let attest = Attest::unmarshall(&marshalled_quote()).expect("Failed to unmarshall quote");
+        assert_eq!(attest.attestation_type(), StructureTag::AttestQuote);
+        assert_eq!(attest.qualified_signer().value(), &QUALIFIED_SIGNER[..]);
+        assert_eq!(attest.extra_data().value(), &[0xde, 0xad, 0xbe, 0xef]);
+        assert_eq!(attest.clock_info().clock(), 123456);
+        assert_eq!(attest.clock_info().reset_count(), 5);
+        assert_eq!(attest.clock_info().restart_count(), 2);
+        assert!(attest.clock_info().safe());
+        assert_eq!(attest.firmware_version(), 0x2019102300163636);
+        match attest.attested() {
+            AttestInfo::Quote(quote_info) => {
+                assert_eq!(quote_info.pcr_digest().value(), &[0xaa; 32]);
+                assert_eq!(quote_info.pcr_selection().len(), 1);
+                assert_eq!(
+                    quote_info.pcr_selection().get_selections()[0].hashing_algorithm(),
+                    HashingAlgorithm::Sha256
+                );
+            }
+            _ => panic!("Attested data was not a quote"),
+        }
+    }
+
+    #[test]
+    fn test_marshall_is_lossless() {
+        let expected = marshalled_quote();
+        let attest = Attest::unmarshall(&expected).expect("Failed to unmarshall quote");
+        let actual = attest.marshall().expect("Failed to marshall quote");
+        assert_eq!(expected, actual);
+
+        let tpm2b_attest = TPM2B_ATTEST::try_from(attest.clone())
+            .expect("Failed to convert Attest into TPM2B_ATTEST");
+        assert_eq!(tpm2b_attest.size as usize, expected.len());
+        assert_eq!(
+            attest,
+            Attest::try_from(tpm2b_attest).expect("Failed to convert TPM2B_ATTEST into Attest")
+        );
+    }
+
+    #[test]
+    fn test_unmarshall_invalid_magic() {
+        let mut data = marshalled_quote();
+        data[0] = 0x00;
+        let _ = Attest::unmarshall(&data).unwrap_err();
+    }
+
+    #[test]
+    fn test_unmarshall_trailing_data() {
+        let mut data = marshalled_quote();
+        data.push(0x00);
+        let _ = Attest::unmarshall(&data).unwrap_err();
+    }
+
+    #[test]
+    fn test_certify_round_trip() {
+        let mut attested = marshalled_name(0x11); // name
+        attested.extend(marshalled_name(0x22)); // qualifiedName
+        let attest = assert_round_trip(
+            marshalled_attest([0x80, 0x17], &attested),
+            StructureTag::AttestCertify,
+        );
+        match attest.attested() {
+            AttestInfo::Certify(certify_info) => {
+                assert_eq!(&certify_info.name().value()[2..], &[0x11; 32]);
+                assert_eq!(&certify_info.qualified_name().value()[2..], &[0x22; 32]);
+            }
+            _ => panic!("Attested data was not a certification"),
+        }
+    }
+
+    #[test]
+    fn test_creation_round_trip() {
+        let mut attested = marshalled_name(0x33); // objectName
+        attested.extend(marshalled_digest(0x44)); // creationHash
+        let _ = assert_round_trip(
+            marshalled_attest([0x80, 0x1a], &attested),
+            StructureTag::AttestCreation,
+        );
+    }
+
+    #[test]
+    fn test_time_round_trip() {
+        let attested = [
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x30, 0x39, // time.time
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x30, 0x3a, // time.clockInfo.clock
+            0x00, 0x00, 0x00, 0x07, // time.clockInfo.resetCount
+            0x00, 0x00, 0x00, 0x00, // time.clockInfo.restartCount
+            0x00, // time.clockInfo.safe
+            0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x02, // firmwareVersion
+        ];
+        let _ = assert_round_trip(
+            marshalled_attest([0x80, 0x19], &attested),
+            StructureTag::AttestTime,
+        );
+    }
+
+    #[test]
+    fn test_session_audit_round_trip() {
+        let mut attested = vec![0x01]; // exclusiveSession
+        attested.extend(marshalled_digest(0x55)); // sessionDigest
+        let _ = assert_round_trip(
+            marshalled_attest([0x80, 0x16], &attested),
+            StructureTag::AttestSessionAudit,
+        );
+    }
+
+    #[test]
+    fn test_command_audit_round_trip() {
+        let mut attested = vec![
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x2a, // auditCounter
+            0x00, 0x0b, // digestAlg
+        ];
+        attested.extend(marshalled_digest(0x66)); // auditDigest
+        attested.extend(marshalled_digest(0x77)); // commandDigest
+        let _ = assert_round_trip(
+            marshalled_attest([0x80, 0x15], &attested),
+            StructureTag::AttestCommandAudit,
+        );
+    }
+
+    #[test]
+    fn test_nv_round_trip() {
+        let mut attested = marshalled_name(0x88); // indexName
+        attested.extend_from_slice(&[
+            0x00, 0x10, // offset
+            0x00, 0x03, 0x01, 0x02, 0x03, // nvContents
+        ]);
+        let _ = assert_round_trip(
+            marshalled_attest([0x80, 0x14], &attested),
+            StructureTag::AttestNv,
+        );
+    }
+}
@@ -0,0 +1,52 @@
+// Copyright 2021 Contributors to the Parsec project.
+// SPDX-License-Identifier: Apache-2.0
+use tss_esapi::{
+    interface_types::{
+        algorithm::{HashingAlgorithm, RsaSchemeAlgorithm},
+        key_bits::RsaKeyBits,
+    },
+    structures::{Public, RsaExponent, RsaScheme},
+    utils, Error, WrapperErrorKind,
+};
+
+mod test_public {
+    use super::*;
+
+    fn signing_key_pub() -> Public {
+        utils::create_unrestricted_signing_rsa_public(
+            RsaScheme::create(RsaSchemeAlgorithm::RsaSsa, Some(HashingAlgorithm::Sha256))
+                .expect("Failed to create RSA scheme"),
+            RsaKeyBits::Rsa2048,
+            RsaExponent::default(),
+        )
+        .expect("Failed to create an unrestricted signing rsa public structure")
+    }
+
+    #[test]
+    fn test_marshall_unmarshall() {
+        let marshalled = signing_key_pub()
+            .marshall()
+            .expect("Failed to marshall public area");
+        // type (TPM2_ALG_RSA) and nameAlg (TPM2_ALG_SHA256)
+        assert_eq!(marshalled[..4], [0x00, 0x01, 0x00, 0x0b]);
+
+        let public = Public::unmarshall(&marshalled).expect("Failed to unmarshall public area");
+        assert_eq!(public.name_hashing_algorithm(), HashingAlgorithm::Sha256);
+        assert_eq!(
+            public.marshall().expect("Failed to marshall public area"),
+            marshalled
+        );
+    }
+
+    #[test]
+    fn test_unmarshall_trailing_bytes() {
+        let mut marshalled = signing_key_pub()
+            .marshall()
+            .expect("Failed to marshall public area");
+        marshalled.push(0x00);
+        assert_eq!(
+            Public::unmarshall(&marshalled).unwrap_err(),
+            Error::WrapperError(WrapperErrorKind::WrongParamSize)
+        );
+    }
+}
@@ -0,0 +1,114 @@
+// Copyright 2021 Contributors to the Parsec project.
+// SPDX-License-Identifier: Apache-2.0
+use std::convert::TryFrom;
+use tss_esapi::{
+    attributes::ObjectAttributesBuilder,
+    interface_types::{
+        algorithm::{HashingAlgorithm, PublicAlgorithm, RsaSchemeAlgorithm},
+        ecc::EccCurve,
+        key_bits::RsaKeyBits,
+    },
+    structures::{
+        EccPoint, EccScheme, HashScheme, Public, PublicBuilder, PublicEccParametersBuilder,
+        PublicRsaParametersBuilder, RsaExponent, RsaScheme, Template,
+    },
+    utils::create_unrestricted_signing_ecc_public,
+    Error, WrapperErrorKind,
+};
+
+/// Returns the public area of an ECC signing key which can be derived
+fn derived_key_public() -> Public {
+    let object_attributes = ObjectAttributesBuilder::new()
+        .with_fixed_tpm(true)
+        .with_fixed_parent(true)
+        .with_sensitive_data_origin(false)
+        .with_user_with_auth(true)
+        .with_sign_encrypt(true)
+        .build()
+        .unwrap();
+
+    PublicBuilder::new()
+        .with_public_algorithm(PublicAlgorithm::Ecc)
+        .with_name_hashing_algorithm(HashingAlgorithm::Sha256)
+        .with_object_attributes(object_attributes)
+        .with_ecc_parameters(
+            PublicEccParametersBuilder::new_unrestricted_signing_key(
+                EccScheme::EcDsa(HashScheme::new(HashingAlgorithm::Sha256)),
+                EccCurve::NistP256,
+            )
+            .build()
+            .unwrap(),
+        )
+        .with_ecc_unique_identifier(&EccPoint::default())
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn derived_template() {
+    let public = derived_key_public();
+    let marshalled = public.marshall().unwrap();
+    let template = Template::new_derived(&public, &[1, 2], &[3]).unwrap();
+
+    // The empty ECC point is replaced by the label and context.
+    let (parameters, unique) = marshalled.split_at(marshalled.len() - 4);
+    assert_eq!(unique, &[0, 0, 0, 0]);
+    assert_eq!(&template[..parameters.len()], parameters);
+    assert_eq!(&template[parameters.len()..], &[0, 2, 1, 2, 0, 1, 3]);
+
+    assert_eq!(Template::try_from(public).unwrap().value(), &marshalled[..]);
+}
+
+#[test]
+fn derived_template_with_too_long_label() {
+    assert_eq!(
+        Template::new_derived(&derived_key_public(), &[0; 33], &[]).unwrap_err(),
+        Error::WrapperError(WrapperErrorKind::WrongParamSize)
+    );
+}
+
+#[test]
+fn derived_template_with_sensitive_data_origin() {
+    let public = create_unrestricted_signing_ecc_public(
+        EccScheme::EcDsa(HashScheme::new(HashingAlgorithm::Sha256)),
+        EccCurve::NistP256,
+    )
+    .unwrap();
+    assert_eq!(
+        Template::new_derived(&public, b"label", b"context").unwrap_err(),
+        Error::WrapperError(WrapperErrorKind::InvalidParam)
+    );
+}
+
+#[test]
+fn derived_rsa_template() {
+    let object_attributes = ObjectAttributesBuilder::new()
+        .with_fixed_tpm(true)
+        .with_fixed_parent(true)
+        .with_sensitive_data_origin(false)
+        .with_user_with_auth(true)
+        .with_sign_encrypt(true)
+        .build()
+        .unwrap();
+    let public = PublicBuilder::new()
+        .with_public_algorithm(PublicAlgorithm::Rsa)
+        .with_name_hashing_algorithm(HashingAlgorithm::Sha256)
+        .with_object_attributes(object_attributes)
+        .with_rsa_parameters(
+            PublicRsaParametersBuilder::new_unrestricted_signing_key(
+                RsaScheme::create(RsaSchemeAlgorithm::RsaSsa, Some(HashingAlgorithm::Sha256))
+                    .unwrap(),
+                RsaKeyBits::Rsa2048,
+                RsaExponent::default(),
+            )
+            .build()
+            .unwrap(),
+        )
+        .with_rsa_unique_identifier(&Default::default())
+        .build()
+        .unwrap();
+    assert_eq!(
+        Template::new_derived(&public, b"label", b"context").unwrap_err(),
+        Error::WrapperError(WrapperErrorKind::InvalidParam)
+    );
+}
@@ -1,7 +1,16 @@
 // Copyright 2020 Contributors to the Parsec project.
 // SPDX-License-Identifier: Apache-2.0
 
-use tss_esapi::constants::CapabilityType;
+use std::convert::TryFrom;
+use tss_esapi::{
+    constants::{
+        tss::{TPM2_CAP_ECC_CURVES, TPM2_ECC_NIST_P256, TPM2_ECC_NIST_P384},
+        CapabilityType,
+    },
+    interface_types::ecc::EccCurve,
+    structures::CapabilityData,
+    tss2_esys::{TPML_ECC_CURVE, TPMS_CAPABILITY_DATA, TPMU_CAPABILITIES},
+};
 
 mod common;
 use common::create_ctx_without_session;
@@ -85,8 +94,41 @@ mod test_capabs {
     fn test_ecc_curves() {
         let mut context = create_ctx_without_session();
 
-        let (_capabs, _more) = context
+        let (capabs, _more) = context
             .get_capability(CapabilityType::ECCCurves, 0, 80)
             .unwrap();
+        match capabs {
+            CapabilityData::ECCCurves(curves) => assert!(curves.contains(&EccCurve::NistP256)),
+            _ => panic!("Expected ECC curves"),
+        }
+    }
+}
+
+mod test_conversions {
+    use super::*;
+
+    #[test]
+    fn test_ecc_curves_with_unknown_curve() {
+        let mut ecc_curves = TPML_ECC_CURVE {
+            count: 3,
+            ..Default::default()
+        };
+        ecc_curves.eccCurves[0] = TPM2_ECC_NIST_P256;
+        ecc_curves.eccCurves[1] = 0x7FFF;
+        ecc_curves.eccCurves[2] = TPM2_ECC_NIST_P384;
+
+        let capability_data = CapabilityData::try_from(TPMS_CAPABILITY_DATA {
+            capability: TPM2_CAP_ECC_CURVES,
+            data: TPMU_CAPABILITIES {
+                eccCurves: ecc_curves,
+            },
+        })
+        .unwrap();
+        match capability_data {
+            CapabilityData::ECCCurves(curves) => {
+                assert_eq!(curves, vec![EccCurve::NistP256, EccCurve::NistP384])
+            }
+            _ => panic!("Expected ECC curves"),
+        }
     }
 }
@@ -0,0 +1,59 @@
+// Copyright 2021 Contributors to the Parsec project.
+// SPDX-License-Identifier: Apache-2.0
+use std::convert::TryFrom;
+use tss_esapi::{
+    interface_types::algorithm::{HashingAlgorithm, SignatureSchemeAlgorithm},
+    structures::{PublicKeyRsa, RsaSignature, Signature},
+    Error, WrapperErrorKind,
+};
+
+mod test_signature {
+    use super::*;
+
+    fn marshalled_rsa_ssa_signature() -> Vec<u8> {
+        vec![
+            0x00, 0x14, // sigAlg (TPM2_ALG_RSASSA)
+            0x00, 0x0b, // hash (TPM2_ALG_SHA256)
+            0x00, 0x04, 0xde, 0xad, 0xbe, 0xef, // sig
+        ]
+    }
+
+    #[test]
+    fn test_unmarshall() {
+        let signature = Signature::unmarshall(&marshalled_rsa_ssa_signature())
+            .expect("Failed to unmarshall signature");
+        assert_eq!(signature.algorithm(), SignatureSchemeAlgorithm::RsaSsa);
+        if let Signature::RsaSsa(rsa_signature) = signature {
+            assert_eq!(rsa_signature.hashing_algorithm(), HashingAlgorithm::Sha256);
+            assert_eq!(rsa_signature.signature().value(), [0xde, 0xad, 0xbe, 0xef]);
+        } else {
+            panic!("Unexpected signature type");
+        }
+    }
+
+    #[test]
+    fn test_marshall() {
+        let signature = Signature::RsaSsa(
+            RsaSignature::create(
+                HashingAlgorithm::Sha256,
+                PublicKeyRsa::try_from(vec![0xde, 0xad, 0xbe, 0xef])
+                    .expect("Failed to create RSA signature buffer"),
+            )
+            .expect("Failed to create RSA signature"),
+        );
+        assert_eq!(
+            signature.marshall().expect("Failed to marshall signature"),
+            marshalled_rsa_ssa_signature()
+        );
+    }
+
+    #[test]
+    fn test_unmarshall_trailing_bytes() {
+        let mut data = marshalled_rsa_ssa_signature();
+        data.push(0x00);
+        assert_eq!(
+            Signature::unmarshall(&data).unwrap_err(),
+            Error::WrapperError(WrapperErrorKind::WrongParamSize)
+        );
+    }
+}
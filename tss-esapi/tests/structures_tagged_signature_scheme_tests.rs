@@ -0,0 +1,102 @@
+// Copyright 2021 Contributors to the Parsec project.
+// SPDX-License-Identifier: Apache-2.0
+use std::convert::TryFrom;
+use tss_esapi::{
+    constants::tss::{
+        TPM2_ALG_ECDAA, TPM2_ALG_HMAC, TPM2_ALG_NULL, TPM2_ALG_RSASSA, TPM2_ALG_SHA256,
+        TPM2_ALG_SHA384,
+    },
+    interface_types::algorithm::{HashingAlgorithm, SignatureSchemeAlgorithm},
+    structures::{HashScheme, HmacScheme, SignatureScheme},
+    tss2_esys::{TPMS_SCHEME_ECDAA, TPMT_SIG_SCHEME, TPMU_SIG_SCHEME},
+};
+
+mod test_signature_scheme {
+    use super::*;
+
+    #[test]
+    fn test_rsa_ssa_conversions() {
+        let tpmt_sig_scheme = TPMT_SIG_SCHEME::from(SignatureScheme::RsaSsa(HashScheme::new(
+            HashingAlgorithm::Sha256,
+        )));
+        assert_eq!(tpmt_sig_scheme.scheme, TPM2_ALG_RSASSA);
+        assert_eq!(
+            unsafe { tpmt_sig_scheme.details.rsassa }.hashAlg,
+            TPM2_ALG_SHA256
+        );
+
+        match SignatureScheme::try_from(tpmt_sig_scheme).unwrap() {
+            SignatureScheme::RsaSsa(hash_scheme) => {
+                assert_eq!(hash_scheme, HashScheme::new(HashingAlgorithm::Sha256))
+            }
+            signature_scheme => panic!("Unexpected signature scheme {:?}", signature_scheme),
+        }
+    }
+
+    #[test]
+    fn test_ec_daa_conversions() {
+        let tpmt_sig_scheme = TPMT_SIG_SCHEME {
+            scheme: TPM2_ALG_ECDAA,
+            details: TPMU_SIG_SCHEME {
+                ecdaa: TPMS_SCHEME_ECDAA {
+                    hashAlg: TPM2_ALG_SHA384,
+                    count: 7,
+                },
+            },
+        };
+
+        let signature_scheme = SignatureScheme::try_from(tpmt_sig_scheme).unwrap();
+        match signature_scheme {
+            SignatureScheme::EcDaa(ec_daa_scheme) => {
+                assert_eq!(ec_daa_scheme.hashing_algorithm(), HashingAlgorithm::Sha384);
+                assert_eq!(ec_daa_scheme.count(), 7);
+            }
+            signature_scheme => panic!("Unexpected signature scheme {:?}", signature_scheme),
+        }
+
+        let tpmt_sig_scheme = TPMT_SIG_SCHEME::from(signature_scheme);
+        assert_eq!(tpmt_sig_scheme.scheme, TPM2_ALG_ECDAA);
+        assert_eq!(
+            unsafe { tpmt_sig_scheme.details.ecdaa }.hashAlg,
+            TPM2_ALG_SHA384
+        );
+        assert_eq!(unsafe { tpmt_sig_scheme.details.ecdaa }.count, 7);
+    }
+
+    #[test]
+    fn test_hmac_conversions() {
+        let tpmt_sig_scheme = TPMT_SIG_SCHEME::from(SignatureScheme::Hmac(HmacScheme::new(
+            HashingAlgorithm::Sha384,
+        )));
+        assert_eq!(tpmt_sig_scheme.scheme, TPM2_ALG_HMAC);
+        assert_eq!(
+            unsafe { tpmt_sig_scheme.details.hmac }.hashAlg,
+            TPM2_ALG_SHA384
+        );
+
+        match SignatureScheme::try_from(tpmt_sig_scheme).unwrap() {
+            SignatureScheme::Hmac(hmac_scheme) => {
+                assert_eq!(hmac_scheme, HmacScheme::new(HashingAlgorithm::Sha384))
+            }
+            signature_scheme => panic!("Unexpected signature scheme {:?}", signature_scheme),
+        }
+    }
+
+    #[test]
+    fn test_null_conversions() {
+        let tpmt_sig_scheme = TPMT_SIG_SCHEME::from(SignatureScheme::Null);
+        assert_eq!(tpmt_sig_scheme.scheme, TPM2_ALG_NULL);
+
+        let signature_scheme = SignatureScheme::try_from(tpmt_sig_scheme).unwrap();
+        assert_eq!(signature_scheme.algorithm(), SignatureSchemeAlgorithm::Null);
+    }
+
+    #[test]
+    fn test_invalid_scheme() {
+        let tpmt_sig_scheme = TPMT_SIG_SCHEME {
+            scheme: TPM2_ALG_SHA256,
+            details: Default::default(),
+        };
+        assert!(SignatureScheme::try_from(tpmt_sig_scheme).is_err());
+    }
+}